@@ -3,26 +3,231 @@
 
 /* auto-generated by NAPI-RS */
 
+export const enum Severity {
+  /** The generated type is less precise than the schema */
+  Warning = 'warning',
+  /** The document is invalid */
+  Error = 'error'
+}
+export interface Diagnostic {
+  /** JSON pointer of the schema or operation, like `#/components/schemas/User/properties/id` */
+  pointer: string
+  severity: Severity
+  /** Stable identifier, like `unresolved-reference` */
+  code: string
+  message: string
+}
+export interface MockOptions {
+  /** Seed of the pseudo-random values, the same seed always gives the same values */
+  seed?: number
+  /**
+   * Nesting past which only required properties and the minimum number of items are generated,
+   * default to 4
+   */
+  maxDepth?: number
+}
 export interface SchemaTypeOptions {
   name?: string
   preferUnknownOverAny?: boolean
   preferInterfaceOverType?: boolean
+  mergeAllOf?: boolean
+  /** Default to `source` */
+  sort?: SortOrder
+  format?: FormatOptions
+  /** Fail with every lossy conversion instead of degrading the type */
+  strict?: boolean
+  /** Append an `is{name}` type guard function, requires `name` */
+  typeGuard?: boolean
+}
+export interface SchemaTypeOutput {
+  tsType: string
+  diagnostics: Array<Diagnostic>
+}
+export const enum SortOrder {
+  /** Declaration order of the schema */
+  Source = 'source',
+  Alphabetical = 'alphabetical'
+}
+export const enum MemberSeparator {
+  Semicolon = 'semicolon',
+  Comma = 'comma',
+  None = 'none'
+}
+export interface FormatOptions {
+  tabWidth?: number
+  useTabs?: boolean
+  semi?: boolean
+  singleQuote?: boolean
+  /** Default to `semicolon` */
+  memberSeparator?: MemberSeparator
+  trailingComma?: boolean
+  /** Unions longer than this are printed one member per line */
+  printWidth?: number
+}
+export interface FilterOptions {
+  includeTags?: Array<string>
+  excludeTags?: Array<string>
+  /** Path globs, `*` matches within a path segment and `**` across segments */
+  includePaths?: Array<string>
+  excludePaths?: Array<string>
+  includeMethods?: Array<string>
+  excludeMethods?: Array<string>
+  includeOperationIds?: Array<string>
+  excludeOperationIds?: Array<string>
+  excludeDeprecated?: boolean
+}
+export interface OpenApiOptions {
+  mergeAllOf?: boolean
+  /**
+   * Default to `source`, applied to paths, methods, components, properties, status codes and
+   * media types
+   */
+  sort?: SortOrder
+  format?: FormatOptions
+  /** Only keep the matching operations, and the components they use */
+  filter?: FilterOptions
+  /** Remove the components no operation uses, default to true with a `filter` and false otherwise */
+  treeShake?: boolean
+  /** Fail with every lossy conversion instead of degrading the types */
+  strict?: boolean
+  /** Generate an `is{Name}` type guard function for every schema component */
+  typeGuards?: boolean
+  /** Generate a mock JSON body for every response of the operations */
+  mocks?: MockOptions
 }
 export interface OpenApiOutput {
   paths: Array<OpenApiPath>
   components: Array<OpenApiComponent>
+  /** Names of the components removed by `treeShake` */
+  removedComponents: Array<string>
+  /** Warnings and degradations of the conversion */
+  diagnostics: Array<Diagnostic>
+}
+export const enum ComponentKind {
+  Schema = 'schema',
+  Response = 'response',
+  Parameter = 'parameter',
+  RequestBody = 'requestBody',
+  Header = 'header',
+  SecurityScheme = 'securityScheme'
 }
 export interface OpenApiComponent {
   name: string
+  kind: ComponentKind
   tsType: string
+  /** `is{Name}` type guard function of schemas, with the `typeGuards` option */
+  typeGuard?: string
 }
 export interface OpenApiPath {
   path: string
   method: string
+  operationId?: string
+  /** Unique camelCase identifier, from `operationId` or synthesized from the method and path */
+  name: string
+  summary?: string
+  description?: string
+  tags: Array<string>
+  deprecated: boolean
   queryParameters?: string
   pathParameters?: string
+  headerParameters?: string
+  cookieParameters?: string
+  /** Type of the JSON request body, or of the first media type when there is no JSON one */
   requestBody?: string
+  /** Request body type by media type */
+  requestBodyContent: Record<string, string>
+  /** Response type by status code (`200`, `2XX` or `default`), picked like `requestBody` */
   responses: Record<string, string>
+  /** Union of the `2XX` response types, or of the `default` one when there is no `2XX` response */
+  successResponse?: string
+  /**
+   * Union of the `4XX`, `5XX` and `default` response types, `default` being the success when
+   * there is no `2XX` response
+   */
+  errorResponse?: string
+  /** Response types by status code, then by media type */
+  responseContent: Record<string, Record<string, string>>
+  /** Response headers type by status code */
+  responseHeaders: Record<string, string>
+  /** JSON response body by status code, from the valid examples or generated, with the `mocks` option */
+  mockResponses: Record<string, string>
+}
+export interface MswOptions {
+  /** Module generated by `openApiToModule` with the same options, default to `./types` */
+  typesModule?: string
+  /** Prefix of the handler paths, like `https://api.example.com` or `*` for any origin */
+  baseUrl?: string
+}
+export interface ServerOptions {
+  /** Module generated by `openApiToModule` with the same options, default to `./types` */
+  typesModule?: string
+  /** Also generate a `{Name}Handler` Express request handler type for every operation */
+  express?: boolean
+}
+export interface OpenApiChange {
+  /**
+   * JSON pointer in the new document, or in the old one for removals. Changes of a referenced
+   * schema point to its definition, like `#/components/schemas/User/properties/id`
+   */
+  pointer: string
+  /** Operation of the change, like `GET /users/{id}` */
+  operation?: string
+  /** Stable identifier, like `property-removed` */
+  code: string
+  /** True when clients of the old document may break */
+  breaking: boolean
+  message: string
+}
+export interface OpenApiDiff {
+  /** True when at least one change is breaking */
+  breaking: boolean
+  changes: Array<OpenApiChange>
+}
+export interface SchemaChange {
+  /**
+   * JSON pointer in the new schema, or in the old one for removals. Changes of a referenced schema
+   * point to its definition
+   */
+  pointer: string
+  /** Stable identifier, like `property-removed` */
+  code: string
+  message: string
+  /** Data valid against the old schema is valid against the new one */
+  backward: boolean
+  /** Data valid against the new schema is valid against the old one */
+  forward: boolean
+}
+/** Overall compatibility of the changes, like schema registries. */
+export const enum Compatibility {
+  /** Backward and forward */
+  Full = 'full',
+  /** Data valid against the old schema is valid against the new one */
+  Backward = 'backward',
+  /** Data valid against the new schema is valid against the old one */
+  Forward = 'forward',
+  None = 'none'
+}
+export interface SchemaDiffOutput {
+  compatibility: Compatibility
+  changes: Array<SchemaChange>
+}
+export interface ValidationError {
+  /** JSON pointer of the invalid value, like `/items/0/id`, empty for the value itself */
+  instancePath: string
+  /** JSON pointer of the failing keyword, like `#/properties/items/items/properties/id/type` */
+  schemaPath: string
+  /** Failing keyword, like `required` */
+  keyword: string
+  message: string
 }
-export declare function openApiToTypes(openApiInput: object): OpenApiOutput
+export declare function openApiToTypes(openApiInput: object, options?: OpenApiOptions | undefined | null): OpenApiOutput
+export declare function openApiToModule(openApiInput: object, options?: OpenApiOptions | undefined | null): string
+export declare function openApiToMswHandlers(openApiInput: object, options?: OpenApiOptions | undefined | null, mswOptions?: MswOptions | undefined | null): string
+export declare function openApiToServerTypes(openApiInput: object, options?: OpenApiOptions | undefined | null, serverOptions?: ServerOptions | undefined | null): string
+export declare function validateOpenApi(openApiInput: object): Array<Diagnostic>
+export declare function diffOpenApi(oldOpenApiInput: object, newOpenApiInput: object): OpenApiDiff
+export declare function diffSchemas(oldSchemaInput: object, newSchemaInput: object): SchemaDiffOutput
+export declare function validate(schemaInput: object, value: unknown, componentsInput?: object | undefined | null): Array<ValidationError>
+export declare function mockFromSchema(schemaInput: object, options?: MockOptions | undefined | null, componentsInput?: object | undefined | null): unknown
 export declare function schemaToType(schemaInput: object, options?: SchemaTypeOptions | undefined | null): string
+export declare function schemaToTypeWithDiagnostics(schemaInput: object, options?: SchemaTypeOptions | undefined | null): SchemaTypeOutput
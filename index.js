@@ -310,7 +310,21 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { openApiToTypes, schemaToType } = nativeBinding
+const { Severity, SortOrder, MemberSeparator, ComponentKind, Compatibility, openApiToTypes, openApiToModule, openApiToMswHandlers, openApiToServerTypes, validateOpenApi, diffOpenApi, diffSchemas, validate, mockFromSchema, schemaToType, schemaToTypeWithDiagnostics } = nativeBinding
 
+module.exports.Severity = Severity
+module.exports.SortOrder = SortOrder
+module.exports.MemberSeparator = MemberSeparator
+module.exports.ComponentKind = ComponentKind
+module.exports.Compatibility = Compatibility
 module.exports.openApiToTypes = openApiToTypes
+module.exports.openApiToModule = openApiToModule
+module.exports.openApiToMswHandlers = openApiToMswHandlers
+module.exports.openApiToServerTypes = openApiToServerTypes
+module.exports.validateOpenApi = validateOpenApi
+module.exports.diffOpenApi = diffOpenApi
+module.exports.diffSchemas = diffSchemas
+module.exports.validate = validate
+module.exports.mockFromSchema = mockFromSchema
 module.exports.schemaToType = schemaToType
+module.exports.schemaToTypeWithDiagnostics = schemaToTypeWithDiagnostics
@@ -0,0 +1,130 @@
+use napi_derive::napi;
+use std::cell::RefCell;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+#[napi(string_enum = "lowercase")]
+pub enum Severity {
+  /// The generated type is less precise than the schema
+  Warning,
+  /// The document is invalid
+  Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[napi(object)]
+pub struct Diagnostic {
+  /// JSON pointer of the schema or operation, like `#/components/schemas/User/properties/id`
+  pub pointer: String,
+  pub severity: Severity,
+  /// Stable identifier, like `unresolved-reference`
+  pub code: String,
+  pub message: String,
+}
+
+/// Collects diagnostics while converting, shared by reference through the conversion context.
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+  diagnostics: RefCell<Vec<Diagnostic>>,
+}
+
+impl Diagnostics {
+  fn push(&self, pointer: &str, severity: Severity, code: &str, message: String) {
+    self.extend([Diagnostic {
+      pointer: if pointer.is_empty() { "#" } else { pointer }.to_string(),
+      severity,
+      code: code.to_string(),
+      message,
+    }]);
+  }
+
+  pub fn error(&self, pointer: &str, code: &str, message: String) {
+    self.push(pointer, Severity::Error, code, message);
+  }
+
+  pub fn warning(&self, pointer: &str, code: &str, message: String) {
+    self.push(pointer, Severity::Warning, code, message);
+  }
+
+  pub fn extend(&self, new_diagnostics: impl IntoIterator<Item = Diagnostic>) {
+    let mut diagnostics = self.diagnostics.borrow_mut();
+
+    for diagnostic in new_diagnostics {
+      // Components referenced from several places are converted once per use.
+      if !diagnostics.contains(&diagnostic) {
+        diagnostics.push(diagnostic);
+      }
+    }
+  }
+
+  pub fn into_vec(self) -> Vec<Diagnostic> {
+    self.diagnostics.into_inner()
+  }
+}
+
+/// Appends segments to a JSON pointer, escaping `~` and `/`.
+pub fn pointer_join(pointer: &str, segments: &[&str]) -> String {
+  let mut joined = if pointer.is_empty() {
+    "#".to_string()
+  } else {
+    pointer.to_string()
+  };
+
+  for segment in segments {
+    joined.push('/');
+    joined.push_str(&segment.replace('~', "~0").replace('/', "~1"));
+  }
+
+  joined
+}
+
+/// Whether a pointer is `parent` or one of its descendants.
+pub fn is_pointer_within(pointer: &str, parent: &str) -> bool {
+  pointer
+    .strip_prefix(parent)
+    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Lossy conversions found in strict mode, all of them rather than the first one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrictModeError {
+  pub violations: Vec<Diagnostic>,
+}
+
+impl fmt::Display for StrictModeError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "Strict mode: {} lossy conversion{}",
+      self.violations.len(),
+      if self.violations.len() == 1 { "" } else { "s" }
+    )?;
+
+    for violation in &self.violations {
+      write!(
+        f,
+        "\n  {}: {} ({})",
+        violation.pointer, violation.message, violation.code
+      )?;
+    }
+
+    Ok(())
+  }
+}
+
+impl std::error::Error for StrictModeError {}
+
+/// Every warning degrades the generated type.
+pub fn ensure_lossless(diagnostics: &[Diagnostic]) -> Result<(), StrictModeError> {
+  let violations: Vec<Diagnostic> = diagnostics
+    .iter()
+    .filter(|diagnostic| diagnostic.severity == Severity::Warning)
+    .cloned()
+    .collect();
+
+  if violations.is_empty() {
+    Ok(())
+  } else {
+    Err(StrictModeError { violations })
+  }
+}
@@ -0,0 +1,531 @@
+use crate::json_schema_to_typescript::{resolve_schema_reference, SchemaContext, SchemaLike};
+use crate::json_schema_validate::{keywords, Keywords};
+
+use napi_derive::napi;
+use openapiv3::{Components, ReferenceOr, Schema};
+use serde_json::{Map, Number, Value};
+
+#[derive(Debug, Default, Clone)]
+#[napi(object)]
+pub struct MockOptions {
+  /// Seed of the pseudo-random values, the same seed always gives the same values
+  pub seed: Option<u32>,
+  /// Nesting past which only required properties and the minimum number of items are generated,
+  /// default to 4
+  pub max_depth: Option<u32>,
+}
+
+const DEFAULT_MAX_DEPTH: usize = 4;
+
+/// Nesting past the depth limit at which required recursive objects and arrays stop, as `null`: no
+/// finite value is valid against them.
+const DEPTH_MARGIN: usize = 8;
+
+const WORDS: [&str; 16] = [
+  "lorem",
+  "ipsum",
+  "dolor",
+  "sit",
+  "amet",
+  "consectetur",
+  "adipiscing",
+  "elit",
+  "sed",
+  "do",
+  "eiusmod",
+  "tempor",
+  "incididunt",
+  "labore",
+  "magna",
+  "aliqua",
+];
+
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// SplitMix64, small and good enough for mock data.
+struct Random(u64);
+
+impl Random {
+  fn next(&mut self) -> u64 {
+    self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = self.0;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+  }
+
+  /// Integer in `0..bound`, `0` when `bound` is `0`.
+  fn below(&mut self, bound: u64) -> u64 {
+    if bound == 0 {
+      0
+    } else {
+      self.next() % bound
+    }
+  }
+
+  fn ratio(&mut self) -> f64 {
+    (self.next() >> 11) as f64 / (1u64 << 53) as f64
+  }
+
+  fn coin(&mut self) -> bool {
+    self.next() & 1 == 1
+  }
+
+  fn pick<'v, T>(&mut self, values: &'v [T]) -> &'v T {
+    &values[self.below(values.len() as u64) as usize]
+  }
+}
+
+/// FNV-1a, to derive distinct seeds from a seed and a key like an operation.
+pub(crate) fn derive_seed(seed: Option<u32>, key: &str) -> u32 {
+  key
+    .bytes()
+    .fold(0x811c_9dc5u32 ^ seed.unwrap_or_default(), |hash, byte| {
+      (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// Generates values valid against schemas, following references into components, or to the
+/// root schema for `#`.
+pub(crate) struct Mocker<'a> {
+  context: SchemaContext<'a>,
+  root: Option<&'a Schema>,
+  random: Random,
+  max_depth: usize,
+  /// References being generated and their depth, a composition member reaching one of them again
+  /// at the same depth, like `{ "allOf": [{ "$ref": "#" }] }`, would never end and is skipped.
+  active: Vec<(&'a str, usize)>,
+}
+
+impl<'a> Mocker<'a> {
+  pub fn new(context: SchemaContext<'a>, root: Option<&'a Schema>, options: &MockOptions) -> Self {
+    Mocker {
+      context,
+      root,
+      random: Random(options.seed.unwrap_or_default() as u64),
+      max_depth: options
+        .max_depth
+        .map_or(DEFAULT_MAX_DEPTH, |max_depth| max_depth as usize),
+      active: vec![],
+    }
+  }
+
+  pub fn mock<T: SchemaLike>(&mut self, schema: &'a ReferenceOr<T>, depth: usize) -> Value {
+    match schema {
+      ReferenceOr::Item(schema) => self.mock_schema(schema.as_schema(), depth),
+      ReferenceOr::Reference { reference } => {
+        let resolved = if reference == "#" {
+          self.root
+        } else {
+          resolve_schema_reference(reference, &self.context)
+        };
+
+        let Some(resolved) = resolved else {
+          return Value::Null;
+        };
+
+        self.active.push((reference, depth));
+        let value = self.mock_schema(resolved, depth);
+        self.active.pop();
+        value
+      }
+    }
+  }
+
+  fn is_cycle(&self, schema: &ReferenceOr<Schema>, depth: usize) -> bool {
+    matches!(schema, ReferenceOr::Reference { reference } if self.active.contains(&(reference.as_str(), depth)))
+  }
+
+  pub fn mock_schema(&mut self, schema: &'a Schema, depth: usize) -> Value {
+    let schema_data = &schema.schema_data;
+
+    if let Some(example) = schema_data
+      .example
+      .as_ref()
+      .or(schema_data.default.as_ref())
+    {
+      return example.clone();
+    }
+
+    if schema_data.nullable && depth >= self.max_depth {
+      return Value::Null;
+    }
+
+    let keywords = keywords(schema);
+
+    if !keywords.enumeration.is_empty() {
+      let values: Vec<&Value> = keywords
+        .enumeration
+        .iter()
+        .filter(|value| !value.is_null())
+        .collect();
+
+      return match values.is_empty() {
+        true => Value::Null,
+        false => (*self.random.pick(&values)).clone(),
+      };
+    }
+
+    let is_past_margin = depth > self.max_depth + DEPTH_MARGIN;
+
+    if !keywords.all_of.is_empty() && !is_past_margin {
+      return self.mock_all_of(&keywords, depth);
+    }
+
+    let alternatives: Vec<&'a ReferenceOr<Schema>> = match keywords.one_of.is_empty() {
+      true => keywords.any_of,
+      false => keywords.one_of,
+    }
+    .iter()
+    .filter(|alternative| !self.is_cycle(alternative, depth))
+    .collect();
+
+    if !alternatives.is_empty() && !is_past_margin {
+      let alternative = *self.random.pick(&alternatives);
+      return self.mock(alternative, depth);
+    }
+
+    let typ = keywords.typ.or_else(|| {
+      if !keywords.properties.is_empty() || keywords.additional_properties.is_some() {
+        Some("object")
+      } else if keywords.items.is_some() {
+        Some("array")
+      } else if keywords.format.is_some() || keywords.min_length.is_some() {
+        Some("string")
+      } else if keywords.minimum.is_some() || keywords.maximum.is_some() {
+        Some("number")
+      } else {
+        None
+      }
+    });
+
+    match typ {
+      Some("string") => Value::String(self.mock_string(&keywords)),
+      Some("integer") => self.mock_number(&keywords, true),
+      Some("number") => self.mock_number(&keywords, false),
+      Some("boolean") => Value::Bool(self.random.coin()),
+      Some("array") if !is_past_margin => self.mock_array(&keywords, depth),
+      Some("object") if !is_past_margin => Value::Object(self.mock_object(&keywords, depth)),
+      _ => Value::Null,
+    }
+  }
+
+  /// Merges the object members, with the properties declared next to `allOf`.
+  fn mock_all_of(&mut self, keywords: &Keywords<'a>, depth: usize) -> Value {
+    let mut merged = self.mock_object(keywords, depth);
+
+    for schema in keywords.all_of {
+      if self.is_cycle(schema, depth) {
+        continue;
+      }
+
+      match self.mock(schema, depth) {
+        Value::Object(map) => merged.extend(map),
+        value if merged.is_empty() => return value,
+        _ => {}
+      }
+    }
+
+    Value::Object(merged)
+  }
+
+  fn mock_string(&mut self, keywords: &Keywords) -> String {
+    let random = &mut self.random;
+
+    match keywords.format {
+      Some("date") => return self.mock_date(),
+      Some("date-time") => {
+        let date = self.mock_date();
+        return format!("{}T{}Z", date, self.mock_time());
+      }
+      Some("time") => return format!("{}Z", self.mock_time()),
+      Some("email") => return format!("{}{}@example.com", random.pick(&WORDS), random.below(100)),
+      Some("uuid") => {
+        let hex = format!("{:016x}{:016x}", random.next(), random.next());
+        return format!(
+          "{}-{}-4{}-{}-{}",
+          &hex[0..8],
+          &hex[8..12],
+          &hex[13..16],
+          &hex[16..20],
+          &hex[20..32]
+        );
+      }
+      Some("uri" | "url") => return format!("https://example.com/{}", random.pick(&WORDS)),
+      Some("hostname") => return format!("{}.example.com", random.pick(&WORDS)),
+      Some("ipv4") => return format!("192.168.{}.{}", random.below(256), 1 + random.below(254)),
+      Some("ipv6") => return format!("2001:db8::{:x}", 1 + random.below(0xffff)),
+      Some("byte") => {
+        return (0..4 * (1 + random.below(4)))
+          .map(|_| *random.pick(BASE64_ALPHABET) as char)
+          .collect()
+      }
+      _ => {}
+    }
+
+    let min_length = keywords.min_length.unwrap_or(0);
+    let max_length = keywords.max_length.unwrap_or(usize::MAX).max(min_length);
+    let mut words: Vec<&str> = (0..1 + random.below(3))
+      .map(|_| *random.pick(&WORDS))
+      .collect();
+
+    while words.join(" ").chars().count() < min_length {
+      words.push(*random.pick(&WORDS));
+    }
+
+    words.join(" ").chars().take(max_length).collect()
+  }
+
+  fn mock_date(&mut self) -> String {
+    format!(
+      "{:04}-{:02}-{:02}",
+      2000 + self.random.below(30),
+      1 + self.random.below(12),
+      1 + self.random.below(28)
+    )
+  }
+
+  fn mock_time(&mut self) -> String {
+    format!(
+      "{:02}:{:02}:{:02}",
+      self.random.below(24),
+      self.random.below(60),
+      self.random.below(60)
+    )
+  }
+
+  /// Numbers within the bounds, default to `0..=100`, as multiples of `multipleOf`, or of `1` for
+  /// integers.
+  fn mock_number(&mut self, keywords: &Keywords, is_integer: bool) -> Value {
+    let lower = keywords
+      .minimum
+      .unwrap_or_else(|| keywords.maximum.map_or(0.0, |maximum| maximum - 100.0));
+    let upper = keywords.maximum.unwrap_or(lower + 100.0).max(lower);
+    let step = keywords
+      .multiple_of
+      .filter(|multiple_of| *multiple_of > 0.0)
+      .or(is_integer.then_some(1.0));
+
+    let Some(step) = step else {
+      let number = lower + self.random.ratio() * (upper - lower);
+      let rounded = (number * 100.0).round() / 100.0;
+      let is_excluded = keywords.exclusive_minimum && rounded <= lower
+        || keywords.exclusive_maximum && rounded >= upper;
+      let number = match is_excluded || rounded < lower || rounded > upper {
+        true => (lower + upper) / 2.0,
+        false => rounded,
+      };
+
+      return Number::from_f64(number).map_or(Value::Null, Value::Number);
+    };
+
+    let mut first = (lower / step).ceil();
+    let mut last = (upper / step).floor();
+
+    if keywords.exclusive_minimum && first * step <= lower {
+      first += 1.0;
+    }
+
+    if keywords.exclusive_maximum && last * step >= upper {
+      last -= 1.0;
+    }
+
+    let count = ((last - first).max(0.0) as u64).saturating_add(1);
+    let number = (first + self.random.below(count) as f64) * step;
+
+    if is_integer {
+      Value::from(number as i64)
+    } else {
+      Number::from_f64((number * 1e9).round() / 1e9).map_or(Value::Null, Value::Number)
+    }
+  }
+
+  /// Between one and three items, or the minimum past the depth limit.
+  fn mock_array(&mut self, keywords: &Keywords<'a>, depth: usize) -> Value {
+    let Some(items) = keywords.items else {
+      return Value::Array(vec![]);
+    };
+
+    let min_items = keywords
+      .min_items
+      .unwrap_or(if depth >= self.max_depth { 0 } else { 1 });
+    let max_items = keywords
+      .max_items
+      .unwrap_or(usize::MAX)
+      .min(min_items.max(3))
+      .max(min_items);
+    let length = match depth >= self.max_depth {
+      true => min_items,
+      false => min_items + self.random.below((max_items - min_items + 1) as u64) as usize,
+    };
+
+    let mut values: Vec<Value> = vec![];
+
+    for _ in 0..length {
+      let mut value = self.mock(items, depth + 1);
+
+      // Only a few attempts, small enums may not have enough distinct values
+      for _ in 0..16 {
+        if !keywords.unique_items || !values.contains(&value) {
+          break;
+        }
+
+        value = self.mock(items, depth + 1);
+      }
+
+      if keywords.unique_items && values.contains(&value) && values.len() >= min_items {
+        break;
+      }
+
+      values.push(value);
+    }
+
+    Value::Array(values)
+  }
+
+  /// Required properties, and randomly half of the optional ones before the depth limit.
+  fn mock_object(&mut self, keywords: &Keywords<'a>, depth: usize) -> Map<String, Value> {
+    let mut map = Map::new();
+
+    for (name, property) in &keywords.properties {
+      let is_required = keywords.required.contains(name);
+
+      if is_required || depth < self.max_depth && self.random.coin() {
+        let value = self.mock(*property, depth + 1);
+        map.insert(name.to_string(), value);
+      }
+    }
+
+    map
+  }
+}
+
+/// Deterministic mock value of a schema, `$ref`s like `#/components/schemas/User` are resolved in
+/// `components`.
+pub fn mock_from_schema(
+  schema: &Schema,
+  components: Option<&Components>,
+  options: &MockOptions,
+) -> Value {
+  let context = SchemaContext {
+    components,
+    ..Default::default()
+  };
+
+  Mocker::new(context, Some(schema), options).mock_schema(schema, 0)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::json_schema_validate::validate_value;
+
+  #[test]
+  fn test_mock_from_schema() {
+    let components: Components = serde_json::from_str(
+      r##"{
+      "schemas": {
+        "Tag": { "type": "string", "enum": ["new", "sale", "popular"] }
+      }
+    }"##,
+    )
+    .unwrap();
+    let schema: Schema = serde_json::from_str(
+      r##"{
+      "type": "object",
+      "required": ["id", "email", "price", "tags", "status", "createdAt", "code"],
+      "properties": {
+        "id": { "type": "string", "format": "uuid" },
+        "email": { "type": "string", "format": "email" },
+        "name": { "type": "string", "minLength": 30, "maxLength": 40 },
+        "price": { "type": "number", "minimum": 0, "exclusiveMinimum": true, "multipleOf": 0.25 },
+        "quantity": { "type": "integer", "minimum": 5, "maximum": 7 },
+        "tags": { "type": "array", "items": { "$ref": "#/components/schemas/Tag" }, "minItems": 2, "uniqueItems": true },
+        "status": { "type": "string", "default": "active" },
+        "createdAt": { "type": "string", "format": "date-time" },
+        "code": { "type": "string", "example": "ABC" },
+        "parent": { "$ref": "#" }
+      }
+    }"##,
+    )
+    .unwrap();
+
+    for seed in 0..20 {
+      let options = MockOptions {
+        seed: Some(seed),
+        max_depth: Some(2),
+      };
+      let value = mock_from_schema(&schema, Some(&components), &options);
+
+      assert_eq!(
+        value,
+        mock_from_schema(&schema, Some(&components), &options)
+      );
+      assert_eq!(
+        validate_value(&schema, &value, Some(&components)),
+        vec![],
+        "{}",
+        value
+      );
+      assert_eq!(value["status"], "active");
+      assert_eq!(value["code"], "ABC");
+    }
+
+    let value = mock_from_schema(&schema, Some(&components), &MockOptions::default());
+
+    assert_ne!(
+      value,
+      mock_from_schema(
+        &schema,
+        Some(&components),
+        &MockOptions {
+          seed: Some(1),
+          ..Default::default()
+        }
+      )
+    );
+  }
+
+  #[test]
+  fn test_mock_recursive_schema() {
+    let schema: Schema = serde_json::from_str(
+      r##"{
+      "type": "object",
+      "allOf": [{ "$ref": "#" }],
+      "oneOf": [{ "$ref": "#" }, { "required": ["name"], "properties": { "name": { "type": "string" } } }]
+    }"##,
+    )
+    .unwrap();
+
+    assert!(mock_from_schema(&schema, None, &MockOptions::default()).is_object());
+
+    let schema: Schema = serde_json::from_str(
+      r##"{
+      "type": "object",
+      "required": ["name", "parent"],
+      "properties": { "name": { "type": "string" }, "parent": { "$ref": "#" } }
+    }"##,
+    )
+    .unwrap();
+    let mut value = &mock_from_schema(&schema, None, &MockOptions::default());
+
+    for _ in 0..DEFAULT_MAX_DEPTH + DEPTH_MARGIN {
+      assert!(value["name"].is_string());
+      value = &value["parent"];
+    }
+    assert!(value["name"].is_string());
+    assert_eq!(value["parent"], Value::Null);
+  }
+  #[test]
+  fn test_mock_huge_range() {
+    for schema in [
+      r#"{ "type": "integer", "minimum": 0, "maximum": 1e300 }"#,
+      r#"{ "type": "number", "minimum": 0, "maximum": 1e300, "multipleOf": 2 }"#,
+    ] {
+      let schema: Schema = serde_json::from_str(schema).unwrap();
+      let value = mock_from_schema(&schema, None, &MockOptions::default());
+
+      assert_eq!(validate_value(&schema, &value, None), vec![], "{}", value);
+    }
+  }
+}
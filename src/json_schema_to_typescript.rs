@@ -1,15 +1,174 @@
+use crate::diagnostics::{pointer_join, Diagnostic, Diagnostics};
+
 use napi_derive::napi;
 use openapiv3::{
-  BooleanType, IntegerType, NumberType, ReferenceOr, Schema, SchemaKind, StringType, Type,
+  AdditionalProperties, AnySchema, BooleanType, Components, IntegerType, NumberType, ReferenceOr,
+  Schema, SchemaKind, StringFormat, StringType, Type, VariantOrUnknownOrEmpty,
 };
+use serde_json::Value;
 use std::fmt;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 #[napi(object)]
 pub struct SchemaTypeOptions {
   pub name: Option<String>,
   pub prefer_unknown_over_any: Option<bool>,
   pub prefer_interface_over_type: Option<bool>,
+  pub merge_all_of: Option<bool>,
+  /// Default to `source`
+  pub sort: Option<SortOrder>,
+  pub format: Option<FormatOptions>,
+  /// Fail with every lossy conversion instead of degrading the type
+  pub strict: Option<bool>,
+  /// Append an `is{name}` type guard function, requires `name`
+  pub type_guard: Option<bool>,
+}
+
+#[derive(Debug)]
+#[napi(object)]
+pub struct SchemaTypeOutput {
+  pub ts_type: String,
+  pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, PartialEq)]
+#[napi(string_enum = "lowercase")]
+pub enum SortOrder {
+  /// Declaration order of the schema
+  Source,
+  Alphabetical,
+}
+
+#[derive(Debug, PartialEq)]
+#[napi(string_enum = "lowercase")]
+pub enum MemberSeparator {
+  Semicolon,
+  Comma,
+  None,
+}
+
+#[derive(Debug, Default, Clone)]
+#[napi(object)]
+pub struct FormatOptions {
+  pub tab_width: Option<u32>,
+  pub use_tabs: Option<bool>,
+  pub semi: Option<bool>,
+  pub single_quote: Option<bool>,
+  /// Default to `semicolon`
+  pub member_separator: Option<MemberSeparator>,
+  pub trailing_comma: Option<bool>,
+  /// Unions longer than this are printed one member per line
+  pub print_width: Option<u32>,
+}
+
+impl FormatOptions {
+  pub fn indentation(&self, depth: usize) -> String {
+    if self.use_tabs.unwrap_or(false) {
+      "\t".repeat(depth)
+    } else {
+      " ".repeat(self.tab_width.unwrap_or(2) as usize * depth)
+    }
+  }
+
+  pub fn statement_end(&self) -> &'static str {
+    if self.semi.unwrap_or(true) {
+      ";"
+    } else {
+      ""
+    }
+  }
+
+  pub(crate) fn member_end(&self, is_last: bool) -> &'static str {
+    match self.member_separator {
+      Some(MemberSeparator::None) => "",
+      Some(MemberSeparator::Comma) if is_last && !self.trailing_comma.unwrap_or(true) => "",
+      Some(MemberSeparator::Comma) => ",",
+      Some(MemberSeparator::Semicolon) | None => ";",
+    }
+  }
+
+  pub fn quote(&self, value: &str) -> String {
+    let quote = if self.single_quote.unwrap_or(false) {
+      '\''
+    } else {
+      '"'
+    };
+
+    let escaped = value
+      .replace('\\', "\\\\")
+      .replace(quote, &format!("\\{}", quote));
+
+    format!("{}{}{}", quote, escaped, quote)
+  }
+
+  /// Property names that aren't valid identifiers are quoted.
+  fn property_name(&self, name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = chars
+      .next()
+      .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+      && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+    if is_identifier {
+      name.to_string()
+    } else {
+      self.quote(name)
+    }
+  }
+
+  /// Whether a line of `text` has more characters than the print width.
+  fn exceeds_print_width(&self, text: &str) -> bool {
+    self.print_width.is_some_and(|width| {
+      text
+        .lines()
+        .any(|line| line.chars().count() > width as usize)
+    })
+  }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SchemaContext<'a> {
+  pub components: Option<&'a Components>,
+  /// Name of the type being generated, used for root `#` references.
+  pub root_name: Option<&'a str>,
+  pub merge_all_of: bool,
+  pub sort_alphabetically: bool,
+  /// Schemas allowing any value are `unknown`, so an empty schema isn't a lossy conversion.
+  pub prefer_unknown_over_any: bool,
+  /// Strings with a `binary` format are `string | Blob`, used for form fields.
+  pub binary_as_blob: bool,
+  /// Collects the diagnostics of the conversion, when requested.
+  pub diagnostics: Option<&'a Diagnostics>,
+  /// JSON pointer of the schema being converted.
+  pub pointer: &'a str,
+}
+
+impl<'a> SchemaContext<'a> {
+  /// Pointer of a nested schema, only built when diagnostics are collected.
+  pub fn child_pointer(&self, segments: &[&str]) -> String {
+    if self.diagnostics.is_some() {
+      pointer_join(self.pointer, segments)
+    } else {
+      String::new()
+    }
+  }
+
+  pub fn at<'b>(&self, pointer: &'b str) -> SchemaContext<'b>
+  where
+    'a: 'b,
+  {
+    SchemaContext { pointer, ..*self }
+  }
+
+  pub fn warning(&self, code: &str, message: String) {
+    if let Some(diagnostics) = self.diagnostics {
+      diagnostics.warning(self.pointer, code, message);
+    }
+  }
+}
+
+pub fn is_alphabetical_sort(sort: &Option<SortOrder>) -> bool {
+  *sort == Some(SortOrder::Alphabetical)
 }
 
 #[derive(Debug, Clone)]
@@ -17,6 +176,8 @@ enum ObjectOrPrimitiveOrRef {
   TypeObject(TypeObject),
   PrimitiveProperty(PrimitiveProperty),
   RefProperty(RefProperty),
+  Group(GroupType),
+  Exclude(ExcludeType),
 }
 
 #[derive(Debug, Clone)]
@@ -43,6 +204,7 @@ enum PrimitiveType {
   Number,
   Boolean,
   Null,
+  Blob,
   Any,
 }
 
@@ -59,6 +221,19 @@ struct PrimitiveProperty {
   is_array: bool,
 }
 
+#[derive(Debug, Clone)]
+struct GroupType {
+  expressions: Vec<Expression>,
+  is_array: bool,
+}
+
+#[derive(Debug, Clone)]
+struct ExcludeType {
+  base: Vec<Expression>,
+  excluded: Vec<Expression>,
+  is_array: bool,
+}
+
 #[derive(Debug, Clone)]
 struct ObjectProperty {
   name: String,
@@ -74,6 +249,76 @@ struct Expression {
   link: Option<UnionOrIntersection>,
 }
 
+const DEFAULT_FORMAT_OPTIONS: FormatOptions = FormatOptions {
+  tab_width: None,
+  use_tabs: None,
+  semi: None,
+  single_quote: None,
+  member_separator: None,
+  trailing_comma: None,
+  print_width: None,
+};
+
+impl SchemaTypeOptions {
+  fn format(&self) -> &FormatOptions {
+    self.format.as_ref().unwrap_or(&DEFAULT_FORMAT_OPTIONS)
+  }
+}
+
+impl TypeObject {
+  fn new(mut properties: Vec<ObjectProperty>, is_array: bool, context: &SchemaContext) -> Self {
+    if context.sort_alphabetically {
+      properties.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    TypeObject {
+      properties,
+      is_array,
+    }
+  }
+}
+
+impl ObjectOrPrimitiveOrRef {
+  fn is_array(&self) -> bool {
+    match self {
+      ObjectOrPrimitiveOrRef::TypeObject(obj) => obj.is_array,
+      ObjectOrPrimitiveOrRef::PrimitiveProperty(primitive) => primitive.is_array,
+      ObjectOrPrimitiveOrRef::RefProperty(reference) => reference.is_array,
+      ObjectOrPrimitiveOrRef::Group(group) => group.is_array,
+      ObjectOrPrimitiveOrRef::Exclude(exclude) => exclude.is_array,
+    }
+  }
+
+  fn into_array(self) -> Self {
+    match self {
+      ObjectOrPrimitiveOrRef::TypeObject(obj) => ObjectOrPrimitiveOrRef::TypeObject(TypeObject {
+        is_array: true,
+        ..obj
+      }),
+      ObjectOrPrimitiveOrRef::PrimitiveProperty(primitive) => {
+        ObjectOrPrimitiveOrRef::PrimitiveProperty(PrimitiveProperty {
+          is_array: true,
+          ..primitive
+        })
+      }
+      ObjectOrPrimitiveOrRef::RefProperty(reference) => {
+        ObjectOrPrimitiveOrRef::RefProperty(RefProperty {
+          is_array: true,
+          ..reference
+        })
+      }
+      ObjectOrPrimitiveOrRef::Group(group) => ObjectOrPrimitiveOrRef::Group(GroupType {
+        is_array: true,
+        ..group
+      }),
+      ObjectOrPrimitiveOrRef::Exclude(exclude) => ObjectOrPrimitiveOrRef::Exclude(ExcludeType {
+        is_array: true,
+        ..exclude
+      }),
+    }
+  }
+}
+
 impl TypeInterface {
   fn get_separator(separator: &Option<UnionOrIntersection>) -> &'static str {
     match separator {
@@ -103,6 +348,7 @@ impl TypeInterface {
       PrimitiveType::Number => "number",
       PrimitiveType::Boolean => "boolean",
       PrimitiveType::Null => "null",
+      PrimitiveType::Blob => "Blob",
       PrimitiveType::Any => {
         if options.prefer_unknown_over_any.unwrap_or(false) {
           "unknown"
@@ -135,7 +381,7 @@ impl TypeInterface {
         .iter()
         .map(|s| {
           if matches!(primitive.primitive_type, PrimitiveType::String) {
-            format!("\"{}\"", s)
+            options.format().quote(s)
           } else {
             s.to_string()
           }
@@ -171,11 +417,136 @@ impl TypeInterface {
 
   fn expression_is_array(expression: &Expression) -> bool {
     expression.types.len() > 1
-      && expression.types.iter().all(|t| match t {
-        ObjectOrPrimitiveOrRef::TypeObject(obj) => obj.is_array,
-        ObjectOrPrimitiveOrRef::PrimitiveProperty(primitive) => primitive.is_array,
-        ObjectOrPrimitiveOrRef::RefProperty(reference) => reference.is_array,
+      && expression
+        .types
+        .iter()
+        .all(ObjectOrPrimitiveOrRef::is_array)
+  }
+
+  fn expressions_to_string(
+    expressions: &[Expression],
+    depth: usize,
+    options: &SchemaTypeOptions,
+  ) -> String {
+    expressions
+      .iter()
+      .map(|expression| {
+        let expression_is_array = TypeInterface::expression_is_array(expression);
+
+        let exp_string = expression
+          .types
+          .iter()
+          .map(|t| TypeInterface::type_object_to_string(t, depth, expression_is_array, options))
+          .collect::<Vec<String>>()
+          .join(TypeInterface::get_separator(&expression.link));
+
+        TypeInterface::format_string_expression(exp_string, expression_is_array)
+      })
+      .collect::<Vec<String>>()
+      .join(TypeInterface::get_separator(&Some(
+        UnionOrIntersection::Union,
+      )))
+  }
+
+  /// Top-level members of a union, used to print long unions one member per line.
+  fn union_members(
+    expressions: &[Expression],
+    depth: usize,
+    options: &SchemaTypeOptions,
+  ) -> Vec<String> {
+    expressions
+      .iter()
+      .flat_map(|expression| {
+        let expression_is_array = TypeInterface::expression_is_array(expression);
+
+        if expression_is_array
+          || (expression.types.len() > 1
+            && matches!(expression.link, Some(UnionOrIntersection::Intersection)))
+        {
+          return vec![TypeInterface::expressions_to_string(
+            std::slice::from_ref(expression),
+            depth,
+            options,
+          )];
+        }
+
+        expression
+          .types
+          .iter()
+          .flat_map(|t| match t {
+            ObjectOrPrimitiveOrRef::PrimitiveProperty(primitive)
+              if !primitive.is_array && primitive.enumeration.len() > 1 =>
+            {
+              primitive
+                .enumeration
+                .iter()
+                .map(|value| {
+                  TypeInterface::primitive_to_string(
+                    &PrimitiveProperty {
+                      enumeration: vec![value.clone()],
+                      ..primitive.clone()
+                    },
+                    false,
+                    options,
+                  )
+                })
+                .collect()
+            }
+            _ => vec![TypeInterface::type_object_to_string(
+              t, depth, false, options,
+            )],
+          })
+          .collect()
       })
+      .collect()
+  }
+
+  /// Prints `prefix` followed by the expressions, breaking the union over several lines when the
+  /// line is longer than the configured print width.
+  fn expressions_with_prefix(
+    prefix: &str,
+    expressions: &[Expression],
+    depth: usize,
+    suffix: &str,
+    options: &SchemaTypeOptions,
+  ) -> String {
+    let format = options.format();
+    let single_line = format!(
+      "{} {}{}",
+      prefix,
+      TypeInterface::expressions_to_string(expressions, depth, options),
+      suffix
+    );
+
+    if !format.exceeds_print_width(&single_line) {
+      return single_line;
+    }
+
+    let members = TypeInterface::union_members(expressions, depth, options);
+
+    if members.len() < 2 {
+      return single_line;
+    }
+
+    let members_indentation = format.indentation(depth);
+
+    format!(
+      "{}{}{}",
+      prefix,
+      members
+        .iter()
+        .map(|member| format!("\n{}| {}", members_indentation, member))
+        .collect::<String>(),
+      suffix
+    )
+  }
+
+  fn array_suffix(is_array: bool, expression_is_array: bool) -> &'static str {
+    if is_array && !expression_is_array {
+      "[]"
+    } else {
+      ""
+    }
   }
 
   fn type_object_to_string(
@@ -190,85 +561,83 @@ impl TypeInterface {
           return "{}".to_string();
         }
 
+        let format = options.format();
+        let properties_count = type_object.properties.len();
+
         let object_string = type_object
           .properties
           .iter()
-          .map(|property| {
-            let ts_types_string = property
-              .expressions
-              .iter()
-              .map(|expression| {
-                let expression_is_array = TypeInterface::expression_is_array(expression);
-
-                let exp_string = expression
-                  .types
-                  .iter()
-                  .map(|t| match t {
-                    ObjectOrPrimitiveOrRef::TypeObject(obj) => {
-                      TypeInterface::type_object_to_string(
-                        &ObjectOrPrimitiveOrRef::TypeObject(obj.clone()),
-                        depth + 1,
-                        expression_is_array,
-                        options,
-                      )
-                    }
-                    ObjectOrPrimitiveOrRef::PrimitiveProperty(primitive) => {
-                      TypeInterface::primitive_to_string(primitive, expression_is_array, options)
-                    }
-                    ObjectOrPrimitiveOrRef::RefProperty(reference) => {
-                      TypeInterface::reference_to_string(reference, expression_is_array)
-                    }
-                  })
-                  .collect::<Vec<String>>()
-                  .join(TypeInterface::get_separator(&expression.link));
-
-                TypeInterface::format_string_expression(exp_string, expression_is_array)
-              })
-              .collect::<Vec<String>>()
-              .join(TypeInterface::get_separator(&Some(
-                UnionOrIntersection::Union,
-              )));
-
-            let whitespace = "  ".repeat(depth);
+          .enumerate()
+          .map(|(index, property)| {
+            let whitespace = format.indentation(depth);
             let comment = if let Some(description) = &property.description {
-              format!(
-                "{}/**\n{} * {}{}\n{} */\n",
-                whitespace,
-                whitespace,
-                if property.deprecated {
-                  "@deprecated "
-                } else {
-                  ""
-                },
-                description,
-                whitespace
-              )
+              let deprecated = if property.deprecated {
+                "@deprecated "
+              } else {
+                ""
+              };
+              let lines = format!("{}{}", deprecated, description)
+                .lines()
+                .map(|line| match line.is_empty() {
+                  true => format!("{} *", whitespace),
+                  false => format!("{} * {}", whitespace, line),
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+
+              format!("{}/**\n{}\n{} */\n", whitespace, lines, whitespace)
             } else {
               "".to_string()
             };
 
-            format!(
-              "{}{}{}{}: {};",
-              comment,
-              whitespace,
-              property.name,
-              if property.required { "" } else { "?" },
-              ts_types_string
-            )
+            let member = TypeInterface::expressions_with_prefix(
+              &format!(
+                "{}{}{}:",
+                whitespace,
+                format.property_name(&property.name),
+                if property.required { "" } else { "?" }
+              ),
+              &property.expressions,
+              depth + 1,
+              format.member_end(index + 1 == properties_count),
+              options,
+            );
+
+            format!("{}{}", comment, member)
           })
           .collect::<Vec<String>>();
 
         format!(
           "{{\n{}\n{}}}{}",
           object_string.join("\n"),
-          "  ".repeat(depth - 1),
-          if type_object.is_array && !expression_is_array {
-            "[]"
-          } else {
-            ""
-          }
+          format.indentation(depth - 1),
+          TypeInterface::array_suffix(type_object.is_array, expression_is_array)
         )
       }
+      ObjectOrPrimitiveOrRef::Group(group) => {
+        let group_string = TypeInterface::expressions_to_string(&group.expressions, depth, options);
+        let is_single_type = group.expressions.len() == 1 && group.expressions[0].types.len() == 1;
+
+        if is_single_type {
+          format!(
+            "{}{}",
+            group_string,
+            TypeInterface::array_suffix(group.is_array, expression_is_array)
+          )
+        } else {
+          format!(
+            "({}){}",
+            group_string,
+            TypeInterface::array_suffix(group.is_array, expression_is_array)
+          )
+        }
+      }
+      ObjectOrPrimitiveOrRef::Exclude(exclude) => format!(
+        "Exclude<{}, {}>{}",
+        TypeInterface::expressions_to_string(&exclude.base, depth, options),
+        TypeInterface::expressions_to_string(&exclude.excluded, depth, options),
+        TypeInterface::array_suffix(exclude.is_array, expression_is_array)
+      ),
       ObjectOrPrimitiveOrRef::PrimitiveProperty(primitive) => {
         TypeInterface::primitive_to_string(primitive, expression_is_array, options)
       }
@@ -285,29 +654,10 @@ impl fmt::Display for TypeInterface {
       return write!(f, "{}", String::new());
     }
 
-    let types = self
-      .expressions
-      .iter()
-      .map(|expression| {
-        let expression_is_array = TypeInterface::expression_is_array(expression);
-        let exp_string = expression
-          .types
-          .iter()
-          .map(|t| TypeInterface::type_object_to_string(t, 1, expression_is_array, &self.options))
-          .collect::<Vec<String>>()
-          .join(TypeInterface::get_separator(&expression.link));
-
-        TypeInterface::format_string_expression(exp_string, expression_is_array)
-      })
-      .collect::<Vec<String>>();
-
-    let final_type = types.join(TypeInterface::get_separator(&Some(
-      UnionOrIntersection::Union,
-    )));
-
     let name = self.options.name.clone().unwrap_or_default();
 
     if name.is_empty() {
+      let final_type = TypeInterface::expressions_to_string(&self.expressions, 1, &self.options);
       write!(f, "{}", final_type)
     } else {
       let is_single_type_object = self.expressions.len() == 1
@@ -323,7 +673,23 @@ impl fmt::Display for TypeInterface {
           format!("export type {} =", name)
         };
 
-      write!(f, "{} {};", export_type, final_type)
+      write!(
+        f,
+        "{}",
+        TypeInterface::expressions_with_prefix(
+          &export_type,
+          &self.expressions,
+          1,
+          self.options.format().statement_end(),
+          &self.options,
+        )
+      )?;
+
+      if self.options.type_guard.unwrap_or(false) {
+        write!(f, "\n\n{}", self.type_guard(&name))?;
+      }
+
+      Ok(())
     }
   }
 }
@@ -403,593 +769,2325 @@ where
 
 fn schema_to_typescript_any_one_all_of_types(
   schema: &[ReferenceOr<Schema>],
+  keyword: &str,
   is_array: bool,
   separator: Option<UnionOrIntersection>,
+  context: &SchemaContext,
 ) -> Vec<ObjectOrPrimitiveOrRef> {
   schema
     .iter()
-    .flat_map(|any_of_item| {
-      schema_to_typescript_expressions(any_of_item, is_array, separator.clone())
+    .enumerate()
+    .flat_map(|(index, any_of_item)| {
+      let pointer = context.child_pointer(&[keyword, &index.to_string()]);
+      schema_to_typescript_expressions(
+        any_of_item,
+        is_array,
+        separator.clone(),
+        &context.at(&pointer),
+      )
     })
     .flat_map(|expression| expression.types)
     .collect()
 }
 
-trait SchemaLike {
-  fn as_schema(&self) -> &Schema;
+/// Runtime checks of the generated types, `item`, `item1`... name array items at each depth.
+struct TypeGuard<'a> {
+  options: &'a SchemaTypeOptions,
 }
 
-impl SchemaLike for Schema {
-  fn as_schema(&self) -> &Schema {
-    self
+/// Joins conditions, parenthesizing the ones using another operator.
+fn join_conditions(conditions: Vec<String>, operator: &str) -> String {
+  if conditions.len() == 1 {
+    return conditions.into_iter().next().unwrap_or_default();
   }
+
+  let other_operator = if operator.trim_start().starts_with("&&") {
+    " || "
+  } else {
+    " && "
+  };
+
+  conditions
+    .into_iter()
+    .map(|condition| {
+      if condition.contains(other_operator) {
+        format!("({})", condition)
+      } else {
+        condition
+      }
+    })
+    .collect::<Vec<String>>()
+    .join(operator)
 }
 
-impl SchemaLike for Box<Schema> {
-  fn as_schema(&self) -> &Schema {
-    self.as_ref()
+impl TypeGuard<'_> {
+  fn item_name(depth: usize) -> String {
+    if depth == 0 {
+      "item".to_string()
+    } else {
+      format!("item{}", depth)
+    }
   }
-}
 
-fn schema_to_typescript_expressions<T: SchemaLike>(
-  schema: &ReferenceOr<T>,
-  is_array: bool,
-  separator: Option<UnionOrIntersection>,
-) -> Vec<Expression> {
-  match schema {
-    ReferenceOr::Item(schema) => {
-      let schema = schema.as_schema();
+  fn every(value: &str, depth: usize, item_condition: impl FnOnce(&str) -> String) -> String {
+    let item = TypeGuard::item_name(depth);
+    // Only variables are narrowed by `Array.isArray`, not property accesses.
+    let array = if value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+      value.to_string()
+    } else {
+      format!("({} as unknown[])", value)
+    };
 
-      let base_expressions = match &schema.schema_kind {
-        SchemaKind::Type(Type::String(string_type)) => {
-          vec![get_primitive_expression(
-            string_type,
-            PrimitiveType::String,
-            is_array,
-          )]
-        }
-        SchemaKind::Type(Type::Number(number_type)) => {
-          vec![get_primitive_expression(
-            number_type,
-            PrimitiveType::Number,
-            is_array,
-          )]
-        }
-        SchemaKind::Type(Type::Integer(integer_type)) => {
-          vec![get_primitive_expression(
-            integer_type,
-            PrimitiveType::Number,
-            is_array,
-          )]
-        }
-        SchemaKind::Type(Type::Boolean(boolean_type)) => {
-          vec![get_primitive_expression(
-            boolean_type,
-            PrimitiveType::Boolean,
-            is_array,
-          )]
-        }
-        SchemaKind::Type(Type::Array(v)) => match &v.items {
-          Some(item) => schema_to_typescript_expressions(item, true, separator.clone()),
-          None => vec![Expression {
-            types: vec![ObjectOrPrimitiveOrRef::PrimitiveProperty(
-              PrimitiveProperty {
-                primitive_type: PrimitiveType::Any,
-                enumeration: vec![],
-                is_array: true,
-              },
-            )],
-            link: None,
-          }],
-        },
-        SchemaKind::Type(Type::Object(object)) => {
-          let properties: Vec<ObjectProperty> = object
-            .properties
-            .iter()
-            .map(|(key, value)| {
-              let description = match value {
-                ReferenceOr::Item(schema) => schema.as_schema().schema_data.description.clone(),
-                ReferenceOr::Reference { .. } => None,
-              };
+    format!(
+      "Array.isArray({}) && {}.every(({}) => {})",
+      value,
+      array,
+      item,
+      item_condition(&item)
+    )
+  }
 
-              let deprecated = match value {
-                ReferenceOr::Item(schema) => schema.as_schema().schema_data.deprecated,
-                ReferenceOr::Reference { .. } => false,
-              };
+  fn expressions(&self, expressions: &[Expression], value: &str, depth: usize) -> String {
+    join_conditions(
+      expressions
+        .iter()
+        .map(|expression| self.expression(expression, value, depth))
+        .collect(),
+      " || ",
+    )
+  }
 
-              ObjectProperty {
-                name: key.to_string(),
-                expressions: schema_to_typescript_expressions(value, false, None),
-                required: object.required.contains(key),
-                description,
-                deprecated,
-              }
-            })
-            .collect();
+  fn expression(&self, expression: &Expression, value: &str, depth: usize) -> String {
+    let operator = match expression.link {
+      Some(UnionOrIntersection::Intersection) => " && ",
+      _ => " || ",
+    };
 
-          vec![Expression {
-            types: vec![ObjectOrPrimitiveOrRef::TypeObject(TypeObject {
-              properties,
-              is_array,
-            })],
-            link: None,
-          }]
-        }
-        SchemaKind::AnyOf { any_of } => vec![Expression {
-          types: schema_to_typescript_any_one_all_of_types(any_of, is_array, None),
-          link: Some(UnionOrIntersection::Union),
-        }],
-        SchemaKind::OneOf { one_of } => vec![Expression {
-          types: schema_to_typescript_any_one_all_of_types(one_of, is_array, None),
-          link: Some(UnionOrIntersection::Union),
-        }],
-        SchemaKind::AllOf { all_of } => vec![Expression {
-          types: schema_to_typescript_any_one_all_of_types(all_of, is_array, None),
-          link: Some(UnionOrIntersection::Intersection),
-        }],
-        _ => {
-          vec![Expression {
-            types: vec![ObjectOrPrimitiveOrRef::PrimitiveProperty(
-              PrimitiveProperty {
-                primitive_type: PrimitiveType::Any,
-                enumeration: vec![],
-                is_array,
-              },
-            )],
-            link: None,
-          }]
+    if TypeInterface::expression_is_array(expression) {
+      return TypeGuard::every(value, depth, |item| {
+        join_conditions(
+          expression
+            .types
+            .iter()
+            .map(|t| self.item(t, item, depth + 1))
+            .collect(),
+          operator,
+        )
+      });
+    }
+
+    join_conditions(
+      expression
+        .types
+        .iter()
+        .map(|t| {
+          if t.is_array() {
+            TypeGuard::every(value, depth, |item| self.item(t, item, depth + 1))
+          } else {
+            self.item(t, value, depth)
+          }
+        })
+        .collect(),
+      operator,
+    )
+  }
+
+  /// Condition of a single value, ignoring `is_array`.
+  fn item(&self, object: &ObjectOrPrimitiveOrRef, value: &str, depth: usize) -> String {
+    match object {
+      ObjectOrPrimitiveOrRef::PrimitiveProperty(primitive) if !primitive.enumeration.is_empty() => {
+        join_conditions(
+          primitive
+            .enumeration
+            .iter()
+            .map(|literal| {
+              let literal = if matches!(primitive.primitive_type, PrimitiveType::String) {
+                self.options.format().quote(literal)
+              } else {
+                literal.clone()
+              };
+              format!("{} === {}", value, literal)
+            })
+            .collect(),
+          " || ",
+        )
+      }
+      ObjectOrPrimitiveOrRef::PrimitiveProperty(primitive) => match primitive.primitive_type {
+        PrimitiveType::String => self.type_of(value, "string"),
+        PrimitiveType::Number => self.type_of(value, "number"),
+        PrimitiveType::Boolean => self.type_of(value, "boolean"),
+        PrimitiveType::Null => format!("{} === null", value),
+        PrimitiveType::Blob => format!("{} instanceof Blob", value),
+        PrimitiveType::Any => "true".to_string(),
+      },
+      ObjectOrPrimitiveOrRef::RefProperty(reference) => {
+        format!("is{}({})", reference.reference, value)
+      }
+      ObjectOrPrimitiveOrRef::TypeObject(type_object) => join_conditions(
+        self.object_conditions(
+          type_object,
+          value,
+          &format!("({} as Record<string, unknown>)", value),
+          depth,
+        ),
+        " && ",
+      ),
+      ObjectOrPrimitiveOrRef::Group(group) => self.expressions(&group.expressions, value, depth),
+      ObjectOrPrimitiveOrRef::Exclude(exclude) => join_conditions(
+        vec![
+          self.expressions(&exclude.base, value, depth),
+          format!("!({})", self.expressions(&exclude.excluded, value, depth)),
+        ],
+        " && ",
+      ),
+    }
+  }
+
+  fn type_of(&self, value: &str, typ: &str) -> String {
+    format!("typeof {} === {}", value, self.options.format().quote(typ))
+  }
+
+  /// `record` reads the properties of `value`.
+  fn object_conditions(
+    &self,
+    type_object: &TypeObject,
+    value: &str,
+    record: &str,
+    depth: usize,
+  ) -> Vec<String> {
+    let mut conditions = vec![
+      self.type_of(value, "object"),
+      format!("{} !== null", value),
+      format!("!Array.isArray({})", value),
+    ];
+
+    for property in &type_object.properties {
+      let access = format!(
+        "{}[{}]",
+        record,
+        self.options.format().quote(&property.name)
+      );
+      let condition = self.expressions(&property.expressions, &access, depth);
+
+      match (property.required, condition.as_str()) {
+        (true, "true") => conditions.push(format!("{} !== undefined", access)),
+        (true, _) => conditions.push(condition),
+        (false, "true") => {}
+        (false, _) => conditions.push(join_conditions(
+          vec![format!("{} === undefined", access), condition],
+          " || ",
+        )),
+      }
+    }
+
+    conditions
+  }
+}
+
+impl TypeInterface {
+  /// `is{name}` function checking an unknown value against the type, calling the guards of the
+  /// referenced types.
+  pub fn type_guard(&self, name: &str) -> String {
+    let format = self.options.format();
+    let guard = TypeGuard {
+      options: &self.options,
+    };
+
+    let root_object = match self.expressions.as_slice() {
+      [Expression { types, .. }] if types.len() == 1 => match &types[0] {
+        ObjectOrPrimitiveOrRef::TypeObject(type_object)
+          if !type_object.is_array && !type_object.properties.is_empty() =>
+        {
+          Some(type_object)
         }
-      };
+        _ => None,
+      },
+      _ => None,
+    };
 
-      if schema.schema_data.nullable {
-        base_expressions
-          .into_iter()
-          .map(|mut expression| {
-            expression
-              .types
-              .push(ObjectOrPrimitiveOrRef::PrimitiveProperty(
-                PrimitiveProperty {
-                  primitive_type: PrimitiveType::Null,
-                  enumeration: vec![],
-                  is_array,
-                },
-              ));
-            expression
-          })
-          .collect()
-      } else {
-        base_expressions
+    // Properties of a root object are read from a single cast.
+    let (declaration, conditions) = match root_object {
+      Some(type_object) => (
+        format!(
+          "{}const object = value as Record<string, unknown>{}\n",
+          format.indentation(1),
+          format.statement_end()
+        ),
+        guard.object_conditions(type_object, "value", "object", 0),
+      ),
+      None => (
+        String::new(),
+        vec![guard.expressions(&self.expressions, "value", 0)],
+      ),
+    };
+
+    let body = if conditions.len() == 1 {
+      conditions[0].clone()
+    } else {
+      format!(
+        "(\n{}{}\n{})",
+        format.indentation(2),
+        join_conditions(conditions, &format!(" &&\n{}", format.indentation(2))),
+        format.indentation(1)
+      )
+    };
+
+    format!(
+      "export function is{}(value: unknown): value is {} {{\n{}{}return {}{}\n}}",
+      name,
+      name,
+      declaration,
+      format.indentation(1),
+      body,
+      format.statement_end()
+    )
+  }
+}
+
+pub(crate) trait SchemaLike {
+  fn as_schema(&self) -> &Schema;
+}
+
+impl SchemaLike for Schema {
+  fn as_schema(&self) -> &Schema {
+    self
+  }
+}
+
+impl SchemaLike for Box<Schema> {
+  fn as_schema(&self) -> &Schema {
+    self.as_ref()
+  }
+}
+
+fn reference_to_name(reference: &str) -> String {
+  type_name(
+    &reference
+      .split('/')
+      .next_back()
+      .unwrap_or_default()
+      .replace("~1", "/")
+      .replace("~0", "~"),
+  )
+}
+
+/// Valid TypeScript identifier of a component name, `User-Profile` becomes `User_Profile`.
+pub(crate) fn type_name(name: &str) -> String {
+  let identifier: String = name
+    .chars()
+    .map(|c| match c.is_alphanumeric() || c == '_' || c == '$' {
+      true => c,
+      false => '_',
+    })
+    .collect();
+
+  match identifier.starts_with(|c: char| c.is_ascii_digit()) {
+    true => format!("_{}", identifier),
+    false => identifier,
+  }
+}
+
+pub(crate) fn resolve_schema_reference<'a>(
+  reference: &str,
+  context: &SchemaContext<'a>,
+) -> Option<&'a Schema> {
+  let mut visited: Vec<&str> = vec![];
+  let mut current = reference;
+
+  // Components can alias each other, stop as soon as a reference comes back.
+  while !visited.contains(&current) {
+    visited.push(current);
+
+    let name = current.strip_prefix("#/components/schemas/")?;
+
+    match context.components?.schemas.get(name)? {
+      ReferenceOr::Item(schema) => return Some(schema),
+      ReferenceOr::Reference { reference } => current = reference,
+    }
+  }
+
+  None
+}
+
+fn any_expression(is_array: bool) -> Expression {
+  Expression {
+    types: vec![ObjectOrPrimitiveOrRef::PrimitiveProperty(
+      PrimitiveProperty {
+        primitive_type: PrimitiveType::Any,
+        enumeration: vec![],
+        is_array,
+      },
+    )],
+    link: None,
+  }
+}
+
+fn object_property(
+  key: &str,
+  values: &[&ReferenceOr<Box<Schema>>],
+  required: bool,
+  context: &SchemaContext,
+) -> ObjectProperty {
+  let pointer = context.child_pointer(&["properties", key]);
+  let context = &context.at(&pointer);
+
+  let property_schemas: Vec<&Schema> = values
+    .iter()
+    .filter_map(|value| match value {
+      ReferenceOr::Item(schema) => Some(schema.as_schema()),
+      ReferenceOr::Reference { reference } => resolve_schema_reference(reference, context),
+    })
+    .collect();
+
+  let mut descriptions: Vec<&str> = vec![];
+  for description in property_schemas
+    .iter()
+    .filter_map(|schema| schema.schema_data.description.as_deref())
+  {
+    if !descriptions.contains(&description) {
+      descriptions.push(description);
+    }
+  }
+  let description = (!descriptions.is_empty()).then(|| descriptions.join("\n\n"));
+  let deprecated = property_schemas
+    .iter()
+    .any(|schema| schema.schema_data.deprecated);
+
+  let mut distinct_types: Vec<(String, Vec<Expression>)> = vec![];
+  for value in values {
+    let expressions = schema_to_typescript_expressions(*value, false, None, context);
+    let rendered =
+      TypeInterface::expressions_to_string(&expressions, 1, &SchemaTypeOptions::default());
+
+    if !distinct_types
+      .iter()
+      .any(|(existing, _)| *existing == rendered)
+    {
+      distinct_types.push((rendered, expressions));
+    }
+  }
+
+  // Members of an allOf declaring the same property with different types: keep the
+  // intersection, which is what `A & B` would have produced, but it is usually `never`.
+  let expressions = if distinct_types.len() > 1 {
+    context.warning(
+      "all-of-conflict",
+      format!(
+        "`{}` has conflicting types in the allOf members, their intersection is used",
+        key
+      ),
+    );
+
+    vec![Expression {
+      types: distinct_types
+        .into_iter()
+        .map(|(_, expressions)| expressions_to_type(expressions))
+        .collect(),
+      link: Some(UnionOrIntersection::Intersection),
+    }]
+  } else {
+    distinct_types
+      .pop()
+      .map(|(_, expressions)| expressions)
+      .unwrap_or_default()
+  };
+
+  ObjectProperty {
+    name: key.to_string(),
+    expressions,
+    required,
+    description,
+    deprecated,
+  }
+}
+
+fn object_to_expression<'a>(
+  properties: impl IntoIterator<Item = (&'a String, &'a ReferenceOr<Box<Schema>>)>,
+  required: &[String],
+  is_array: bool,
+  context: &SchemaContext,
+) -> Expression {
+  let properties: Vec<ObjectProperty> = properties
+    .into_iter()
+    .map(|(key, value)| object_property(key, &[value], required.contains(key), context))
+    .collect();
+
+  Expression {
+    types: vec![ObjectOrPrimitiveOrRef::TypeObject(TypeObject::new(
+      properties, is_array, context,
+    ))],
+    link: None,
+  }
+}
+
+/// Wraps expressions into a single type, grouping them when they hold more than one type.
+fn expressions_to_type(expressions: Vec<Expression>) -> ObjectOrPrimitiveOrRef {
+  if expressions.len() == 1 && expressions[0].types.len() == 1 {
+    expressions[0].types[0].clone()
+  } else {
+    ObjectOrPrimitiveOrRef::Group(GroupType {
+      expressions,
+      is_array: false,
+    })
+  }
+}
+
+fn collect_all_of_members<'a>(
+  all_of: &'a [ReferenceOr<Schema>],
+  context: &SchemaContext<'a>,
+  visited: &mut Vec<&'a str>,
+  objects: &mut Vec<&'a Schema>,
+  others: &mut Vec<&'a ReferenceOr<Schema>>,
+) {
+  for member in all_of {
+    let schema = match member {
+      ReferenceOr::Item(schema) => Some(schema),
+      ReferenceOr::Reference { reference } if !visited.contains(&reference.as_str()) => {
+        visited.push(reference);
+        resolve_schema_reference(reference, context)
+      }
+      ReferenceOr::Reference { .. } => None,
+    };
+
+    match schema.map(|schema| &schema.schema_kind) {
+      Some(SchemaKind::Type(Type::Object(_))) => objects.extend(schema),
+      Some(SchemaKind::AllOf { all_of }) => {
+        collect_all_of_members(all_of, context, visited, objects, others)
+      }
+      Some(SchemaKind::Any(any_schema))
+        if matches!(any_schema.typ.as_deref(), None | Some("object"))
+          && any_schema.items.is_none()
+          && any_schema.enumeration.is_empty()
+          && any_schema.one_of.is_empty()
+          && any_schema.any_of.is_empty()
+          && any_schema.not.is_none() =>
+      {
+        objects.extend(schema);
+        collect_all_of_members(&any_schema.all_of, context, visited, objects, others);
       }
+      _ => others.push(member),
     }
-    ReferenceOr::Reference { reference } => {
-      let reference_name = reference
-        .split('/')
-        .next_back()
-        .unwrap_or_default()
-        .to_string();
-      vec![Expression {
-        types: vec![ObjectOrPrimitiveOrRef::RefProperty(RefProperty {
-          reference: reference_name,
-          is_array,
-        })],
-        link: separator,
-      }]
+  }
+}
+
+/// Merges the object members of an allOf (inline or referenced) into one object type, the
+/// remaining members are kept in an intersection with it.
+fn merge_all_of_expressions(
+  all_of: &[ReferenceOr<Schema>],
+  is_array: bool,
+  context: &SchemaContext,
+) -> Vec<Expression> {
+  let mut objects: Vec<&Schema> = vec![];
+  let mut others: Vec<&ReferenceOr<Schema>> = vec![];
+  collect_all_of_members(all_of, context, &mut vec![], &mut objects, &mut others);
+
+  let mut required: Vec<&String> = vec![];
+  let mut properties: Vec<(&String, Vec<&ReferenceOr<Box<Schema>>>)> = vec![];
+
+  for object in &objects {
+    let (object_properties, object_required) = match &object.schema_kind {
+      SchemaKind::Type(Type::Object(object)) => (&object.properties, &object.required),
+      SchemaKind::Any(any_schema) => (&any_schema.properties, &any_schema.required),
+      _ => continue,
+    };
+
+    required.extend(object_required);
+
+    for (key, value) in object_properties {
+      match properties.iter_mut().find(|(name, _)| *name == key) {
+        Some((_, values)) => values.push(value),
+        None => properties.push((key, vec![value])),
+      }
     }
   }
+
+  let mut types = vec![ObjectOrPrimitiveOrRef::TypeObject(TypeObject::new(
+    properties
+      .into_iter()
+      .map(|(key, values)| object_property(key, &values, required.contains(&key), context))
+      .collect(),
+    false,
+    context,
+  ))];
+
+  types.extend(others.into_iter().map(|other| {
+    expressions_to_type(schema_to_typescript_expressions(
+      other, false, None, context,
+    ))
+  }));
+
+  let link = (types.len() > 1).then_some(UnionOrIntersection::Intersection);
+
+  expressions_into_array(vec![Expression { types, link }], is_array)
 }
 
-pub fn schema_to_typescript(
-  schema: ReferenceOr<Schema>,
-  options: Option<SchemaTypeOptions>,
-) -> TypeInterface {
-  TypeInterface {
-    options: options.unwrap_or_default(),
-    expressions: schema_to_typescript_expressions(&schema, false, None),
+fn enumeration_to_types(values: &[Value]) -> Vec<ObjectOrPrimitiveOrRef> {
+  let literals = |primitive_type: PrimitiveType, filter: fn(&Value) -> Option<String>| {
+    let enumeration: Vec<String> = values.iter().filter_map(filter).collect();
+
+    (!enumeration.is_empty()).then_some(ObjectOrPrimitiveOrRef::PrimitiveProperty(
+      PrimitiveProperty {
+        primitive_type,
+        enumeration,
+        is_array: false,
+      },
+    ))
+  };
+
+  let mut types: Vec<ObjectOrPrimitiveOrRef> = [
+    literals(PrimitiveType::String, |v| v.as_str().map(String::from)),
+    literals(PrimitiveType::Number, |v| {
+      v.is_number().then(|| v.to_string())
+    }),
+    literals(PrimitiveType::Boolean, |v| {
+      v.as_bool().map(|b| b.to_string())
+    }),
+  ]
+  .into_iter()
+  .flatten()
+  .collect();
+
+  if values.iter().any(Value::is_null) {
+    types.push(ObjectOrPrimitiveOrRef::PrimitiveProperty(
+      PrimitiveProperty {
+        primitive_type: PrimitiveType::Null,
+        enumeration: vec![],
+        is_array: false,
+      },
+    ));
+  }
+
+  if values.iter().any(|v| v.is_object() || v.is_array()) {
+    types.extend(any_expression(false).types);
+  }
+
+  types
+}
+
+fn is_literal_or_primitive(schema: &Schema) -> bool {
+  match &schema.schema_kind {
+    SchemaKind::Type(Type::String(_))
+    | SchemaKind::Type(Type::Number(_))
+    | SchemaKind::Type(Type::Integer(_))
+    | SchemaKind::Type(Type::Boolean(_)) => true,
+    SchemaKind::Any(any_schema) => {
+      !any_schema.enumeration.is_empty()
+        || matches!(
+          any_schema.typ.as_deref(),
+          Some("string" | "number" | "integer" | "boolean" | "null")
+        )
+    }
+    _ => false,
+  }
+}
+
+/// Expressions for a `not` schema, only when it can be expressed with `Exclude<...>`.
+fn not_to_excluded_expressions(
+  not: &ReferenceOr<Schema>,
+  context: &SchemaContext,
+) -> Option<Vec<Expression>> {
+  let not_schema = match not {
+    ReferenceOr::Item(schema) => Some(schema),
+    ReferenceOr::Reference { reference } => resolve_schema_reference(reference, context),
+  }?;
+
+  is_literal_or_primitive(not_schema)
+    .then(|| schema_to_typescript_expressions(not, false, None, context))
+}
+
+fn exclude_expression(
+  base: Vec<Expression>,
+  not: &ReferenceOr<Schema>,
+  is_array: bool,
+  context: &SchemaContext,
+) -> Vec<Expression> {
+  let pointer = context.child_pointer(&["not"]);
+
+  match not_to_excluded_expressions(not, &context.at(&pointer)) {
+    Some(excluded) => vec![Expression {
+      types: vec![ObjectOrPrimitiveOrRef::Exclude(ExcludeType {
+        base,
+        excluded,
+        is_array,
+      })],
+      link: None,
+    }],
+    None => {
+      context.warning(
+        "ignored-keyword",
+        "`not` can only exclude literals and primitives, it is ignored".to_string(),
+      );
+      expressions_into_array(base, is_array)
+    }
+  }
+}
+
+/// Only declared properties are typed, an `additionalProperties` schema is ignored.
+fn warn_additional_properties_schema(
+  additional_properties: &Option<AdditionalProperties>,
+  context: &SchemaContext,
+) {
+  if let Some(AdditionalProperties::Schema(_)) = additional_properties {
+    context.warning(
+      "ignored-keyword",
+      "`additionalProperties` schema isn't supported, it is ignored".to_string(),
+    );
+  }
+}
+
+fn expressions_into_array(expressions: Vec<Expression>, is_array: bool) -> Vec<Expression> {
+  if !is_array {
+    return expressions;
+  }
+
+  expressions
+    .into_iter()
+    .map(|expression| Expression {
+      types: expression
+        .types
+        .into_iter()
+        .map(ObjectOrPrimitiveOrRef::into_array)
+        .collect(),
+      link: expression.link,
+    })
+    .collect()
+}
+
+/// Best-effort structural type for schemas mixing `type`, `properties`, `enum` and composition
+/// keywords: every constraint becomes a member of an intersection.
+fn any_schema_to_typescript_expressions(
+  any_schema: &AnySchema,
+  is_array: bool,
+  context: &SchemaContext,
+) -> Vec<Expression> {
+  let mut parts: Vec<Vec<Expression>> = vec![];
+
+  let primitive = |primitive_type: PrimitiveType| Expression {
+    types: vec![ObjectOrPrimitiveOrRef::PrimitiveProperty(
+      PrimitiveProperty {
+        primitive_type,
+        enumeration: vec![],
+        is_array: false,
+      },
+    )],
+    link: None,
+  };
+
+  if any_schema
+    .enumeration
+    .iter()
+    .any(|value| value.is_object() || value.is_array())
+  {
+    context.warning(
+      "unsupported-enum-value",
+      "Object and array enum values are converted to any".to_string(),
+    );
+  }
+
+  warn_additional_properties_schema(&any_schema.additional_properties, context);
+
+  let items_pointer = context.child_pointer(&["items"]);
+
+  let structural = if !any_schema.enumeration.is_empty() {
+    Some(vec![Expression {
+      types: enumeration_to_types(&any_schema.enumeration),
+      link: Some(UnionOrIntersection::Union),
+    }])
+  } else {
+    match any_schema.typ.as_deref() {
+      Some("object") => Some(vec![object_to_expression(
+        &any_schema.properties,
+        &any_schema.required,
+        false,
+        context,
+      )]),
+      None if !any_schema.properties.is_empty() => Some(vec![object_to_expression(
+        &any_schema.properties,
+        &any_schema.required,
+        false,
+        context,
+      )]),
+      Some("array") | None if any_schema.items.is_some() => match &any_schema.items {
+        Some(items) => Some(schema_to_typescript_expressions(
+          items,
+          true,
+          None,
+          &context.at(&items_pointer),
+        )),
+        None => Some(vec![any_expression(true)]),
+      },
+      Some("array") => {
+        context.warning(
+          "implicit-any",
+          "Array without `items`, converted to any[]".to_string(),
+        );
+        Some(vec![any_expression(true)])
+      }
+      Some("string") => Some(vec![primitive(PrimitiveType::String)]),
+      Some("number" | "integer") => Some(vec![primitive(PrimitiveType::Number)]),
+      Some("boolean") => Some(vec![primitive(PrimitiveType::Boolean)]),
+      Some("null") => Some(vec![primitive(PrimitiveType::Null)]),
+      Some(typ) => {
+        context.warning(
+          "unknown-type",
+          format!("Unknown type `{}`, converted to any", typ),
+        );
+        None
+      }
+      None => None,
+    }
+  };
+
+  parts.extend(structural);
+
+  parts.extend(
+    any_schema
+      .all_of
+      .iter()
+      .enumerate()
+      .map(|(index, all_of_item)| {
+        let pointer = context.child_pointer(&["allOf", &index.to_string()]);
+        schema_to_typescript_expressions(all_of_item, false, None, &context.at(&pointer))
+      }),
+  );
+
+  for (keyword, union_members) in [("oneOf", &any_schema.one_of), ("anyOf", &any_schema.any_of)] {
+    if !union_members.is_empty() {
+      parts.push(vec![Expression {
+        types: schema_to_typescript_any_one_all_of_types(
+          union_members,
+          keyword,
+          false,
+          None,
+          context,
+        ),
+        link: Some(UnionOrIntersection::Union),
+      }]);
+    }
+  }
+
+  if parts.is_empty() && any_schema.typ.is_none() && any_schema.not.is_none() {
+    if *any_schema != AnySchema::default() {
+      context.warning(
+        "implicit-any",
+        "Schema without a type, converted to any".to_string(),
+      );
+    } else if !context.prefer_unknown_over_any {
+      context.warning("implicit-any", "Empty schema, converted to any".to_string());
+    }
+  }
+
+  let base = match parts.len() {
+    0 => vec![any_expression(false)],
+    1 => parts.remove(0),
+    _ => vec![Expression {
+      types: parts.into_iter().map(expressions_to_type).collect(),
+      link: Some(UnionOrIntersection::Intersection),
+    }],
+  };
+
+  match &any_schema.not {
+    Some(not) => exclude_expression(base, not, is_array, context),
+    None => expressions_into_array(base, is_array),
   }
 }
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+fn schema_to_typescript_expressions<T: SchemaLike>(
+  schema: &ReferenceOr<T>,
+  is_array: bool,
+  separator: Option<UnionOrIntersection>,
+  context: &SchemaContext,
+) -> Vec<Expression> {
+  match schema {
+    ReferenceOr::Item(schema) => {
+      let schema = schema.as_schema();
+
+      let base_expressions = match &schema.schema_kind {
+        SchemaKind::Type(Type::String(string_type))
+          if context.binary_as_blob
+            && string_type.format == VariantOrUnknownOrEmpty::Item(StringFormat::Binary) =>
+        {
+          // Form fields are sent as strings or files, `FormData` accepts both for a file.
+          vec![Expression {
+            types: [PrimitiveType::String, PrimitiveType::Blob]
+              .into_iter()
+              .map(|primitive_type| {
+                ObjectOrPrimitiveOrRef::PrimitiveProperty(PrimitiveProperty {
+                  primitive_type,
+                  enumeration: vec![],
+                  is_array,
+                })
+              })
+              .collect(),
+            link: Some(UnionOrIntersection::Union),
+          }]
+        }
+        SchemaKind::Type(Type::String(string_type)) => {
+          vec![get_primitive_expression(
+            string_type,
+            PrimitiveType::String,
+            is_array,
+          )]
+        }
+        SchemaKind::Type(Type::Number(number_type)) => {
+          vec![get_primitive_expression(
+            number_type,
+            PrimitiveType::Number,
+            is_array,
+          )]
+        }
+        SchemaKind::Type(Type::Integer(integer_type)) => {
+          vec![get_primitive_expression(
+            integer_type,
+            PrimitiveType::Number,
+            is_array,
+          )]
+        }
+        SchemaKind::Type(Type::Boolean(boolean_type)) => {
+          vec![get_primitive_expression(
+            boolean_type,
+            PrimitiveType::Boolean,
+            is_array,
+          )]
+        }
+        SchemaKind::Type(Type::Array(v)) => match &v.items {
+          Some(item) => {
+            let pointer = context.child_pointer(&["items"]);
+            schema_to_typescript_expressions(item, true, separator.clone(), &context.at(&pointer))
+          }
+          None => {
+            context.warning(
+              "implicit-any",
+              "Array without `items`, converted to any[]".to_string(),
+            );
+            vec![any_expression(true)]
+          }
+        },
+        SchemaKind::Type(Type::Object(object)) => {
+          warn_additional_properties_schema(&object.additional_properties, context);
+          vec![object_to_expression(
+            &object.properties,
+            &object.required,
+            is_array,
+            context,
+          )]
+        }
+        SchemaKind::AnyOf { any_of } => vec![Expression {
+          types: schema_to_typescript_any_one_all_of_types(
+            any_of, "anyOf", is_array, None, context,
+          ),
+          link: Some(UnionOrIntersection::Union),
+        }],
+        SchemaKind::OneOf { one_of } => vec![Expression {
+          types: schema_to_typescript_any_one_all_of_types(
+            one_of, "oneOf", is_array, None, context,
+          ),
+          link: Some(UnionOrIntersection::Union),
+        }],
+        SchemaKind::AllOf { all_of } if context.merge_all_of && all_of.len() > 1 => {
+          merge_all_of_expressions(all_of, is_array, context)
+        }
+        SchemaKind::AllOf { all_of } => vec![Expression {
+          types: schema_to_typescript_any_one_all_of_types(
+            all_of, "allOf", is_array, None, context,
+          ),
+          link: Some(UnionOrIntersection::Intersection),
+        }],
+        SchemaKind::Not { not } => {
+          exclude_expression(vec![any_expression(false)], not, is_array, context)
+        }
+        SchemaKind::Any(any_schema) => {
+          any_schema_to_typescript_expressions(any_schema, is_array, context)
+        }
+      };
+
+      if schema.schema_data.nullable {
+        base_expressions
+          .into_iter()
+          .map(|mut expression| {
+            expression
+              .types
+              .push(ObjectOrPrimitiveOrRef::PrimitiveProperty(
+                PrimitiveProperty {
+                  primitive_type: PrimitiveType::Null,
+                  enumeration: vec![],
+                  is_array,
+                },
+              ));
+            expression
+          })
+          .collect()
+      } else {
+        base_expressions
+      }
+    }
+    ReferenceOr::Reference { reference } => {
+      let reference_name = if reference == "#" {
+        context.root_name.map(String::from)
+      } else {
+        Some(reference_to_name(reference)).filter(|name| !name.is_empty())
+      };
+
+      let Some(reference_name) = reference_name else {
+        context.warning(
+          "unresolved-reference",
+          format!("`{}` can't be resolved, converted to any", reference),
+        );
+        return vec![any_expression(is_array)];
+      };
+
+      if !reference.starts_with('#') {
+        context.warning(
+          "external-reference",
+          format!(
+            "External reference `{}` isn't resolved, `{}` is used as its type name",
+            reference, reference_name
+          ),
+        );
+      } else if let (Some(components), Some(name)) = (
+        context.components,
+        reference.strip_prefix("#/components/schemas/"),
+      ) {
+        if !components.schemas.contains_key(name) {
+          context.warning(
+            "unresolved-reference",
+            format!("`{}` doesn't exist in the components", reference),
+          );
+        }
+      }
+
+      vec![Expression {
+        types: vec![ObjectOrPrimitiveOrRef::RefProperty(RefProperty {
+          reference: reference_name,
+          is_array,
+        })],
+        link: separator,
+      }]
+    }
+  }
+}
+
+/// JSON Schema keywords `openapiv3::Schema` drops on deserialization.
+const UNSUPPORTED_KEYWORDS: [&str; 12] = [
+  "patternProperties",
+  "propertyNames",
+  "dependencies",
+  "dependentSchemas",
+  "dependentRequired",
+  "contains",
+  "prefixItems",
+  "if",
+  "then",
+  "else",
+  "unevaluatedProperties",
+  "unevaluatedItems",
+];
+
+/// Prepares a schema for its deserialization into `openapiv3::Schema`, which drops the keywords it
+/// doesn't know: `const` becomes a single-value `enum`, and the `description` and `deprecated`
+/// siblings of a `$ref` (allowed since OpenAPI 3.1) move into a single-member `allOf`. The other
+/// unsupported keywords are reported as ignored at their pointer, `pointer` being the schema one.
+pub fn normalize_schema(schema: &mut Value, pointer: &str, diagnostics: &Diagnostics) {
+  let Value::Object(map) = schema else {
+    return;
+  };
+
+  for keyword in UNSUPPORTED_KEYWORDS
+    .iter()
+    .filter(|keyword| map.contains_key(**keyword))
+  {
+    diagnostics.warning(
+      pointer,
+      "ignored-keyword",
+      format!("`{}` isn't supported, it is ignored", keyword),
+    );
+  }
+
+  if let Some(value) = map.remove("const") {
+    map.insert("enum".to_string(), Value::Array(vec![value]));
+  }
+
+  if map.contains_key("$ref") {
+    if map.contains_key("description") || map.contains_key("deprecated") {
+      let reference = map.remove("$ref").unwrap_or_default();
+      let mut reference_map = serde_json::Map::new();
+      reference_map.insert("$ref".to_string(), reference);
+      map.insert(
+        "allOf".to_string(),
+        Value::Array(vec![Value::Object(reference_map)]),
+      );
+    }
+    return;
+  }
+
+  if let Some(Value::Object(properties)) = map.get_mut("properties") {
+    for (name, property) in properties {
+      normalize_schema(
+        property,
+        &pointer_join(pointer, &["properties", name]),
+        diagnostics,
+      );
+    }
+  }
+
+  for key in ["items", "additionalProperties", "not"] {
+    if let Some(value) = map.get_mut(key) {
+      normalize_schema(value, &pointer_join(pointer, &[key]), diagnostics);
+    }
+  }
+
+  for key in ["allOf", "anyOf", "oneOf"] {
+    if let Some(Value::Array(values)) = map.get_mut(key) {
+      for (index, value) in values.iter_mut().enumerate() {
+        normalize_schema(
+          value,
+          &pointer_join(pointer, &[key, &index.to_string()]),
+          diagnostics,
+        );
+      }
+    }
+  }
+}
+
+pub fn schema_to_typescript(
+  schema: ReferenceOr<Schema>,
+  options: Option<SchemaTypeOptions>,
+) -> TypeInterface {
+  schema_to_typescript_with_context(schema, options, &SchemaContext::default())
+}
+
+/// Like [`schema_to_typescript`], along with the diagnostics of the conversion.
+pub fn schema_to_typescript_with_diagnostics(
+  schema: ReferenceOr<Schema>,
+  options: Option<SchemaTypeOptions>,
+) -> SchemaTypeOutput {
+  let diagnostics = Diagnostics::default();
+  let interface = schema_to_typescript_with_context(
+    schema,
+    options,
+    &SchemaContext {
+      diagnostics: Some(&diagnostics),
+      ..Default::default()
+    },
+  );
+
+  SchemaTypeOutput {
+    ts_type: interface.to_string(),
+    diagnostics: diagnostics.into_vec(),
+  }
+}
+
+pub fn schema_to_typescript_with_context(
+  schema: ReferenceOr<Schema>,
+  options: Option<SchemaTypeOptions>,
+  context: &SchemaContext,
+) -> TypeInterface {
+  let options = options.unwrap_or_default();
+  let context = SchemaContext {
+    root_name: context.root_name.or(options.name.as_deref()),
+    merge_all_of: context.merge_all_of || options.merge_all_of.unwrap_or(false),
+    sort_alphabetically: context.sort_alphabetically || is_alphabetical_sort(&options.sort),
+    prefer_unknown_over_any: context.prefer_unknown_over_any
+      || options.prefer_unknown_over_any.unwrap_or(false),
+    ..*context
+  };
+
+  TypeInterface {
+    expressions: schema_to_typescript_expressions(&schema, false, None, &context),
+    options,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::diagnostics::Severity;
+
+  #[test]
+  fn test_empty_object() {
+    let schema_json = r#"
+        {
+            "type": "object",
+            "properties": {
+                "metadata": {
+                    "type": "object",
+                    "properties": {}
+                }
+            }
+        }
+        "#;
+
+    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("EmptyObject".to_string()),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r##"export type EmptyObject = {
+  metadata?: {};
+};"##;
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
+
+  #[test]
+  fn test_basic_object() {
+    let schema_json = r#"
+        {
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "title": { "type": "string" },
+                "author": { "type": "string" },
+                "publishedDate": { "type": "string", "format": "date" },
+                "rating": { "type": "number", "format": "float" },
+                "age": { "type": "integer" }
+            }
+        }
+        "#;
+
+    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("Book".to_string()),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r##"export type Book = {
+  id?: string;
+  title?: string;
+  author?: string;
+  publishedDate?: string;
+  rating?: number;
+  age?: number;
+};"##;
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
+
+  #[test]
+  fn test_object_with_array() {
+    let schema_json = r#"
+        {
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "genres": { "type": "array", "items": { "type": "string" } },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            }
+        }
+        "#;
+
+    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("BookMetadata".to_string()),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r##"export type BookMetadata = {
+  id?: string;
+  genres?: string[];
+  tags?: string[];
+};"##;
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
+
+  #[test]
+  fn test_object_with_required_properties() {
+    let schema_json = r#"
+        {
+            "type": "object",
+            "properties": {
+                "title": { "type": "string" },
+                "author": { "type": "string" },
+                "genres": { "type": "array", "items": { "type": "string" } },
+                "publishedDate": { "type": "string", "format": "date" },
+                "rating": { "type": "number", "format": "float" }
+            },
+            "required": ["title", "author"]
+        }
+        "#;
+
+    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("NewBook".to_string()),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r##"export type NewBook = {
+  title: string;
+  author: string;
+  genres?: string[];
+  publishedDate?: string;
+  rating?: number;
+};"##;
+
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
+
+  #[test]
+  fn test_object_with_nullable_properties() {
+    let schema_json = r#"
+        {
+            "type": "object",
+            "properties": {
+                "reviewer": {
+                    "type": "string"
+                },
+                "comment": {
+                    "type": "string",
+                    "nullable": true
+                },
+                "rating": {
+                    "type": "number",
+                    "format": "float",
+                    "nullable": true
+                },
+                "date": {
+                    "type": "string",
+                    "format": "date-time",
+                    "nullable": true
+                }
+            }
+        }
+        "#;
+
+    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("Review".to_string()),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r##"export type Review = {
+  reviewer?: string;
+  comment?: string | null;
+  rating?: number | null;
+  date?: string | null;
+};"##;
+
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
+
+  #[test]
+  fn test_object_with_string_enum() {
+    let schema_json = r#"
+        {
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "status": {
+                    "type": "string",
+                    "enum": ["draft", "published", "archived"]
+                },
+                "visibility": {
+                    "type": "string",
+                    "enum": ["public", "private"],
+                    "nullable": true
+                },
+                "size": {
+                    "type": "array",
+                    "items": {
+                        "type": "string",
+                        "enum": ["small"],
+                        "nullable": true
+                    }
+                },
+                "options": {
+                    "type": "array",
+                    "items": {
+                        "type": "string",
+                        "enum": ["medium"]
+                    },
+                    "nullable": true
+                },
+                "tags": {
+                    "type": "array",
+                    "items": {
+                        "type": "string",
+                        "enum": ["featured", "trending", "new"]
+                    },
+                    "nullable": true
+                },
+                "categories": {
+                    "type": "array",
+                    "items": {
+                        "type": "string",
+                        "enum": ["tech", "science"],
+                        "nullable": true
+                    }
+                },
+                "isActive": {
+                    "type": "boolean",
+                    "enum": [true, false]
+                }
+            },
+            "required": ["id", "status"]
+        }
+        "#;
+
+    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("Post".to_string()),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r##"export type Post = {
+  id: string;
+  status: "draft" | "published" | "archived";
+  visibility?: "public" | "private" | null;
+  size?: ("small" | null)[];
+  options?: "medium"[] | null;
+  tags?: ("featured" | "trending" | "new")[] | null;
+  categories?: ("tech" | "science" | null)[];
+  isActive?: true | false;
+};"##;
+
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
+
+  #[test]
+  fn test_object_with_number_enum() {
+    let schema_json = r#"
+        {
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "priority": {
+                    "type": "number",
+                    "enum": [1.5, 2.5, 3.5]
+                },
+                "score": {
+                    "type": "number",
+                    "enum": [0.5, 1.0],
+                    "nullable": true
+                },
+                "size": {
+                    "type": "array",
+                    "items": {
+                        "type": "number",
+                        "enum": [1]
+                    },
+                    "nullable": true
+                },
+                "options": {
+                    "type": "array",
+                    "items": {
+                        "type": "number",
+                        "enum": [1]
+                    },
+                    "nullable": true
+                },
+                "weights": {
+                    "type": "array",
+                    "items": {
+                        "type": "number",
+                        "enum": [0.1, 0.2, 0.3]
+                    },
+                    "nullable": true
+                },
+                "metrics": {
+                    "type": "array",
+                    "items": {
+                        "type": "number",
+                        "enum": [1.1, 2.2],
+                        "nullable": true
+                    }
+                },
+                "isEnabled": {
+                    "type": "boolean",
+                    "enum": [true]
+                }
+            },
+            "required": ["id", "priority"]
+        }
+        "#;
+
+    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("Task".to_string()),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r##"export type Task = {
+  id: string;
+  priority: 1.5 | 2.5 | 3.5;
+  score?: 0.5 | 1 | null;
+  size?: 1[] | null;
+  options?: 1[] | null;
+  weights?: (0.1 | 0.2 | 0.3)[] | null;
+  metrics?: (1.1 | 2.2 | null)[];
+  isEnabled?: true;
+};"##;
+
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
+
+  #[test]
+  fn test_object_with_integer_enum() {
+    let schema_json = r#"
+        {
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "level": {
+                    "type": "integer",
+                    "enum": [1, 2, 3]
+                },
+                "grade": {
+                    "type": "integer",
+                    "enum": [0, 1],
+                    "nullable": true
+                },
+                "size": {
+                    "type": "array",
+                    "items": {
+                        "type": "number",
+                        "enum": [2]
+                    },
+                    "nullable": true
+                },
+                "options": {
+                    "type": "array",
+                    "items": {
+                        "type": "number",
+                        "enum": [2]
+                    },
+                    "nullable": true
+                },
+                "stages": {
+                    "type": "array",
+                    "items": {
+                        "type": "integer",
+                        "enum": [10, 20, 30]
+                    },
+                    "nullable": true
+                },
+                "points": {
+                    "type": "array",
+                    "items": {
+                        "type": "integer",
+                        "enum": [100, 200],
+                        "nullable": true
+                    }
+                },
+                "isPublic": {
+                    "type": "boolean",
+                    "enum": [false]
+                }
+            },
+            "required": ["id", "level"]
+        }
+        "#;
+
+    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("Grade".to_string()),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r##"export type Grade = {
+  id: string;
+  level: 1 | 2 | 3;
+  grade?: 0 | 1 | null;
+  size?: 2[] | null;
+  options?: 2[] | null;
+  stages?: (10 | 20 | 30)[] | null;
+  points?: (100 | 200 | null)[];
+  isPublic?: false;
+};"##;
+
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
+
+  #[test]
+  fn test_object_with_boolean_enum() {
+    let schema_json = r#"
+        {
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "isActive": {
+                    "type": "boolean",
+                    "enum": [true]
+                },
+                "isPublic": {
+                    "type": "boolean",
+                    "enum": [false],
+                    "nullable": true
+                },
+                "flags": {
+                    "type": "array",
+                    "items": {
+                        "type": "boolean",
+                        "enum": [true]
+                    },
+                    "nullable": true
+                },
+                "settings": {
+                    "type": "array",
+                    "items": {
+                        "type": "boolean",
+                        "enum": [false],
+                        "nullable": true
+                    }
+                }
+            },
+            "required": ["id", "isActive"]
+        }
+        "#;
+
+    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("Config".to_string()),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r##"export type Config = {
+  id: string;
+  isActive: true;
+  isPublic?: false | null;
+  flags?: true[] | null;
+  settings?: (false | null)[];
+};"##;
+
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
+
+  #[test]
+  fn test_object_with_invalid_property() {
+    let schema_json = r#"
+        {
+            "type": "object",
+            "properties": {
+                "invalid_property": {
+                    "required": ["invalid_property"],
+                    "anyOf": [
+                        {
+                            "type": "string"
+                        },
+                        {
+                            "type": "number"
+                        }
+                    ]
+                }
+            }
+        }
+        "#;
+
+    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("InvalidObject".to_string()),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r##"export type InvalidObject = {
+  invalid_property?: string | number;
+};"##;
+
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
+
+  #[test]
+  fn test_object_with_mixed_enums_oneof() {
+    let schema_json = r##"
+        {
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "value": {
+                    "oneOf": [
+                        {
+                            "type": "string",
+                            "enum": ["low", "medium", "high"]
+                        },
+                        {
+                            "type": "integer",
+                            "enum": [0, 1, 2]
+                        },
+                        {
+                            "type": "number",
+                            "enum": [0.5, 1.5, 2.5]
+                        },
+                        {
+                            "type": "array",
+                            "items": {
+                                "type": "string",
+                                "enum": ["A", "B", "C"]
+                            }
+                        },
+                        {
+                            "type": "boolean",
+                            "enum": [true, false]
+                        },
+                        {
+                            "$ref": "#/components/schemas/ExternalRef"
+                        }
+                    ]
+                },
+                "mixedArray": {
+                    "type": "array",
+                    "items": {
+                        "oneOf": [
+                            {
+                                "type": "string",
+                                "enum": ["red", "green", "blue"]
+                            },
+                            {
+                                "type": "number",
+                                "enum": [1, 2, 3]
+                            },
+                            {
+                                "type": "boolean",
+                                "enum": [true]
+                            },
+                            {
+                                "type": "string",
+                                "enum": ["small", "medium", "large"]
+                            },
+                            {
+                                "$ref": "#/components/schemas/ExternalRef"
+                            }
+                        ]
+                    }
+                }
+            },
+            "required": ["id", "value", "mixedArray"]
+        }
+        "##;
+
+    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("MixedEnum".to_string()),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r##"export type MixedEnum = {
+  id: string;
+  value: "low" | "medium" | "high" | 0 | 1 | 2 | 0.5 | 1.5 | 2.5 | ("A" | "B" | "C")[] | true | false | ExternalRef;
+  mixedArray: ("red" | "green" | "blue" | 1 | 2 | 3 | true | "small" | "medium" | "large" | ExternalRef)[];
+};"##;
+
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
+
+  #[test]
+  fn test_object_with_mixed_enums_anyof() {
+    let schema_json = r##"
+        {
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "value": {
+                    "anyOf": [
+                        {
+                            "type": "string",
+                            "enum": ["low", "medium", "high"]
+                        },
+                        {
+                            "type": "integer",
+                            "enum": [0, 1, 2]
+                        },
+                        {
+                            "type": "number",
+                            "enum": [0.5, 1.5, 2.5]
+                        },
+                        {
+                            "type": "array",
+                            "items": {
+                                "type": "string",
+                                "enum": ["A", "B", "C"]
+                            }
+                        },
+                        {
+                            "type": "boolean",
+                            "enum": [true, false]
+                        },
+                        {
+                            "$ref": "#/components/schemas/ExternalRef"
+                        }
+                    ]
+                },
+                "mixedArray": {
+                    "type": "array",
+                    "items": {
+                        "anyOf": [
+                            {
+                                "type": "string",
+                                "enum": ["red", "green", "blue"]
+                            },
+                            {
+                                "type": "number",
+                                "enum": [1, 2, 3]
+                            },
+                            {
+                                "type": "boolean",
+                                "enum": [true]
+                            },
+                            {
+                                "type": "string",
+                                "enum": ["small", "medium", "large"]
+                            },
+                            {
+                                "$ref": "#/components/schemas/ExternalRef"
+                            }
+                        ]
+                    }
+                }
+            },
+            "required": ["id", "value", "mixedArray"]
+        }
+        "##;
+
+    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("MixedEnum".to_string()),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r##"export type MixedEnum = {
+  id: string;
+  value: "low" | "medium" | "high" | 0 | 1 | 2 | 0.5 | 1.5 | 2.5 | ("A" | "B" | "C")[] | true | false | ExternalRef;
+  mixedArray: ("red" | "green" | "blue" | 1 | 2 | 3 | true | "small" | "medium" | "large" | ExternalRef)[];
+};"##;
+
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
+
+  #[test]
+  fn test_object_with_mixed_enums_allof() {
+    let schema_json = r##"
+        {
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "value": {
+                    "allOf": [
+                        {
+                            "type": "object",
+                            "properties": {
+                                "status": {
+                                    "type": "string",
+                                    "enum": ["active", "inactive"]
+                                }
+                            }
+                        },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "priority": {
+                                    "type": "number",
+                                    "enum": [1, 2, 3]
+                                }
+                            }
+                        },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "isEnabled": {
+                                    "type": "boolean",
+                                    "enum": [true]
+                                }
+                            }
+                        },
+                        {
+                            "$ref": "#/components/schemas/ExternalRef"
+                        }
+                    ]
+                }
+            },
+            "required": ["id", "value"]
+        }
+        "##;
+
+    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("MixedEnumAllOf".to_string()),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r##"export type MixedEnumAllOf = {
+  id: string;
+  value: {
+    status?: "active" | "inactive";
+  } & {
+    priority?: 1 | 2 | 3;
+  } & {
+    isEnabled?: true;
+  } & ExternalRef;
+};"##;
+
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
 
   #[test]
-  fn test_empty_object() {
-    let schema_json = r#"
+  fn test_object_with_oneof() {
+    let schema_json = r##"
         {
-            "type": "object",
-            "properties": {
-                "metadata": {
+            "oneOf": [
+                { "$ref": "#/components/schemas/Book" },
+                {
                     "type": "object",
-                    "properties": {}
+                    "properties": {
+                        "query": { "type": "string" },
+                        "genres": { "type": "array", "items": { "type": "string" } },
+                        "rating": { "type": "number", "format": "float" }
+                    }
                 }
-            }
+            ]
         }
-        "#;
+        "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("EmptyObject".to_string()),
+        name: Some("SearchCriteria".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type EmptyObject = {
-  metadata?: {};
+    let expected = r##"export type SearchCriteria = Book | {
+  query?: string;
+  genres?: string[];
+  rating?: number;
 };"##;
+
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_basic_object() {
-    let schema_json = r#"
+  fn test_object_with_allof() {
+    let schema_json = r##"
         {
-            "type": "object",
-            "properties": {
-                "id": { "type": "string" },
-                "title": { "type": "string" },
-                "author": { "type": "string" },
-                "publishedDate": { "type": "string", "format": "date" },
-                "rating": { "type": "number", "format": "float" },
-                "age": { "type": "integer" }
-            }
+            "allOf": [
+                { "$ref": "#/components/schemas/Book" },
+                {
+                    "type": "object",
+                    "properties": {
+                        "query": { "type": "string" },
+                        "genres": { "type": "array", "items": { "type": "string" } },
+                        "rating": { "type": "number", "format": "float" }
+                    }
+                }
+            ]
         }
-        "#;
+        "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("Book".to_string()),
+        name: Some("BookWithMetadata".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type Book = {
-  id?: string;
-  title?: string;
-  author?: string;
-  publishedDate?: string;
+    let expected = r##"export type BookWithMetadata = Book & {
+  query?: string;
+  genres?: string[];
   rating?: number;
+};"##;
+
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
+
+  #[test]
+  fn test_object_with_anyof() {
+    let schema_json = r##"
+        {
+            "anyOf": [
+                {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "age": { "type": "number" }
+                    },
+                    "required": ["name"]
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "string" },
+                        "role": {
+                            "type": "string",
+                            "enum": ["admin", "user"]
+                        }
+                    },
+                    "required": ["id", "role"]
+                }
+            ]
+        }
+        "##;
+
+    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("UserInfo".to_string()),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r##"export type UserInfo = {
+  name: string;
   age?: number;
+} | {
+  id: string;
+  role: "admin" | "user";
 };"##;
+
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_array() {
-    let schema_json = r#"
+  fn test_array_with_oneof() {
+    let schema_json = r##"
         {
-            "type": "object",
-            "properties": {
-                "id": { "type": "string" },
-                "genres": { "type": "array", "items": { "type": "string" } },
-                "tags": { "type": "array", "items": { "type": "string" } }
+            "type": "array",
+            "items": {
+                "oneOf": [
+                    { "type": "string" },
+                    { "type": "number" },
+                    {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "value": { "type": "number" }
+                        },
+                        "required": ["name", "value"]
+                    }
+                ]
             }
         }
-        "#;
+        "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("BookMetadata".to_string()),
+        name: Some("MixedArray".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type BookMetadata = {
-  id?: string;
-  genres?: string[];
-  tags?: string[];
-};"##;
+    let expected = r##"export type MixedArray = (string | number | {
+  name: string;
+  value: number;
+})[];"##;
+
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_required_properties() {
-    let schema_json = r#"
+  fn test_array_with_allof() {
+    let schema_json = r##"
         {
-            "type": "object",
-            "properties": {
-                "title": { "type": "string" },
-                "author": { "type": "string" },
-                "genres": { "type": "array", "items": { "type": "string" } },
-                "publishedDate": { "type": "string", "format": "date" },
-                "rating": { "type": "number", "format": "float" }
-            },
-            "required": ["title", "author"]
+            "type": "array",
+            "items": {
+                "allOf": [
+                    { "type": "object",
+                      "properties": {
+                          "id": { "type": "string" },
+                          "name": { "type": "string" }
+                      },
+                      "required": ["id"]
+                    },
+                    {
+                        "type": "object",
+                        "properties": {
+                            "metadata": {
+                                "type": "object",
+                                "properties": {
+                                    "created": { "type": "string" },
+                                    "modified": { "type": "string" }
+                                }
+                            }
+                        }
+                    }
+                ]
+            }
         }
-        "#;
+        "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("NewBook".to_string()),
+        name: Some("CombinedArray".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type NewBook = {
-  title: string;
-  author: string;
-  genres?: string[];
-  publishedDate?: string;
-  rating?: number;
-};"##;
+    let expected = r##"export type CombinedArray = ({
+  id: string;
+  name?: string;
+} & {
+  metadata?: {
+    created?: string;
+    modified?: string;
+  };
+})[];"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_nullable_properties() {
-    let schema_json = r#"
+  fn test_array_with_anyof() {
+    let schema_json = r##"
         {
-            "type": "object",
-            "properties": {
-                "reviewer": {
-                    "type": "string"
-                },
-                "comment": {
-                    "type": "string",
-                    "nullable": true
-                },
-                "rating": {
-                    "type": "number",
-                    "format": "float",
-                    "nullable": true
-                },
-                "date": {
-                    "type": "string",
-                    "format": "date-time",
-                    "nullable": true
-                }
+            "type": "array",
+            "items": {
+                "anyOf": [
+                    { "type": "string" },
+                    { "type": "number" },
+                    {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "value": { "type": "number" }
+                        },
+                        "required": ["name", "value"]
+                    }
+                ]
             }
         }
-        "#;
+        "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("Review".to_string()),
+        name: Some("MixedAnyArray".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type Review = {
-  reviewer?: string;
-  comment?: string | null;
-  rating?: number | null;
-  date?: string | null;
-};"##;
+    let expected = r##"export type MixedAnyArray = (string | number | {
+  name: string;
+  value: number;
+})[];"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_string_enum() {
-    let schema_json = r#"
+  fn test_object_with_anyof_array_primitive_object() {
+    let schema_json = r##"
         {
             "type": "object",
             "properties": {
-                "id": { "type": "string" },
-                "status": {
-                    "type": "string",
-                    "enum": ["draft", "published", "archived"]
-                },
-                "visibility": {
-                    "type": "string",
-                    "enum": ["public", "private"],
-                    "nullable": true
-                },
-                "size": {
-                    "type": "array",
-                    "items": {
-                        "type": "string",
-                        "enum": ["small"],
-                        "nullable": true
-                    }
-                },
-                "options": {
-                    "type": "array",
-                    "items": {
-                        "type": "string",
-                        "enum": ["medium"]
-                    },
-                    "nullable": true
-                },
-                "tags": {
-                    "type": "array",
-                    "items": {
-                        "type": "string",
-                        "enum": ["featured", "trending", "new"]
-                    },
-                    "nullable": true
-                },
-                "categories": {
-                    "type": "array",
-                    "items": {
-                        "type": "string",
-                        "enum": ["tech", "science"],
-                        "nullable": true
-                    }
+                "key": {
+                    "type": "string"
                 },
-                "isActive": {
-                    "type": "boolean",
-                    "enum": [true, false]
+                "value": {
+                    "anyOf": [
+                        {
+                            "type": "array",
+                            "items": {
+                                "type": "number"
+                            }
+                        },
+                        {
+                            "type": "string"
+                        },
+                        {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "name": { "type": "string" },
+                                    "count": { "type": "number" }
+                                },
+                                "required": ["name", "count"]
+                            }
+                        },
+                        {
+                            "type": "array",
+                            "items": {
+                                "$ref": "#/components/schemas/SomeType"
+                            }
+                        }
+                    ]
                 }
             },
-            "required": ["id", "status"]
+            "required": [
+                "key",
+                "value"
+            ]
         }
-        "#;
+        "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("Post".to_string()),
+        name: Some("MixedValue".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type Post = {
-  id: string;
-  status: "draft" | "published" | "archived";
-  visibility?: "public" | "private" | null;
-  size?: ("small" | null)[];
-  options?: "medium"[] | null;
-  tags?: ("featured" | "trending" | "new")[] | null;
-  categories?: ("tech" | "science" | null)[];
-  isActive?: true | false;
+    let expected = r##"export type MixedValue = {
+  key: string;
+  value: number[] | string | {
+    name: string;
+    count: number;
+  }[] | SomeType[];
 };"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_number_enum() {
-    let schema_json = r#"
+  fn test_object_with_oneof_array_primitive_object() {
+    let schema_json = r##"
         {
             "type": "object",
             "properties": {
-                "id": { "type": "string" },
-                "priority": {
-                    "type": "number",
-                    "enum": [1.5, 2.5, 3.5]
-                },
-                "score": {
-                    "type": "number",
-                    "enum": [0.5, 1.0],
-                    "nullable": true
-                },
-                "size": {
-                    "type": "array",
-                    "items": {
-                        "type": "number",
-                        "enum": [1]
-                    },
-                    "nullable": true
-                },
-                "options": {
-                    "type": "array",
-                    "items": {
-                        "type": "number",
-                        "enum": [1]
-                    },
-                    "nullable": true
-                },
-                "weights": {
-                    "type": "array",
-                    "items": {
-                        "type": "number",
-                        "enum": [0.1, 0.2, 0.3]
-                    },
-                    "nullable": true
-                },
-                "metrics": {
-                    "type": "array",
-                    "items": {
-                        "type": "number",
-                        "enum": [1.1, 2.2],
-                        "nullable": true
-                    }
+                "key": {
+                    "type": "string"
                 },
-                "isEnabled": {
-                    "type": "boolean",
-                    "enum": [true]
+                "value": {
+                    "oneOf": [
+                        {
+                            "type": "array",
+                            "items": {
+                                "type": "number"
+                            }
+                        },
+                        {
+                            "type": "string"
+                        },
+                        {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "name": { "type": "string" },
+                                    "count": { "type": "number" }
+                                },
+                                "required": ["name", "count"]
+                            }
+                        },
+                        {
+                            "type": "array",
+                            "items": {
+                                "$ref": "#/components/schemas/SomeType"
+                            }
+                        }
+                    ]
                 }
             },
-            "required": ["id", "priority"]
+            "required": [
+                "key",
+                "value"
+            ]
         }
-        "#;
+        "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("Task".to_string()),
+        name: Some("MixedValue".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type Task = {
-  id: string;
-  priority: 1.5 | 2.5 | 3.5;
-  score?: 0.5 | 1 | null;
-  size?: 1[] | null;
-  options?: 1[] | null;
-  weights?: (0.1 | 0.2 | 0.3)[] | null;
-  metrics?: (1.1 | 2.2 | null)[];
-  isEnabled?: true;
-};"##;
-
-    assert_eq!(type_interface.to_string(), expected.to_string());
-  }
-
-  #[test]
-  fn test_object_with_integer_enum() {
-    let schema_json = r#"
-        {
-            "type": "object",
-            "properties": {
-                "id": { "type": "string" },
-                "level": {
-                    "type": "integer",
-                    "enum": [1, 2, 3]
-                },
-                "grade": {
-                    "type": "integer",
-                    "enum": [0, 1],
-                    "nullable": true
-                },
-                "size": {
-                    "type": "array",
-                    "items": {
-                        "type": "number",
-                        "enum": [2]
-                    },
-                    "nullable": true
-                },
-                "options": {
-                    "type": "array",
-                    "items": {
-                        "type": "number",
-                        "enum": [2]
-                    },
-                    "nullable": true
-                },
-                "stages": {
-                    "type": "array",
-                    "items": {
-                        "type": "integer",
-                        "enum": [10, 20, 30]
+    let expected = r##"export type MixedValue = {
+  key: string;
+  value: number[] | string | {
+    name: string;
+    count: number;
+  }[] | SomeType[];
+};"##;
+
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
+
+  #[test]
+  fn test_object_with_nested_objects() {
+    let schema_json = r#"
+        {
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "name": { "type": "string" },
+                "address": {
+                    "type": "object",
+                    "properties": {
+                        "street": { "type": "string" },
+                        "city": { "type": "string" },
+                        "country": { "type": "string" },
+                        "coordinates": {
+                            "type": "object",
+                            "properties": {
+                                "latitude": { "type": "number" },
+                                "longitude": { "type": "number" }
+                            },
+                            "required": ["latitude", "longitude"]
+                        }
                     },
-                    "nullable": true
-                },
-                "points": {
-                    "type": "array",
-                    "items": {
-                        "type": "integer",
-                        "enum": [100, 200],
-                        "nullable": true
-                    }
-                },
-                "isPublic": {
-                    "type": "boolean",
-                    "enum": [false]
+                    "required": ["street", "city"]
                 }
             },
-            "required": ["id", "level"]
+            "required": ["id", "name", "address"]
         }
         "#;
 
@@ -998,59 +3096,51 @@ mod tests {
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("Grade".to_string()),
+        name: Some("Location".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type Grade = {
+    let expected = r##"export type Location = {
   id: string;
-  level: 1 | 2 | 3;
-  grade?: 0 | 1 | null;
-  size?: 2[] | null;
-  options?: 2[] | null;
-  stages?: (10 | 20 | 30)[] | null;
-  points?: (100 | 200 | null)[];
-  isPublic?: false;
+  name: string;
+  address: {
+    street: string;
+    city: string;
+    country?: string;
+    coordinates?: {
+      latitude: number;
+      longitude: number;
+    };
+  };
 };"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_boolean_enum() {
+  fn test_object_with_nested_arrays() {
     let schema_json = r#"
         {
             "type": "object",
             "properties": {
                 "id": { "type": "string" },
-                "isActive": {
-                    "type": "boolean",
-                    "enum": [true]
-                },
-                "isPublic": {
-                    "type": "boolean",
-                    "enum": [false],
-                    "nullable": true
-                },
-                "flags": {
-                    "type": "array",
-                    "items": {
-                        "type": "boolean",
-                        "enum": [true]
-                    },
-                    "nullable": true
-                },
-                "settings": {
+                "categories": {
                     "type": "array",
                     "items": {
-                        "type": "boolean",
-                        "enum": [false],
-                        "nullable": true
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "subcategories": {
+                                "type": "array",
+                                "items": { "type": "string" }
+                            }
+                        },
+                        "required": ["name"]
                     }
                 }
             },
-            "required": ["id", "isActive"]
+            "required": ["id"]
         }
         "#;
 
@@ -1059,40 +3149,81 @@ mod tests {
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("Config".to_string()),
+        name: Some("Product".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type Config = {
+    let expected = r##"export type Product = {
   id: string;
-  isActive: true;
-  isPublic?: false | null;
-  flags?: true[] | null;
-  settings?: (false | null)[];
+  categories?: {
+    name: string;
+    subcategories?: string[];
+  }[];
 };"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_invalid_property() {
+  fn test_object_with_complex_nested_arrays() {
     let schema_json = r#"
         {
             "type": "object",
             "properties": {
-                "invalid_property": {
-                    "required": ["invalid_property"],
-                    "anyOf": [
-                        {
-                            "type": "string"
+                "id": { "type": "string" },
+                "departments": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "teams": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "teamName": { "type": "string" },
+                                        "members": {
+                                            "type": "array",
+                                            "items": {
+                                                "type": "object",
+                                                "properties": {
+                                                    "id": { "type": "string" },
+                                                    "name": { "type": "string" },
+                                                    "skills": {
+                                                        "type": "array",
+                                                        "items": {
+                                                            "type": "object",
+                                                            "properties": {
+                                                                "name": { "type": "string" },
+                                                                "level": { "type": "number" },
+                                                                "certifications": {
+                                                                    "type": "array",
+                                                                    "items": { "type": "string" }
+                                                                }
+                                                            },
+                                                            "required": ["name", "level"]
+                                                        }
+                                                    }
+                                                },
+                                                "required": ["id", "name"]
+                                            }
+                                        },
+                                        "projects": {
+                                            "type": "array",
+                                            "items": { "type": "string" }
+                                        }
+                                    },
+                                    "required": ["teamName", "members"]
+                                }
+                            }
                         },
-                        {
-                            "type": "number"
-                        }
-                    ]
+                        "required": ["name", "teams"]
+                    }
                 }
-            }
+            },
+            "required": ["id", "departments"]
         }
         "#;
 
@@ -1101,83 +3232,74 @@ mod tests {
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("InvalidObject".to_string()),
+        name: Some("Organization".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type InvalidObject = {
-  invalid_property?: any;
+    let expected = r##"export type Organization = {
+  id: string;
+  departments: {
+    name: string;
+    teams: {
+      teamName: string;
+      members: {
+        id: string;
+        name: string;
+        skills?: {
+          name: string;
+          level: number;
+          certifications?: string[];
+        }[];
+      }[];
+      projects?: string[];
+    }[];
+  }[];
 };"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_mixed_enums_oneof() {
+  fn test_nested_object_with_array_oneof() {
     let schema_json = r##"
         {
             "type": "object",
             "properties": {
                 "id": { "type": "string" },
-                "value": {
-                    "oneOf": [
-                        {
-                            "type": "string",
-                            "enum": ["low", "medium", "high"]
-                        },
-                        {
-                            "type": "integer",
-                            "enum": [0, 1, 2]
-                        },
-                        {
-                            "type": "number",
-                            "enum": [0.5, 1.5, 2.5]
-                        },
-                        {
+                "metadata": {
+                    "type": "object",
+                    "properties": {
+                        "title": { "type": "string" },
+                        "tags": {
                             "type": "array",
                             "items": {
-                                "type": "string",
-                                "enum": ["A", "B", "C"]
-                            }
-                        },
-                        {
-                            "type": "boolean",
-                            "enum": [true, false]
-                        },
-                        {
-                            "$ref": "#/components/schemas/ExternalRef"
-                        }
-                    ]
-                },
-                "mixedArray": {
-                    "type": "array",
-                    "items": {
-                        "oneOf": [
-                            {
-                                "type": "string",
-                                "enum": ["red", "green", "blue"]
-                            },
-                            {
-                                "type": "number",
-                                "enum": [1, 2, 3]
-                            },
-                            {
-                                "type": "boolean",
-                                "enum": [true]
-                            },
-                            {
-                                "type": "string",
-                                "enum": ["small", "medium", "large"]
-                            },
-                            {
-                                "$ref": "#/components/schemas/ExternalRef"
+                                "oneOf": [
+                                    { "type": "string" },
+                                    {
+                                        "type": "object",
+                                        "properties": {
+                                            "name": { "type": "string" },
+                                            "value": { "type": "number" },
+                                            "metadata": {
+                                                "type": "object",
+                                                "properties": {
+                                                    "description": { "type": "string" },
+                                                    "priority": { "type": "number" }
+                                                },
+                                                "required": ["description"]
+                                            }
+                                        },
+                                        "required": ["name", "value"]
+                                    }
+                                ]
                             }
-                        ]
-                    }
+                        }
+                    },
+                    "required": ["title", "tags"]
                 }
             },
-            "required": ["id", "value", "mixedArray"]
+            "required": ["id", "metadata"]
         }
         "##;
 
@@ -1186,150 +3308,195 @@ mod tests {
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("MixedEnum".to_string()),
+        name: Some("DeepArray".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type MixedEnum = {
+    let expected = r##"export type DeepArray = {
   id: string;
-  value: "low" | "medium" | "high" | 0 | 1 | 2 | 0.5 | 1.5 | 2.5 | ("A" | "B" | "C")[] | true | false | ExternalRef;
-  mixedArray: ("red" | "green" | "blue" | 1 | 2 | 3 | true | "small" | "medium" | "large" | ExternalRef)[];
+  metadata: {
+    title: string;
+    tags: (string | {
+      name: string;
+      value: number;
+      metadata?: {
+        description: string;
+        priority?: number;
+      };
+    })[];
+  };
 };"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_mixed_enums_anyof() {
-    let schema_json = r##"
-        {
-            "type": "object",
-            "properties": {
-                "id": { "type": "string" },
-                "value": {
-                    "anyOf": [
-                        {
-                            "type": "string",
-                            "enum": ["low", "medium", "high"]
-                        },
-                        {
-                            "type": "integer",
-                            "enum": [0, 1, 2]
-                        },
-                        {
-                            "type": "number",
-                            "enum": [0.5, 1.5, 2.5]
-                        },
-                        {
-                            "type": "array",
-                            "items": {
-                                "type": "string",
-                                "enum": ["A", "B", "C"]
-                            }
-                        },
-                        {
-                            "type": "boolean",
-                            "enum": [true, false]
-                        },
-                        {
-                            "$ref": "#/components/schemas/ExternalRef"
-                        }
-                    ]
-                },
-                "mixedArray": {
-                    "type": "array",
-                    "items": {
-                        "anyOf": [
-                            {
-                                "type": "string",
-                                "enum": ["red", "green", "blue"]
-                            },
-                            {
-                                "type": "number",
-                                "enum": [1, 2, 3]
-                            },
-                            {
-                                "type": "boolean",
-                                "enum": [true]
-                            },
-                            {
-                                "type": "string",
-                                "enum": ["small", "medium", "large"]
-                            },
-                            {
-                                "$ref": "#/components/schemas/ExternalRef"
-                            }
-                        ]
-                    }
-                }
-            },
-            "required": ["id", "value", "mixedArray"]
-        }
-        "##;
+  fn test_nested_object_with_array_allof() {
+    let schema_json = r#"
+          {
+              "type": "object",
+              "properties": {
+                  "id": { "type": "string" },
+                  "metadata": {
+                      "type": "object",
+                      "properties": {
+                          "title": { "type": "string" },
+                          "tags": {
+                              "type": "array",
+                              "items": {
+                                  "allOf": [
+                                      {
+                                          "type": "object",
+                                          "properties": {
+                                              "id": { "type": "string" },
+                                              "type": { "type": "string" }
+                                          },
+                                          "required": ["id"]
+                                      },
+                                      {
+                                          "type": "object",
+                                          "properties": {
+                                              "metadata": {
+                                                  "type": "object",
+                                                  "properties": {
+                                                      "description": { "type": "string" },
+                                                      "created": { "type": "string" }
+                                                  }
+                                              }
+                                          }
+                                      }
+                                  ]
+                              }
+                          }
+                      },
+                      "required": ["title", "tags"]
+                  }
+              },
+              "required": ["id", "metadata"]
+          }
+          "#;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("MixedEnum".to_string()),
+        name: Some("DeepArrayAllOf".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type MixedEnum = {
+    let expected = r##"export type DeepArrayAllOf = {
   id: string;
-  value: "low" | "medium" | "high" | 0 | 1 | 2 | 0.5 | 1.5 | 2.5 | ("A" | "B" | "C")[] | true | false | ExternalRef;
-  mixedArray: ("red" | "green" | "blue" | 1 | 2 | 3 | true | "small" | "medium" | "large" | ExternalRef)[];
+  metadata: {
+    title: string;
+    tags: ({
+      id: string;
+      type?: string;
+    } & {
+      metadata?: {
+        description?: string;
+        created?: string;
+      };
+    })[];
+  };
 };"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_mixed_enums_allof() {
-    let schema_json = r##"
-        {
-            "type": "object",
-            "properties": {
-                "id": { "type": "string" },
-                "value": {
-                    "allOf": [
-                        {
-                            "type": "object",
-                            "properties": {
-                                "status": {
-                                    "type": "string",
-                                    "enum": ["active", "inactive"]
-                                }
-                            }
-                        },
-                        {
-                            "type": "object",
-                            "properties": {
-                                "priority": {
-                                    "type": "number",
-                                    "enum": [1, 2, 3]
-                                }
-                            }
-                        },
-                        {
-                            "type": "object",
-                            "properties": {
-                                "isEnabled": {
-                                    "type": "boolean",
-                                    "enum": [true]
-                                }
-                            }
-                        },
-                        {
-                            "$ref": "#/components/schemas/ExternalRef"
+  fn test_nested_object_with_array_anyof() {
+    let schema_json = r#"
+          {
+              "type": "object",
+              "properties": {
+                  "id": { "type": "string" },
+                  "metadata": {
+                      "type": "object",
+                      "properties": {
+                          "title": { "type": "string" },
+                          "tags": {
+                              "type": "array",
+                              "items": {
+                                  "anyOf": [
+                                      { "type": "string" },
+                                      {
+                                          "type": "object",
+                                          "properties": {
+                                              "name": { "type": "string" },
+                                              "value": { "type": "number" },
+                                              "metadata": {
+                                                  "type": "object",
+                                                  "properties": {
+                                                      "description": { "type": "string" },
+                                                      "priority": { "type": "number" }
+                                                  },
+                                                  "required": ["description"]
+                                              }
+                                          },
+                                          "required": ["name", "value"]
+                                      }
+                                  ]
+                              }
+                          }
+                      },
+                      "required": ["title", "tags"]
+                  }
+              },
+              "required": ["id", "metadata"]
+          }
+          "#;
+
+    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("DeepArrayAny".to_string()),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r##"export type DeepArrayAny = {
+  id: string;
+  metadata: {
+    title: string;
+    tags: (string | {
+      name: string;
+      value: number;
+      metadata?: {
+        description: string;
+        priority?: number;
+      };
+    })[];
+  };
+};"##;
+
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
+
+  #[test]
+  fn test_object_with_deep_array_refs() {
+    let schema_json = r##"
+        {
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "data": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "references": {
+                            "type": "array",
+                            "items": { "$ref": "#/components/schemas/ExternalRef" }
                         }
-                    ]
+                    },
+                    "required": ["name", "references"]
                 }
             },
-            "required": ["id", "value"]
+            "required": ["id", "data"]
         }
         "##;
 
@@ -1338,40 +3505,79 @@ mod tests {
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("MixedEnumAllOf".to_string()),
+        name: Some("DeepRefArray".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type MixedEnumAllOf = {
+    let expected = r##"export type DeepRefArray = {
   id: string;
-  value: {
-    status?: "active" | "inactive";
-  } & {
-    priority?: 1 | 2 | 3;
-  } & {
-    isEnabled?: true;
-  } & ExternalRef;
+  data: {
+    name: string;
+    references: ExternalRef[];
+  };
 };"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_oneof() {
+  fn test_object_with_property_descriptions() {
     let schema_json = r##"
         {
-            "oneOf": [
-                { "$ref": "#/components/schemas/Book" },
-                {
+            "type": "object",
+            "description": "Root object description",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Unique identifier"
+                },
+                "numbers": {
+                    "type": "array",
+                    "description": "List of important numbers",
+                    "items": {
+                        "type": "number",
+                        "description": "A numeric value"
+                    }
+                },
+                "flags": {
                     "type": "object",
+                    "description": "Configuration flags",
                     "properties": {
-                        "query": { "type": "string" },
-                        "genres": { "type": "array", "items": { "type": "string" } },
-                        "rating": { "type": "number", "format": "float" }
+                        "isEnabled": {
+                            "type": "boolean",
+                            "description": "Whether feature is enabled"
+                        },
+                        "priority": {
+                            "type": "integer",
+                            "description": "Priority level 1-5",
+                            "minimum": 1,
+                            "maximum": 5
+                        }
+                    },
+                    "required": ["isEnabled"]
+                },
+                "metadata": {
+                    "type": "array",
+                    "description": "List of metadata objects",
+                    "items": {
+                        "type": "object",
+                        "description": "Metadata entry",
+                        "properties": {
+                            "key": {
+                                "type": "string",
+                                "description": "Metadata key"
+                            },
+                            "value": {
+                                "type": "string",
+                                "description": "Metadata value"
+                            }
+                        },
+                        "required": ["key", "value"]
                     }
                 }
-            ]
+            },
+            "required": ["id", "flags", "metadata"]
         }
         "##;
 
@@ -1380,35 +3586,123 @@ mod tests {
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("SearchCriteria".to_string()),
+        name: Some("ComplexObject".to_string()),
         ..Default::default()
       }),
     );
-
-    let expected = r##"export type SearchCriteria = Book | {
-  query?: string;
-  genres?: string[];
-  rating?: number;
+    let expected = r##"export type ComplexObject = {
+  /**
+   * Unique identifier
+   */
+  id: string;
+  /**
+   * List of important numbers
+   */
+  numbers?: number[];
+  /**
+   * Configuration flags
+   */
+  flags: {
+    /**
+     * Whether feature is enabled
+     */
+    isEnabled: boolean;
+    /**
+     * Priority level 1-5
+     */
+    priority?: number;
+  };
+  /**
+   * List of metadata objects
+   */
+  metadata: {
+    /**
+     * Metadata key
+     */
+    key: string;
+    /**
+     * Metadata value
+     */
+    value: string;
+  }[];
 };"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_allof() {
+  fn test_object_with_deprecated_properties() {
     let schema_json = r##"
         {
-            "allOf": [
-                { "$ref": "#/components/schemas/Book" },
-                {
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Unique identifier"
+                },
+                "oldName": {
+                    "type": "string",
+                    "description": "Use name instead",
+                    "deprecated": true
+                },
+                "name": {
+                    "type": "string",
+                    "description": "The current name field"
+                },
+                "oldTags": {
+                    "type": "array",
+                    "description": "Use categories instead",
+                    "deprecated": true,
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "categories": {
+                    "type": "array",
+                    "description": "Current categories field",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "config": {
                     "type": "object",
                     "properties": {
-                        "query": { "type": "string" },
-                        "genres": { "type": "array", "items": { "type": "string" } },
-                        "rating": { "type": "number", "format": "float" }
+                        "oldSetting": {
+                            "type": "boolean",
+                            "description": "Deprecated setting - use newSetting",
+                            "deprecated": true
+                        },
+                        "newSetting": {
+                            "type": "boolean",
+                            "description": "Current setting to use"
+                        },
+                        "oldOptions": {
+                            "type": "array",
+                            "description": "Deprecated options array - use newOptions",
+                            "deprecated": true,
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "key": { "type": "string" },
+                                    "value": { "type": "string" }
+                                }
+                            }
+                        },
+                        "newOptions": {
+                            "type": "array",
+                            "description": "Current options to use",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "key": { "type": "string" },
+                                    "value": { "type": "string" }
+                                }
+                            }
+                        }
                     }
                 }
-            ]
+            },
+            "required": ["id", "name"]
         }
         "##;
 
@@ -1417,578 +3711,470 @@ mod tests {
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("BookWithMetadata".to_string()),
+        name: Some("ObjectWithDeprecated".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type BookWithMetadata = Book & {
-  query?: string;
-  genres?: string[];
-  rating?: number;
+    let expected = r##"export type ObjectWithDeprecated = {
+  /**
+   * Unique identifier
+   */
+  id: string;
+  /**
+   * @deprecated Use name instead
+   */
+  oldName?: string;
+  /**
+   * The current name field
+   */
+  name: string;
+  /**
+   * @deprecated Use categories instead
+   */
+  oldTags?: string[];
+  /**
+   * Current categories field
+   */
+  categories?: string[];
+  config?: {
+    /**
+     * @deprecated Deprecated setting - use newSetting
+     */
+    oldSetting?: boolean;
+    /**
+     * Current setting to use
+     */
+    newSetting?: boolean;
+    /**
+     * @deprecated Deprecated options array - use newOptions
+     */
+    oldOptions?: {
+      key?: string;
+      value?: string;
+    }[];
+    /**
+     * Current options to use
+     */
+    newOptions?: {
+      key?: string;
+      value?: string;
+    }[];
+  };
 };"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_anyof() {
+  fn test_schema_with_any_types() {
     let schema_json = r##"
         {
-            "anyOf": [
-                {
-                    "type": "object",
-                    "properties": {
-                        "name": { "type": "string" },
-                        "age": { "type": "number" }
-                    },
-                    "required": ["name"]
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string"
                 },
-                {
+                "dynamicValue": {},
+                "arrayOfAny": {
+                    "type": "array",
+                    "items": {}
+                },
+                "objectWithAny": {
                     "type": "object",
                     "properties": {
-                        "id": { "type": "string" },
-                        "role": {
-                            "type": "string",
-                            "enum": ["admin", "user"]
-                        }
-                    },
-                    "required": ["id", "role"]
+                        "anyProp": {}
+                    }
                 }
-            ]
+            },
+            "required": ["id"]
         }
         "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
+    // Test with default options (prefer_unknown_over_any = false)
     let type_interface = schema_to_typescript(
-      ReferenceOr::Item(schema),
+      ReferenceOr::Item(schema.clone()),
       Some(SchemaTypeOptions {
-        name: Some("UserInfo".to_string()),
+        name: Some("SchemaWithAny".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type UserInfo = {
-  name: string;
-  age?: number;
-} | {
+    let expected = r##"export type SchemaWithAny = {
   id: string;
-  role: "admin" | "user";
+  dynamicValue?: any;
+  arrayOfAny?: any[];
+  objectWithAny?: {
+    anyProp?: any;
+  };
 };"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
-
   #[test]
-  fn test_array_with_oneof() {
+  fn test_schema_with_unknown_types() {
     let schema_json = r##"
         {
-            "type": "array",
-            "items": {
-                "oneOf": [
-                    { "type": "string" },
-                    { "type": "number" },
-                    {
-                        "type": "object",
-                        "properties": {
-                            "name": { "type": "string" },
-                            "value": { "type": "number" }
-                        },
-                        "required": ["name", "value"]
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string"
+                },
+                "dynamicValue": {},
+                "arrayOfAny": {
+                    "type": "array",
+                    "items": {}
+                },
+                "objectWithAny": {
+                    "type": "object",
+                    "properties": {
+                        "anyProp": {}
                     }
-                ]
-            }
+                }
+            },
+            "required": ["id"]
         }
         "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
-    let type_interface = schema_to_typescript(
-      ReferenceOr::Item(schema),
+    let type_interface_unknown = schema_to_typescript(
+      ReferenceOr::Item(schema.clone()),
       Some(SchemaTypeOptions {
-        name: Some("MixedArray".to_string()),
+        name: Some("SchemaWithUnknown".to_string()),
+        prefer_unknown_over_any: Some(true),
+        prefer_interface_over_type: Some(false),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type MixedArray = (string | number | {
-  name: string;
-  value: number;
-})[];"##;
+    let expected_unknown = r##"export type SchemaWithUnknown = {
+  id: string;
+  dynamicValue?: unknown;
+  arrayOfAny?: unknown[];
+  objectWithAny?: {
+    anyProp?: unknown;
+  };
+};"##;
 
-    assert_eq!(type_interface.to_string(), expected.to_string());
+    assert_eq!(
+      type_interface_unknown.to_string(),
+      expected_unknown.to_string()
+    );
   }
 
   #[test]
-  fn test_array_with_allof() {
+  fn test_prefer_interface_simple_object() {
     let schema_json = r##"
         {
-            "type": "array",
-            "items": {
-                "allOf": [
-                    { "type": "object",
-                      "properties": {
-                          "id": { "type": "string" },
-                          "name": { "type": "string" }
-                      },
-                      "required": ["id"]
-                    },
-                    {
-                        "type": "object",
-                        "properties": {
-                            "metadata": {
-                                "type": "object",
-                                "properties": {
-                                    "created": { "type": "string" },
-                                    "modified": { "type": "string" }
-                                }
-                            }
-                        }
-                    }
-                ]
+            "type": "object",
+            "properties": {
+                "name": {"type": "string"},
+                "age": {"type": "number"}
             }
         }
         "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
-    let type_interface = schema_to_typescript(
+    let interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("CombinedArray".to_string()),
+        name: Some("Person".to_string()),
+        prefer_unknown_over_any: Some(false),
+        prefer_interface_over_type: Some(true),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type CombinedArray = ({
-  id: string;
+    let expected = r##"export interface Person {
   name?: string;
-} & {
-  metadata?: {
-    created?: string;
-    modified?: string;
-  };
-})[];"##;
+  age?: number;
+};"##;
 
-    assert_eq!(type_interface.to_string(), expected.to_string());
+    assert_eq!(interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_array_with_anyof() {
+  fn test_prefer_interface_union_type() {
     let schema_json = r##"
         {
-            "type": "array",
-            "items": {
-                "anyOf": [
-                    { "type": "string" },
-                    { "type": "number" },
-                    {
-                        "type": "object",
-                        "properties": {
-                            "name": { "type": "string" },
-                            "value": { "type": "number" }
-                        },
-                        "required": ["name", "value"]
-                    }
-                ]
-            }
+            "oneOf": [
+                {"type": "string"},
+                {"type": "number"}
+            ]
         }
         "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
-    let type_interface = schema_to_typescript(
+    let type_def = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("MixedAnyArray".to_string()),
+        name: Some("UnionType".to_string()),
+        prefer_unknown_over_any: Some(false),
+        prefer_interface_over_type: Some(true),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type MixedAnyArray = (string | number | {
-  name: string;
-  value: number;
-})[];"##;
+    // Should still use type for unions even with prefer_interface_over_type
+    let expected = r##"export type UnionType = string | number;"##;
 
-    assert_eq!(type_interface.to_string(), expected.to_string());
+    assert_eq!(type_def.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_anyof_array_primitive_object() {
+  fn test_prefer_interface_nested_object() {
     let schema_json = r##"
         {
             "type": "object",
             "properties": {
-                "key": {
-                    "type": "string"
-                },
-                "value": {
-                    "anyOf": [
-                        {
-                            "type": "array",
-                            "items": {
-                                "type": "number"
-                            }
-                        },
-                        {
-                            "type": "string"
-                        },
-                        {
-                            "type": "array",
-                            "items": {
-                                "type": "object",
-                                "properties": {
-                                    "name": { "type": "string" },
-                                    "count": { "type": "number" }
-                                },
-                                "required": ["name", "count"]
-                            }
-                        },
-                        {
-                            "type": "array",
-                            "items": {
-                                "$ref": "#/components/schemas/SomeType"
+                "id": {"type": "string"},
+                "user": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string"},
+                        "settings": {
+                            "type": "object",
+                            "properties": {
+                                "theme": {"type": "string"},
+                                "notifications": {"type": "boolean"}
                             }
                         }
-                    ]
+                    }
                 }
-            },
-            "required": [
-                "key",
-                "value"
-            ]
+            }
         }
         "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
-    let type_interface = schema_to_typescript(
+    let interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("MixedValue".to_string()),
+        name: Some("UserConfig".to_string()),
+        prefer_unknown_over_any: Some(false),
+        prefer_interface_over_type: Some(true),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type MixedValue = {
-  key: string;
-  value: number[] | string | {
-    name: string;
-    count: number;
-  }[] | SomeType[];
+    let expected = r##"export interface UserConfig {
+  id?: string;
+  user?: {
+    name?: string;
+    settings?: {
+      theme?: string;
+      notifications?: boolean;
+    };
+  };
 };"##;
 
-    assert_eq!(type_interface.to_string(), expected.to_string());
+    assert_eq!(interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_oneof_array_primitive_object() {
+  fn test_schema_without_name() {
     let schema_json = r##"
         {
             "type": "object",
             "properties": {
-                "key": {
-                    "type": "string"
-                },
-                "value": {
-                    "oneOf": [
-                        {
-                            "type": "array",
-                            "items": {
-                                "type": "number"
-                            }
-                        },
-                        {
-                            "type": "string"
-                        },
-                        {
-                            "type": "array",
-                            "items": {
-                                "type": "object",
-                                "properties": {
-                                    "name": { "type": "string" },
-                                    "count": { "type": "number" }
-                                },
-                                "required": ["name", "count"]
-                            }
-                        },
-                        {
-                            "type": "array",
-                            "items": {
-                                "$ref": "#/components/schemas/SomeType"
-                            }
-                        }
-                    ]
-                }
-            },
-            "required": [
-                "key",
-                "value"
-            ]
+                "id": {"type": "string"},
+                "age": {"type": "number"}
+            }
         }
         "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
-    let type_interface = schema_to_typescript(
-      ReferenceOr::Item(schema),
-      Some(SchemaTypeOptions {
-        name: Some("MixedValue".to_string()),
-        ..Default::default()
-      }),
-    );
+    let type_def = schema_to_typescript(ReferenceOr::Item(schema), None);
 
-    let expected = r##"export type MixedValue = {
-  key: string;
-  value: number[] | string | {
-    name: string;
-    count: number;
-  }[] | SomeType[];
-};"##;
+    let expected = r##"{
+  id?: string;
+  age?: number;
+}"##;
 
-    assert_eq!(type_interface.to_string(), expected.to_string());
+    assert_eq!(type_def.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_nested_objects() {
-    let schema_json = r#"
+  fn test_schema_without_name_unknown_over_any() {
+    let schema_json = r##"
         {
             "type": "object",
             "properties": {
-                "id": { "type": "string" },
-                "name": { "type": "string" },
-                "address": {
-                    "type": "object",
-                    "properties": {
-                        "street": { "type": "string" },
-                        "city": { "type": "string" },
-                        "country": { "type": "string" },
-                        "coordinates": {
-                            "type": "object",
-                            "properties": {
-                                "latitude": { "type": "number" },
-                                "longitude": { "type": "number" }
-                            },
-                            "required": ["latitude", "longitude"]
-                        }
-                    },
-                    "required": ["street", "city"]
-                }
-            },
-            "required": ["id", "name", "address"]
+                "id": {"type": "string"},
+                "data": {}
+            }
         }
-        "#;
+        "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
-    let type_interface = schema_to_typescript(
+    let type_def = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("Location".to_string()),
+        name: None,
+        prefer_unknown_over_any: Some(true),
+        prefer_interface_over_type: None,
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type Location = {
-  id: string;
-  name: string;
-  address: {
-    street: string;
-    city: string;
-    country?: string;
-    coordinates?: {
-      latitude: number;
-      longitude: number;
-    };
-  };
-};"##;
+    let expected = r##"{
+  id?: string;
+  data?: unknown;
+}"##;
 
-    assert_eq!(type_interface.to_string(), expected.to_string());
+    assert_eq!(type_def.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_nested_arrays() {
-    let schema_json = r#"
+  fn test_ref_property_description_from_component() {
+    let components_json = r##"
         {
-            "type": "object",
-            "properties": {
-                "id": { "type": "string" },
-                "categories": {
-                    "type": "array",
-                    "items": {
-                        "type": "object",
-                        "properties": {
-                            "name": { "type": "string" },
-                            "subcategories": {
-                                "type": "array",
-                                "items": { "type": "string" }
-                            }
-                        },
-                        "required": ["name"]
+            "schemas": {
+                "Address": {
+                    "type": "object",
+                    "description": "Postal address",
+                    "properties": {
+                        "city": { "type": "string" }
                     }
+                },
+                "LegacyId": {
+                    "type": "string",
+                    "description": "Old identifier",
+                    "deprecated": true
                 }
+            }
+        }
+        "##;
+
+    let schema_json = r##"
+        {
+            "type": "object",
+            "properties": {
+                "address": { "$ref": "#/components/schemas/Address" },
+                "legacyId": { "$ref": "#/components/schemas/LegacyId" },
+                "unknown": { "$ref": "#/components/schemas/Unknown" }
             },
-            "required": ["id"]
+            "required": ["address"]
         }
-        "#;
+        "##;
 
+    let components: Components =
+      serde_json::from_str(components_json).expect("Could not deserialize components");
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
-    let type_interface = schema_to_typescript(
+    let type_interface = schema_to_typescript_with_context(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("Product".to_string()),
+        name: Some("Customer".to_string()),
         ..Default::default()
       }),
+      &SchemaContext {
+        components: Some(&components),
+        ..Default::default()
+      },
     );
 
-    let expected = r##"export type Product = {
-  id: string;
-  categories?: {
-    name: string;
-    subcategories?: string[];
-  }[];
+    let expected = r##"export type Customer = {
+  /**
+   * Postal address
+   */
+  address: Address;
+  /**
+   * @deprecated Old identifier
+   */
+  legacyId?: LegacyId;
+  unknown?: Unknown;
 };"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_complex_nested_arrays() {
-    let schema_json = r#"
+  fn test_ref_property_sibling_description() {
+    let components_json = r##"
         {
-            "type": "object",
-            "properties": {
-                "id": { "type": "string" },
-                "departments": {
-                    "type": "array",
-                    "items": {
-                        "type": "object",
-                        "properties": {
-                            "name": { "type": "string" },
-                            "teams": {
-                                "type": "array",
-                                "items": {
-                                    "type": "object",
-                                    "properties": {
-                                        "teamName": { "type": "string" },
-                                        "members": {
-                                            "type": "array",
-                                            "items": {
-                                                "type": "object",
-                                                "properties": {
-                                                    "id": { "type": "string" },
-                                                    "name": { "type": "string" },
-                                                    "skills": {
-                                                        "type": "array",
-                                                        "items": {
-                                                            "type": "object",
-                                                            "properties": {
-                                                                "name": { "type": "string" },
-                                                                "level": { "type": "number" },
-                                                                "certifications": {
-                                                                    "type": "array",
-                                                                    "items": { "type": "string" }
-                                                                }
-                                                            },
-                                                            "required": ["name", "level"]
-                                                        }
-                                                    }
-                                                },
-                                                "required": ["id", "name"]
-                                            }
-                                        },
-                                        "projects": {
-                                            "type": "array",
-                                            "items": { "type": "string" }
-                                        }
-                                    },
-                                    "required": ["teamName", "members"]
-                                }
-                            }
-                        },
-                        "required": ["name", "teams"]
+            "schemas": {
+                "Address": {
+                    "type": "object",
+                    "description": "Postal address",
+                    "properties": {
+                        "city": { "type": "string" }
+                    }
+                }
+            }
+        }
+        "##;
+
+    let schema_json = r##"
+        {
+            "type": "object",
+            "properties": {
+                "billing": {
+                    "$ref": "#/components/schemas/Address",
+                    "description": "Where invoices are sent"
+                },
+                "shipping": {
+                    "type": "array",
+                    "items": {
+                        "$ref": "#/components/schemas/Address",
+                        "description": "Delivery address"
                     }
                 }
-            },
-            "required": ["id", "departments"]
+            }
         }
-        "#;
+        "##;
 
-    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+    let components: Components =
+      serde_json::from_str(components_json).expect("Could not deserialize components");
+    let mut schema_value: Value =
+      serde_json::from_str(schema_json).expect("Could not deserialize schema");
+    normalize_schema(&mut schema_value, "#", &Diagnostics::default());
+    let schema: Schema =
+      serde_json::from_value(schema_value).expect("Could not deserialize schema");
 
-    let type_interface = schema_to_typescript(
+    let type_interface = schema_to_typescript_with_context(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("Organization".to_string()),
+        name: Some("Order".to_string()),
         ..Default::default()
       }),
+      &SchemaContext {
+        components: Some(&components),
+        ..Default::default()
+      },
     );
 
-    let expected = r##"export type Organization = {
-  id: string;
-  departments: {
-    name: string;
-    teams: {
-      teamName: string;
-      members: {
-        id: string;
-        name: string;
-        skills?: {
-          name: string;
-          level: number;
-          certifications?: string[];
-        }[];
-      }[];
-      projects?: string[];
-    }[];
-  }[];
+    let expected = r##"export type Order = {
+  /**
+   * Where invoices are sent
+   */
+  billing?: Address;
+  shipping?: Address[];
 };"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_nested_object_with_array_oneof() {
+  fn test_any_schema_with_properties_and_oneof() {
     let schema_json = r##"
         {
             "type": "object",
             "properties": {
-                "id": { "type": "string" },
-                "metadata": {
-                    "type": "object",
-                    "properties": {
-                        "title": { "type": "string" },
-                        "tags": {
-                            "type": "array",
-                            "items": {
-                                "oneOf": [
-                                    { "type": "string" },
-                                    {
-                                        "type": "object",
-                                        "properties": {
-                                            "name": { "type": "string" },
-                                            "value": { "type": "number" },
-                                            "metadata": {
-                                                "type": "object",
-                                                "properties": {
-                                                    "description": { "type": "string" },
-                                                    "priority": { "type": "number" }
-                                                },
-                                                "required": ["description"]
-                                            }
-                                        },
-                                        "required": ["name", "value"]
-                                    }
-                                ]
-                            }
-                        }
-                    },
-                    "required": ["title", "tags"]
-                }
+                "id": { "type": "string" }
             },
-            "required": ["id", "metadata"]
+            "required": ["id"],
+            "oneOf": [
+                { "$ref": "#/components/schemas/Cat" },
+                { "$ref": "#/components/schemas/Dog" }
+            ]
         }
         "##;
 
@@ -1997,276 +4183,233 @@ mod tests {
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("DeepArray".to_string()),
+        name: Some("Pet".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type DeepArray = {
+    let expected = r##"export type Pet = {
   id: string;
-  metadata: {
-    title: string;
-    tags: (string | {
-      name: string;
-      value: number;
-      metadata?: {
-        description: string;
-        priority?: number;
-      };
-    })[];
-  };
-};"##;
+} & (Cat | Dog);"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_nested_object_with_array_allof() {
-    let schema_json = r#"
-          {
-              "type": "object",
-              "properties": {
-                  "id": { "type": "string" },
-                  "metadata": {
-                      "type": "object",
-                      "properties": {
-                          "title": { "type": "string" },
-                          "tags": {
-                              "type": "array",
-                              "items": {
-                                  "allOf": [
-                                      {
-                                          "type": "object",
-                                          "properties": {
-                                              "id": { "type": "string" },
-                                              "type": { "type": "string" }
-                                          },
-                                          "required": ["id"]
-                                      },
-                                      {
-                                          "type": "object",
-                                          "properties": {
-                                              "metadata": {
-                                                  "type": "object",
-                                                  "properties": {
-                                                      "description": { "type": "string" },
-                                                      "created": { "type": "string" }
-                                                  }
-                                              }
-                                          }
-                                      }
-                                  ]
-                              }
-                          }
-                      },
-                      "required": ["title", "tags"]
-                  }
-              },
-              "required": ["id", "metadata"]
-          }
-          "#;
+  fn test_any_schema_with_mixed_enum() {
+    let schema_json = r##"
+        {
+            "type": "object",
+            "properties": {
+                "value": { "enum": ["auto", 0, 1, true, null] },
+                "values": {
+                    "type": "array",
+                    "items": { "enum": ["a", 1] }
+                }
+            }
+        }
+        "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("DeepArrayAllOf".to_string()),
+        name: Some("Setting".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type DeepArrayAllOf = {
-  id: string;
-  metadata: {
-    title: string;
-    tags: ({
-      id: string;
-      type?: string;
-    } & {
-      metadata?: {
-        description?: string;
-        created?: string;
-      };
-    })[];
-  };
+    let expected = r##"export type Setting = {
+  value?: "auto" | 0 | 1 | true | null;
+  values?: ("a" | 1)[];
 };"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_nested_object_with_array_anyof() {
-    let schema_json = r#"
-          {
-              "type": "object",
-              "properties": {
-                  "id": { "type": "string" },
-                  "metadata": {
-                      "type": "object",
-                      "properties": {
-                          "title": { "type": "string" },
-                          "tags": {
-                              "type": "array",
-                              "items": {
-                                  "anyOf": [
-                                      { "type": "string" },
-                                      {
-                                          "type": "object",
-                                          "properties": {
-                                              "name": { "type": "string" },
-                                              "value": { "type": "number" },
-                                              "metadata": {
-                                                  "type": "object",
-                                                  "properties": {
-                                                      "description": { "type": "string" },
-                                                      "priority": { "type": "number" }
-                                                  },
-                                                  "required": ["description"]
-                                              }
-                                          },
-                                          "required": ["name", "value"]
-                                      }
-                                  ]
-                              }
-                          }
-                      },
-                      "required": ["title", "tags"]
-                  }
-              },
-              "required": ["id", "metadata"]
-          }
-          "#;
+  fn test_any_schema_with_composed_items() {
+    let schema_json = r##"
+        {
+            "minItems": 1,
+            "items": {
+                "oneOf": [{ "type": "string" }, { "$ref": "#/components/schemas/Tag" }]
+            }
+        }
+        "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("DeepArrayAny".to_string()),
+        name: Some("Tags".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type DeepArrayAny = {
-  id: string;
-  metadata: {
-    title: string;
-    tags: (string | {
-      name: string;
-      value: number;
-      metadata?: {
-        description: string;
-        priority?: number;
-      };
-    })[];
-  };
+    assert_eq!(
+      type_interface.to_string(),
+      "export type Tags = (string | Tag)[];"
+    );
+  }
+
+  #[test]
+  fn test_not_keyword() {
+    let schema_json = r##"
+        {
+            "type": "object",
+            "properties": {
+                "role": {
+                    "type": "string",
+                    "not": { "enum": ["root", "admin"] }
+                },
+                "tags": {
+                    "type": "array",
+                    "items": {
+                        "type": "string",
+                        "not": { "enum": ["internal"] }
+                    }
+                },
+                "anything": {
+                    "not": { "type": "null" }
+                },
+                "shape": {
+                    "not": { "type": "object", "properties": {} }
+                }
+            }
+        }
+        "##;
+
+    let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("Account".to_string()),
+        prefer_unknown_over_any: Some(true),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r##"export type Account = {
+  role?: Exclude<string, "root" | "admin">;
+  tags?: Exclude<string, "internal">[];
+  anything?: Exclude<unknown, null>;
+  shape?: unknown;
 };"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
-  #[test]
-  fn test_object_with_deep_array_refs() {
+  #[test]
+  fn test_merge_all_of_objects() {
+    let components_json = r##"
+        {
+            "schemas": {
+                "Entity": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "string", "description": "Entity identifier" },
+                        "version": { "type": "integer", "description": "Revision number" }
+                    },
+                    "required": ["id"]
+                },
+                "Timestamped": {
+                    "allOf": [
+                        {
+                            "type": "object",
+                            "properties": {
+                                "createdAt": { "type": "string" }
+                            },
+                            "required": ["createdAt"]
+                        }
+                    ]
+                },
+                "Owner": {
+                    "oneOf": [
+                        { "type": "string" },
+                        { "type": "number" }
+                    ]
+                }
+            }
+        }
+        "##;
+
     let schema_json = r##"
         {
-            "type": "object",
-            "properties": {
-                "id": { "type": "string" },
-                "data": {
+            "allOf": [
+                { "$ref": "#/components/schemas/Entity" },
+                { "$ref": "#/components/schemas/Timestamped" },
+                { "$ref": "#/components/schemas/Owner" },
+                {
                     "type": "object",
                     "properties": {
-                        "name": { "type": "string" },
-                        "references": {
-                            "type": "array",
-                            "items": { "$ref": "#/components/schemas/ExternalRef" }
-                        }
+                        "title": { "type": "string" },
+                        "version": { "type": "string", "description": "Semantic version" }
                     },
-                    "required": ["name", "references"]
+                    "required": ["title", "version"]
                 }
-            },
-            "required": ["id", "data"]
+            ]
         }
         "##;
 
+    let components: Components =
+      serde_json::from_str(components_json).expect("Could not deserialize components");
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
-    let type_interface = schema_to_typescript(
+    let type_interface = schema_to_typescript_with_context(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("DeepRefArray".to_string()),
+        name: Some("Document".to_string()),
+        merge_all_of: Some(true),
         ..Default::default()
       }),
+      &SchemaContext {
+        components: Some(&components),
+        ..Default::default()
+      },
     );
 
-    let expected = r##"export type DeepRefArray = {
+    let expected = r##"export type Document = {
+  /**
+   * Entity identifier
+   */
   id: string;
-  data: {
-    name: string;
-    references: ExternalRef[];
-  };
-};"##;
+  /**
+   * Revision number
+   *
+   * Semantic version
+   */
+  version: number & string;
+  createdAt: string;
+  title: string;
+} & Owner;"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_object_with_property_descriptions() {
+  fn test_merge_all_of_keeps_single_reference() {
     let schema_json = r##"
         {
             "type": "object",
-            "description": "Root object description",
             "properties": {
-                "id": {
-                    "type": "string",
-                    "description": "Unique identifier"
-                },
-                "numbers": {
-                    "type": "array",
-                    "description": "List of important numbers",
-                    "items": {
-                        "type": "number",
-                        "description": "A numeric value"
-                    }
-                },
-                "flags": {
-                    "type": "object",
-                    "description": "Configuration flags",
-                    "properties": {
-                        "isEnabled": {
-                            "type": "boolean",
-                            "description": "Whether feature is enabled"
-                        },
-                        "priority": {
-                            "type": "integer",
-                            "description": "Priority level 1-5",
-                            "minimum": 1,
-                            "maximum": 5
-                        }
-                    },
-                    "required": ["isEnabled"]
+                "parent": {
+                    "description": "Parent node",
+                    "allOf": [{ "$ref": "#/components/schemas/Node" }]
                 },
-                "metadata": {
+                "children": {
                     "type": "array",
-                    "description": "List of metadata objects",
                     "items": {
-                        "type": "object",
-                        "description": "Metadata entry",
-                        "properties": {
-                            "key": {
-                                "type": "string",
-                                "description": "Metadata key"
-                            },
-                            "value": {
-                                "type": "string",
-                                "description": "Metadata value"
-                            }
-                        },
-                        "required": ["key", "value"]
+                        "allOf": [
+                            { "type": "object", "properties": { "a": { "type": "string" } } },
+                            { "type": "object", "properties": { "b": { "type": "string" } } }
+                        ]
                     }
                 }
-            },
-            "required": ["id", "flags", "metadata"]
+            }
         }
         "##;
 
@@ -2275,44 +4418,20 @@ mod tests {
     let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("ComplexObject".to_string()),
+        name: Some("Tree".to_string()),
+        merge_all_of: Some(true),
         ..Default::default()
       }),
     );
-    let expected = r##"export type ComplexObject = {
-  /**
-   * Unique identifier
-   */
-  id: string;
-  /**
-   * List of important numbers
-   */
-  numbers?: number[];
-  /**
-   * Configuration flags
-   */
-  flags: {
-    /**
-     * Whether feature is enabled
-     */
-    isEnabled: boolean;
-    /**
-     * Priority level 1-5
-     */
-    priority?: number;
-  };
+
+    let expected = r##"export type Tree = {
   /**
-   * List of metadata objects
+   * Parent node
    */
-  metadata: {
-    /**
-     * Metadata key
-     */
-    key: string;
-    /**
-     * Metadata value
-     */
-    value: string;
+  parent?: Node;
+  children?: {
+    a?: string;
+    b?: string;
   }[];
 };"##;
 
@@ -2320,316 +4439,329 @@ mod tests {
   }
 
   #[test]
-  fn test_object_with_deprecated_properties() {
+  fn test_recursive_root_reference() {
     let schema_json = r##"
         {
             "type": "object",
             "properties": {
-                "id": {
-                    "type": "string",
-                    "description": "Unique identifier"
-                },
-                "oldName": {
-                    "type": "string",
-                    "description": "Use name instead",
-                    "deprecated": true
-                },
-                "name": {
-                    "type": "string",
-                    "description": "The current name field"
-                },
-                "oldTags": {
-                    "type": "array",
-                    "description": "Use categories instead",
-                    "deprecated": true,
-                    "items": {
-                        "type": "string"
-                    }
-                },
-                "categories": {
+                "value": { "type": "string" },
+                "parent": { "$ref": "#" },
+                "children": {
                     "type": "array",
-                    "description": "Current categories field",
-                    "items": {
-                        "type": "string"
-                    }
-                },
-                "config": {
-                    "type": "object",
-                    "properties": {
-                        "oldSetting": {
-                            "type": "boolean",
-                            "description": "Deprecated setting - use newSetting",
-                            "deprecated": true
-                        },
-                        "newSetting": {
-                            "type": "boolean",
-                            "description": "Current setting to use"
-                        },
-                        "oldOptions": {
-                            "type": "array",
-                            "description": "Deprecated options array - use newOptions",
-                            "deprecated": true,
-                            "items": {
-                                "type": "object",
-                                "properties": {
-                                    "key": { "type": "string" },
-                                    "value": { "type": "string" }
-                                }
-                            }
-                        },
-                        "newOptions": {
-                            "type": "array",
-                            "description": "Current options to use",
-                            "items": {
-                                "type": "object",
-                                "properties": {
-                                    "key": { "type": "string" },
-                                    "value": { "type": "string" }
-                                }
-                            }
-                        }
-                    }
+                    "items": { "$ref": "#" }
                 }
             },
-            "required": ["id", "name"]
+            "required": ["value"]
         }
         "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
     let type_interface = schema_to_typescript(
-      ReferenceOr::Item(schema),
+      ReferenceOr::Item(schema.clone()),
       Some(SchemaTypeOptions {
-        name: Some("ObjectWithDeprecated".to_string()),
+        name: Some("TreeNode".to_string()),
         ..Default::default()
       }),
     );
 
-    let expected = r##"export type ObjectWithDeprecated = {
-  /**
-   * Unique identifier
-   */
-  id: string;
-  /**
-   * @deprecated Use name instead
-   */
-  oldName?: string;
-  /**
-   * The current name field
-   */
-  name: string;
-  /**
-   * @deprecated Use categories instead
-   */
-  oldTags?: string[];
-  /**
-   * Current categories field
-   */
-  categories?: string[];
-  config?: {
-    /**
-     * @deprecated Deprecated setting - use newSetting
-     */
-    oldSetting?: boolean;
-    /**
-     * Current setting to use
-     */
-    newSetting?: boolean;
-    /**
-     * @deprecated Deprecated options array - use newOptions
-     */
-    oldOptions?: {
-      key?: string;
-      value?: string;
-    }[];
-    /**
-     * Current options to use
-     */
-    newOptions?: {
-      key?: string;
-      value?: string;
-    }[];
-  };
+    let expected = r##"export type TreeNode = {
+  value: string;
+  parent?: TreeNode;
+  children?: TreeNode[];
 };"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
+
+    let unnamed_type = schema_to_typescript(ReferenceOr::Item(schema), None);
+
+    let expected_unnamed = r##"{
+  value: string;
+  parent?: any;
+  children?: any[];
+}"##;
+
+    assert_eq!(unnamed_type.to_string(), expected_unnamed.to_string());
   }
 
-  #[test]
-  fn test_schema_with_any_types() {
+  #[test]
+  fn test_cyclic_component_aliases() {
+    let components_json = r##"
+        {
+            "schemas": {
+                "A": { "$ref": "#/components/schemas/B" },
+                "B": { "$ref": "#/components/schemas/A" },
+                "Comment": {
+                    "allOf": [
+                        { "$ref": "#/components/schemas/Comment" },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "replies": {
+                                    "type": "array",
+                                    "items": { "$ref": "#/components/schemas/Comment" }
+                                }
+                            }
+                        }
+                    ]
+                }
+            }
+        }
+        "##;
+
     let schema_json = r##"
         {
             "type": "object",
             "properties": {
-                "id": {
-                    "type": "string"
-                },
-                "dynamicValue": {},
-                "arrayOfAny": {
-                    "type": "array",
-                    "items": {}
-                },
-                "objectWithAny": {
-                    "type": "object",
-                    "properties": {
-                        "anyProp": {}
-                    }
+                "a": { "$ref": "#/components/schemas/A" },
+                "comment": {
+                    "allOf": [
+                        { "$ref": "#/components/schemas/Comment" },
+                        { "$ref": "#/components/schemas/A" }
+                    ]
                 }
-            },
-            "required": ["id"]
+            }
         }
         "##;
 
+    let components: Components =
+      serde_json::from_str(components_json).expect("Could not deserialize components");
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
-    // Test with default options (prefer_unknown_over_any = false)
-    let type_interface = schema_to_typescript(
-      ReferenceOr::Item(schema.clone()),
+    let type_interface = schema_to_typescript_with_context(
+      ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("SchemaWithAny".to_string()),
+        name: Some("Thread".to_string()),
+        merge_all_of: Some(true),
         ..Default::default()
       }),
+      &SchemaContext {
+        components: Some(&components),
+        ..Default::default()
+      },
     );
 
-    let expected = r##"export type SchemaWithAny = {
-  id: string;
-  dynamicValue?: any;
-  arrayOfAny?: any[];
-  objectWithAny?: {
-    anyProp?: any;
-  };
+    let expected = r##"export type Thread = {
+  a?: A;
+  comment?: {
+    replies?: Comment[];
+  } & Comment & A;
 };"##;
 
     assert_eq!(type_interface.to_string(), expected.to_string());
   }
+
   #[test]
-  fn test_schema_with_unknown_types() {
+  fn test_format_options() {
     let schema_json = r##"
         {
             "type": "object",
             "properties": {
-                "id": {
-                    "type": "string"
-                },
-                "dynamicValue": {},
-                "arrayOfAny": {
-                    "type": "array",
-                    "items": {}
+                "status": {
+                    "type": "string",
+                    "description": "Current status",
+                    "enum": ["pending", "in_progress", "done", "it's cancelled"]
                 },
-                "objectWithAny": {
+                "owner": {
                     "type": "object",
                     "properties": {
-                        "anyProp": {}
+                        "id": { "type": "string" },
+                        "name": { "type": "string" }
                     }
                 }
             },
-            "required": ["id"]
+            "required": ["status"]
         }
         "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
-    let type_interface_unknown = schema_to_typescript(
+    let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema.clone()),
       Some(SchemaTypeOptions {
-        name: Some("SchemaWithUnknown".to_string()),
-        prefer_unknown_over_any: Some(true),
-        prefer_interface_over_type: Some(false),
+        name: Some("Task".to_string()),
+        format: Some(FormatOptions {
+          use_tabs: Some(true),
+          semi: Some(false),
+          single_quote: Some(true),
+          member_separator: Some(MemberSeparator::Comma),
+          trailing_comma: Some(false),
+          print_width: Some(40),
+          ..Default::default()
+        }),
+        ..Default::default()
       }),
     );
 
-    let expected_unknown = r##"export type SchemaWithUnknown = {
-  id: string;
-  dynamicValue?: unknown;
-  arrayOfAny?: unknown[];
-  objectWithAny?: {
-    anyProp?: unknown;
-  };
-};"##;
+    let expected = "export type Task = {
+\t/**
+\t * Current status
+\t */
+\tstatus:
+\t\t| 'pending'
+\t\t| 'in_progress'
+\t\t| 'done'
+\t\t| 'it\\'s cancelled',
+\towner?: {
+\t\tid?: string,
+\t\tname?: string
+\t}
+}";
 
-    assert_eq!(
-      type_interface_unknown.to_string(),
-      expected_unknown.to_string()
+    assert_eq!(type_interface.to_string(), expected.to_string());
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("Task".to_string()),
+        format: Some(FormatOptions {
+          tab_width: Some(4),
+          member_separator: Some(MemberSeparator::None),
+          ..Default::default()
+        }),
+        ..Default::default()
+      }),
     );
+
+    let expected = r##"export type Task = {
+    /**
+     * Current status
+     */
+    status: "pending" | "in_progress" | "done" | "it's cancelled"
+    owner?: {
+        id?: string
+        name?: string
+    }
+};"##;
+
+    assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_prefer_interface_simple_object() {
+  fn test_format_print_width_type_alias() {
     let schema_json = r##"
         {
-            "type": "object",
-            "properties": {
-                "name": {"type": "string"},
-                "age": {"type": "number"}
-            }
+            "oneOf": [
+                { "$ref": "#/components/schemas/CreatedEvent" },
+                { "$ref": "#/components/schemas/DeletedEvent" },
+                { "type": "string", "enum": ["ping"] }
+            ]
         }
         "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
-    let interface = schema_to_typescript(
+    let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("Person".to_string()),
-        prefer_unknown_over_any: Some(false),
-        prefer_interface_over_type: Some(true),
+        name: Some("Event".to_string()),
+        format: Some(FormatOptions {
+          print_width: Some(30),
+          ..Default::default()
+        }),
+        ..Default::default()
       }),
     );
 
-    let expected = r##"export interface Person {
-  name?: string;
-  age?: number;
-};"##;
+    let expected = r##"export type Event =
+  | CreatedEvent
+  | DeletedEvent
+  | "ping";"##;
 
-    assert_eq!(interface.to_string(), expected.to_string());
+    assert_eq!(type_interface.to_string(), expected.to_string());
+
+    // The width counts characters, not bytes
+    let schema: Schema = serde_json::from_str(r##"{ "type": "string", "enum": ["éé", "èè"] }"##)
+      .expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("A".to_string()),
+        format: Some(FormatOptions {
+          print_width: Some(30),
+          ..Default::default()
+        }),
+        ..Default::default()
+      }),
+    );
+
+    assert_eq!(
+      type_interface.to_string(),
+      r#"export type A = "éé" | "èè";"#
+    );
   }
 
   #[test]
-  fn test_prefer_interface_union_type() {
+  fn test_quoted_property_names() {
     let schema_json = r##"
         {
-            "oneOf": [
-                {"type": "string"},
-                {"type": "number"}
-            ]
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "_links": { "type": "string" },
+                "$type": { "type": "string" },
+                "content-type": { "type": "string" },
+                "2fa": { "type": "boolean" },
+                "user name": { "type": "string" },
+                "it's": { "type": "number" }
+            },
+            "required": ["content-type"]
         }
         "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
-    let type_def = schema_to_typescript(
-      ReferenceOr::Item(schema),
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema.clone()),
       Some(SchemaTypeOptions {
-        name: Some("UnionType".to_string()),
-        prefer_unknown_over_any: Some(false),
-        prefer_interface_over_type: Some(true),
+        name: Some("Headers".to_string()),
+        ..Default::default()
       }),
     );
 
-    // Should still use type for unions even with prefer_interface_over_type
-    let expected = r##"export type UnionType = string | number;"##;
+    let expected = r##"export type Headers = {
+  id?: string;
+  _links?: string;
+  $type?: string;
+  "content-type": string;
+  "2fa"?: boolean;
+  "user name"?: string;
+  "it's"?: number;
+};"##;
 
-    assert_eq!(type_def.to_string(), expected.to_string());
+    assert_eq!(type_interface.to_string(), expected.to_string());
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("Headers".to_string()),
+        format: Some(FormatOptions {
+          single_quote: Some(true),
+          ..Default::default()
+        }),
+        ..Default::default()
+      }),
+    );
+
+    assert!(type_interface
+      .to_string()
+      .contains("  'content-type': string;\n  '2fa'?: boolean;\n  'user name'?: string;\n  'it\\'s'?: number;"));
   }
 
   #[test]
-  fn test_prefer_interface_nested_object() {
+  fn test_alphabetical_sort() {
     let schema_json = r##"
         {
             "type": "object",
             "properties": {
-                "id": {"type": "string"},
-                "user": {
+                "zip": { "type": "string" },
+                "city": { "type": "string" },
+                "geo": {
                     "type": "object",
                     "properties": {
-                        "name": {"type": "string"},
-                        "settings": {
-                            "type": "object",
-                            "properties": {
-                                "theme": {"type": "string"},
-                                "notifications": {"type": "boolean"}
-                            }
-                        }
+                        "lng": { "type": "number" },
+                        "lat": { "type": "number" }
                     }
                 }
             }
@@ -2638,81 +4770,217 @@ mod tests {
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
-    let interface = schema_to_typescript(
+    let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: Some("UserConfig".to_string()),
-        prefer_unknown_over_any: Some(false),
-        prefer_interface_over_type: Some(true),
+        name: Some("Address".to_string()),
+        sort: Some(SortOrder::Alphabetical),
+        ..Default::default()
       }),
     );
 
-    let expected = r##"export interface UserConfig {
-  id?: string;
-  user?: {
-    name?: string;
-    settings?: {
-      theme?: string;
-      notifications?: boolean;
-    };
+    let expected = r##"export type Address = {
+  city?: string;
+  geo?: {
+    lat?: number;
+    lng?: number;
   };
+  zip?: string;
 };"##;
 
-    assert_eq!(interface.to_string(), expected.to_string());
+    assert_eq!(type_interface.to_string(), expected.to_string());
   }
 
   #[test]
-  fn test_schema_without_name() {
+  fn test_diagnostics() {
     let schema_json = r##"
         {
             "type": "object",
             "properties": {
-                "id": {"type": "string"},
-                "age": {"type": "number"}
+                "file": { "type": "file" },
+                "owner": { "$ref": "other.json#/User" },
+                "name": { "type": "string", "not": { "type": "object" } },
+                "parent": { "$ref": "#" },
+                "merged": {
+                    "allOf": [
+                        { "type": "object", "properties": { "id": { "type": "string" } } },
+                        { "type": "object", "properties": { "id": { "type": "number" } } }
+                    ]
+                }
             }
         }
         "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
-    let type_def = schema_to_typescript(ReferenceOr::Item(schema), None);
+    let output = schema_to_typescript_with_diagnostics(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        merge_all_of: Some(true),
+        ..Default::default()
+      }),
+    );
 
-    let expected = r##"{
-  id?: string;
-  age?: number;
-}"##;
+    let diagnostics: Vec<(&str, Severity, &str)> = output
+      .diagnostics
+      .iter()
+      .map(|d| (d.pointer.as_str(), d.severity, d.code.as_str()))
+      .collect();
 
-    assert_eq!(type_def.to_string(), expected.to_string());
+    assert_eq!(
+      diagnostics,
+      vec![
+        ("#/properties/file", Severity::Warning, "unknown-type"),
+        (
+          "#/properties/owner",
+          Severity::Warning,
+          "external-reference"
+        ),
+        ("#/properties/name", Severity::Warning, "ignored-keyword"),
+        (
+          "#/properties/parent",
+          Severity::Warning,
+          "unresolved-reference"
+        ),
+        (
+          "#/properties/merged/properties/id",
+          Severity::Warning,
+          "all-of-conflict"
+        ),
+      ]
+    );
+    assert!(output.ts_type.contains("owner?: User;"));
   }
 
   #[test]
-  fn test_schema_without_name_unknown_over_any() {
+  fn test_lossy_conversion_diagnostics() {
+    let mut schema_value: Value = serde_json::from_str(
+      r##"
+        {
+            "type": "object",
+            "properties": {
+                "tags": { "type": "array" },
+                "createdAt": { "format": "date-time" },
+                "labels": {
+                    "type": "object",
+                    "patternProperties": { "^x-": { "type": "string" } }
+                },
+                "metadata": {
+                    "type": "object",
+                    "additionalProperties": { "type": "string" }
+                },
+                "owner": { "$ref": "#/components/schemas/User", "if": { "required": ["id"] } },
+                "kind": { "const": "user" },
+                "extra": {}
+            }
+        }
+        "##,
+    )
+    .unwrap();
+    let normalize_diagnostics = Diagnostics::default();
+    normalize_schema(&mut schema_value, "#", &normalize_diagnostics);
+
+    let schema: Schema = serde_json::from_value(schema_value).unwrap();
+    let output = schema_to_typescript_with_diagnostics(ReferenceOr::Item(schema), None);
+    let all_diagnostics = [normalize_diagnostics.into_vec(), output.diagnostics].concat();
+
+    let diagnostics: Vec<(&str, &str)> = all_diagnostics
+      .iter()
+      .map(|d| (d.pointer.as_str(), d.code.as_str()))
+      .collect();
+
+    assert_eq!(
+      diagnostics,
+      vec![
+        ("#/properties/labels", "ignored-keyword"),
+        ("#/properties/owner", "ignored-keyword"),
+        ("#/properties/tags", "implicit-any"),
+        ("#/properties/createdAt", "implicit-any"),
+        ("#/properties/metadata", "ignored-keyword"),
+        ("#/properties/extra", "implicit-any"),
+      ]
+    );
+    assert!(output.ts_type.contains("kind?: \"user\";"));
+
+    let error = crate::diagnostics::ensure_lossless(&all_diagnostics).unwrap_err();
+    assert_eq!(error.violations.len(), 6);
+    assert!(error.to_string().starts_with(
+      "Strict mode: 6 lossy conversions\n  #/properties/labels: `patternProperties` isn't supported, it is ignored (ignored-keyword)"
+    ));
+  }
+
+  #[test]
+  fn test_type_guard() {
     let schema_json = r##"
         {
             "type": "object",
+            "required": ["id", "role"],
             "properties": {
-                "id": {"type": "string"},
-                "data": {}
+                "id": { "type": "string" },
+                "role": { "type": "string", "enum": ["admin", "user"] },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "manager": { "$ref": "#" },
+                "age": { "type": "integer", "nullable": true }
             }
         }
         "##;
 
     let schema: Schema = serde_json::from_str(schema_json).expect("Could not deserialize schema");
 
-    let type_def = schema_to_typescript(
+    let type_interface = schema_to_typescript(
       ReferenceOr::Item(schema),
       Some(SchemaTypeOptions {
-        name: None,
-        prefer_unknown_over_any: Some(true),
-        prefer_interface_over_type: None,
+        name: Some("User".to_string()),
+        type_guard: Some(true),
+        ..Default::default()
       }),
     );
 
-    let expected = r##"{
-  id?: string;
-  data?: unknown;
+    let expected = r##"export type User = {
+  id: string;
+  role: "admin" | "user";
+  tags?: string[];
+  manager?: User;
+  age?: number | null;
+};
+
+export function isUser(value: unknown): value is User {
+  const object = value as Record<string, unknown>;
+  return (
+    typeof value === "object" &&
+    value !== null &&
+    !Array.isArray(value) &&
+    typeof object["id"] === "string" &&
+    (object["role"] === "admin" || object["role"] === "user") &&
+    (object["tags"] === undefined || (Array.isArray(object["tags"]) && (object["tags"] as unknown[]).every((item) => typeof item === "string"))) &&
+    (object["manager"] === undefined || isUser(object["manager"])) &&
+    (object["age"] === undefined || typeof object["age"] === "number" || object["age"] === null)
+  );
 }"##;
+    assert_eq!(type_interface.to_string(), expected.to_string());
+  }
 
-    assert_eq!(type_def.to_string(), expected.to_string());
+  #[test]
+  fn test_type_guard_excluded_union() {
+    let schema: Schema = serde_json::from_str(
+      r#"{ "anyOf": [{ "type": "string" }, { "type": "number" }], "not": { "enum": ["x"] } }"#,
+    )
+    .expect("Could not deserialize schema");
+
+    let type_interface = schema_to_typescript(
+      ReferenceOr::Item(schema),
+      Some(SchemaTypeOptions {
+        name: Some("Value".to_string()),
+        type_guard: Some(true),
+        ..Default::default()
+      }),
+    );
+
+    let expected = r#"export type Value = Exclude<string | number, "x">;
+
+export function isValue(value: unknown): value is Value {
+  return (typeof value === "string" || typeof value === "number") && !(value === "x");
+}"#;
+    assert_eq!(type_interface.to_string(), expected.to_string());
   }
 }
@@ -0,0 +1,815 @@
+use crate::diagnostics::pointer_join;
+use crate::json_schema_to_typescript::{resolve_schema_reference, SchemaContext, SchemaLike};
+
+use napi_derive::napi;
+use openapiv3::{
+  AdditionalProperties, Components, ReferenceOr, Schema, SchemaKind, StringFormat, Type,
+  VariantOrUnknownOrEmpty,
+};
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, PartialEq)]
+#[napi(object)]
+pub struct ValidationError {
+  /// JSON pointer of the invalid value, like `/items/0/id`, empty for the value itself
+  pub instance_path: String,
+  /// JSON pointer of the failing keyword, like `#/properties/items/items/properties/id/type`
+  pub schema_path: String,
+  /// Failing keyword, like `required`
+  pub keyword: String,
+  pub message: String,
+}
+
+type Properties<'s> = Vec<(&'s String, &'s ReferenceOr<Box<Schema>>)>;
+
+/// Keywords of a schema, whatever its `SchemaKind`.
+#[derive(Default)]
+pub(crate) struct Keywords<'s> {
+  pub typ: Option<&'s str>,
+  pub enumeration: Vec<Value>,
+  pub format: Option<&'s str>,
+  pub pattern: Option<&'s str>,
+  pub min_length: Option<usize>,
+  pub max_length: Option<usize>,
+  pub minimum: Option<f64>,
+  pub maximum: Option<f64>,
+  pub exclusive_minimum: bool,
+  pub exclusive_maximum: bool,
+  pub multiple_of: Option<f64>,
+  pub properties: Properties<'s>,
+  pub required: &'s [String],
+  pub additional_properties: Option<&'s AdditionalProperties>,
+  pub min_properties: Option<usize>,
+  pub max_properties: Option<usize>,
+  pub items: Option<&'s ReferenceOr<Box<Schema>>>,
+  pub min_items: Option<usize>,
+  pub max_items: Option<usize>,
+  pub unique_items: bool,
+  pub one_of: &'s [ReferenceOr<Schema>],
+  pub any_of: &'s [ReferenceOr<Schema>],
+  pub all_of: &'s [ReferenceOr<Schema>],
+  pub not: Option<&'s ReferenceOr<Schema>>,
+}
+
+fn enum_values<T: serde::Serialize>(enumeration: &[Option<T>]) -> Vec<Value> {
+  enumeration
+    .iter()
+    .map(|value| serde_json::to_value(value).unwrap_or_default())
+    .collect()
+}
+
+pub(crate) fn keywords(schema: &Schema) -> Keywords<'_> {
+  match &schema.schema_kind {
+    SchemaKind::Type(Type::String(string)) => Keywords {
+      typ: Some("string"),
+      enumeration: enum_values(&string.enumeration),
+      format: match &string.format {
+        VariantOrUnknownOrEmpty::Item(StringFormat::Date) => Some("date"),
+        VariantOrUnknownOrEmpty::Item(StringFormat::DateTime) => Some("date-time"),
+        VariantOrUnknownOrEmpty::Item(StringFormat::Byte) => Some("byte"),
+        VariantOrUnknownOrEmpty::Item(_) | VariantOrUnknownOrEmpty::Empty => None,
+        VariantOrUnknownOrEmpty::Unknown(format) => Some(format),
+      },
+      pattern: string.pattern.as_deref(),
+      min_length: string.min_length,
+      max_length: string.max_length,
+      ..Default::default()
+    },
+    SchemaKind::Type(Type::Number(number)) => Keywords {
+      typ: Some("number"),
+      enumeration: enum_values(&number.enumeration),
+      minimum: number.minimum,
+      maximum: number.maximum,
+      exclusive_minimum: number.exclusive_minimum,
+      exclusive_maximum: number.exclusive_maximum,
+      multiple_of: number.multiple_of,
+      ..Default::default()
+    },
+    SchemaKind::Type(Type::Integer(integer)) => Keywords {
+      typ: Some("integer"),
+      enumeration: enum_values(&integer.enumeration),
+      minimum: integer.minimum.map(|minimum| minimum as f64),
+      maximum: integer.maximum.map(|maximum| maximum as f64),
+      exclusive_minimum: integer.exclusive_minimum,
+      exclusive_maximum: integer.exclusive_maximum,
+      multiple_of: integer.multiple_of.map(|multiple_of| multiple_of as f64),
+      ..Default::default()
+    },
+    SchemaKind::Type(Type::Boolean(boolean)) => Keywords {
+      typ: Some("boolean"),
+      enumeration: enum_values(&boolean.enumeration),
+      ..Default::default()
+    },
+    SchemaKind::Type(Type::Object(object)) => Keywords {
+      typ: Some("object"),
+      properties: object.properties.iter().collect(),
+      required: &object.required,
+      additional_properties: object.additional_properties.as_ref(),
+      min_properties: object.min_properties,
+      max_properties: object.max_properties,
+      ..Default::default()
+    },
+    SchemaKind::Type(Type::Array(array)) => Keywords {
+      typ: Some("array"),
+      items: array.items.as_ref(),
+      min_items: array.min_items,
+      max_items: array.max_items,
+      unique_items: array.unique_items,
+      ..Default::default()
+    },
+    SchemaKind::OneOf { one_of } => Keywords {
+      one_of,
+      ..Default::default()
+    },
+    SchemaKind::AnyOf { any_of } => Keywords {
+      any_of,
+      ..Default::default()
+    },
+    SchemaKind::AllOf { all_of } => Keywords {
+      all_of,
+      ..Default::default()
+    },
+    SchemaKind::Not { not } => Keywords {
+      not: Some(not),
+      ..Default::default()
+    },
+    SchemaKind::Any(any) => Keywords {
+      typ: any.typ.as_deref(),
+      enumeration: any.enumeration.clone(),
+      format: any.format.as_deref(),
+      pattern: any.pattern.as_deref(),
+      min_length: any.min_length,
+      max_length: any.max_length,
+      minimum: any.minimum,
+      maximum: any.maximum,
+      exclusive_minimum: any.exclusive_minimum.unwrap_or(false),
+      exclusive_maximum: any.exclusive_maximum.unwrap_or(false),
+      multiple_of: any.multiple_of,
+      properties: any.properties.iter().collect(),
+      required: &any.required,
+      additional_properties: any.additional_properties.as_ref(),
+      min_properties: any.min_properties,
+      max_properties: any.max_properties,
+      items: any.items.as_ref(),
+      min_items: any.min_items,
+      max_items: any.max_items,
+      unique_items: any.unique_items.unwrap_or(false),
+      one_of: &any.one_of,
+      any_of: &any.any_of,
+      all_of: &any.all_of,
+      not: any.not.as_deref(),
+    },
+  }
+}
+
+fn matches_type(value: &Value, typ: &str) -> bool {
+  match typ {
+    "string" => value.is_string(),
+    "number" => value.is_number(),
+    "integer" => value.as_f64().is_some_and(|number| number.fract() == 0.0),
+    "boolean" => value.is_boolean(),
+    "array" => value.is_array(),
+    "object" => value.is_object(),
+    "null" => value.is_null(),
+    _ => true,
+  }
+}
+
+/// Regexes of the formats checked by pattern, compiled on first use.
+fn format_regex(format: &str) -> Option<&'static Regex> {
+  static FORMAT_REGEXES: OnceLock<Vec<(&str, Regex)>> = OnceLock::new();
+
+  FORMAT_REGEXES
+    .get_or_init(|| {
+      [
+        ("date", r"^\d{4}-\d{2}-\d{2}$"),
+        (
+          "date-time",
+          r"^\d{4}-\d{2}-\d{2}[Tt ]\d{2}:\d{2}:\d{2}(\.\d+)?([Zz]|[+-]\d{2}:\d{2})$",
+        ),
+        (
+          "time",
+          r"^\d{2}:\d{2}:\d{2}(\.\d+)?([Zz]|[+-]\d{2}:\d{2})?$",
+        ),
+        ("email", r"^[^\s@]+@[^\s@]+\.[^\s@]+$"),
+        (
+          "uuid",
+          r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$",
+        ),
+        ("uri", r"^[a-zA-Z][a-zA-Z0-9+.-]*:\S*$"),
+        (
+          "byte",
+          r"^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==|[A-Za-z0-9+/]{3}=)?$",
+        ),
+      ]
+      .into_iter()
+      .filter_map(|(format, pattern)| Some((format, Regex::new(pattern).ok()?)))
+      .collect()
+    })
+    .iter()
+    .find(|(name, _)| *name == format)
+    .map(|(_, regex)| regex)
+}
+
+/// Unknown formats, like `password`, are accepted.
+fn matches_format(value: &str, format: &str) -> bool {
+  match format {
+    "ipv4" => value.parse::<Ipv4Addr>().is_ok(),
+    "ipv6" => value.parse::<Ipv6Addr>().is_ok(),
+    _ => format_regex(format).is_none_or(|regex| regex.is_match(value)),
+  }
+}
+
+fn format_number(number: f64) -> String {
+  serde_json::Number::from_f64(number)
+    .map(|number| number.to_string())
+    .unwrap_or_else(|| number.to_string())
+}
+
+/// Validates values against schemas, following references into components, or to the root schema
+/// for `#`.
+struct Validator<'a> {
+  context: SchemaContext<'a>,
+  root: &'a Schema,
+  /// References being validated and their instance path, a reference reached again for the same
+  /// value, like `{ "not": { "$ref": "#" } }`, would never end and is considered valid.
+  active: Vec<(String, String)>,
+  errors: Vec<ValidationError>,
+}
+
+impl<'a> Validator<'a> {
+  fn error(&mut self, instance_path: &str, schema_path: &str, keyword: &str, message: String) {
+    self.errors.push(ValidationError {
+      instance_path: instance_path.trim_start_matches('#').to_string(),
+      schema_path: pointer_join(schema_path, &[keyword]),
+      keyword: keyword.to_string(),
+      message,
+    });
+  }
+
+  /// Whether a value is valid, without reporting its errors.
+  fn is_valid<T: SchemaLike>(
+    &self,
+    schema: &'a ReferenceOr<T>,
+    value: &Value,
+    instance_path: &str,
+  ) -> bool {
+    let mut validator = Validator {
+      context: self.context,
+      root: self.root,
+      active: self.active.clone(),
+      errors: vec![],
+    };
+    validator.validate(schema, value, instance_path, "#");
+    validator.errors.is_empty()
+  }
+
+  fn validate<T: SchemaLike>(
+    &mut self,
+    schema: &'a ReferenceOr<T>,
+    value: &Value,
+    instance_path: &str,
+    schema_path: &str,
+  ) {
+    match schema {
+      ReferenceOr::Item(schema) => {
+        self.validate_schema(schema.as_schema(), value, instance_path, schema_path)
+      }
+      ReferenceOr::Reference { reference } => {
+        let key = (reference.clone(), instance_path.to_string());
+        if self.active.contains(&key) {
+          return;
+        }
+
+        let resolved = if reference == "#" {
+          Some(self.root)
+        } else {
+          resolve_schema_reference(reference, &self.context)
+        };
+
+        match resolved {
+          Some(resolved) => {
+            self.active.push(key);
+            self.validate_schema(resolved, value, instance_path, reference);
+            self.active.pop();
+          }
+          None => self.error(
+            instance_path,
+            schema_path,
+            "$ref",
+            format!("`{}` can't be resolved", reference),
+          ),
+        }
+      }
+    }
+  }
+
+  fn validate_schema(
+    &mut self,
+    schema: &'a Schema,
+    value: &Value,
+    instance_path: &str,
+    schema_path: &str,
+  ) {
+    if value.is_null() && schema.schema_data.nullable {
+      return;
+    }
+
+    let keywords = keywords(schema);
+
+    if let Some(typ) = keywords.typ {
+      if !matches_type(value, typ) {
+        return self.error(
+          instance_path,
+          schema_path,
+          "type",
+          format!("Must be {}", typ),
+        );
+      }
+    }
+
+    if !keywords.enumeration.is_empty() && !keywords.enumeration.contains(value) {
+      self.error(
+        instance_path,
+        schema_path,
+        "enum",
+        format!(
+          "Must be one of {}",
+          keywords
+            .enumeration
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<String>>()
+            .join(", ")
+        ),
+      );
+    }
+
+    match value {
+      Value::String(string) => self.validate_string(&keywords, string, instance_path, schema_path),
+      Value::Number(number) => self.validate_number(
+        &keywords,
+        number.as_f64().unwrap_or_default(),
+        instance_path,
+        schema_path,
+      ),
+      Value::Object(map) => self.validate_object(&keywords, map, instance_path, schema_path),
+      Value::Array(values) => self.validate_array(&keywords, values, instance_path, schema_path),
+      _ => {}
+    }
+
+    self.validate_composition(&keywords, value, instance_path, schema_path);
+  }
+
+  fn validate_string(
+    &mut self,
+    keywords: &Keywords,
+    string: &str,
+    instance_path: &str,
+    schema_path: &str,
+  ) {
+    let length = string.chars().count();
+
+    if let Some(min_length) = keywords
+      .min_length
+      .filter(|min_length| length < *min_length)
+    {
+      self.error(
+        instance_path,
+        schema_path,
+        "minLength",
+        format!("Must be at least {} characters long", min_length),
+      );
+    }
+
+    if let Some(max_length) = keywords
+      .max_length
+      .filter(|max_length| length > *max_length)
+    {
+      self.error(
+        instance_path,
+        schema_path,
+        "maxLength",
+        format!("Must be at most {} characters long", max_length),
+      );
+    }
+
+    if let Some(pattern) = keywords.pattern {
+      match Regex::new(pattern) {
+        Ok(regex) if !regex.is_match(string) => self.error(
+          instance_path,
+          schema_path,
+          "pattern",
+          format!("Must match `{}`", pattern),
+        ),
+        Ok(_) => {}
+        Err(_) => self.error(
+          instance_path,
+          schema_path,
+          "pattern",
+          format!("`{}` isn't a valid pattern", pattern),
+        ),
+      }
+    }
+
+    if let Some(format) = keywords.format {
+      if !matches_format(string, format) {
+        self.error(
+          instance_path,
+          schema_path,
+          "format",
+          format!("Must be a valid `{}`", format),
+        );
+      }
+    }
+  }
+
+  fn validate_number(
+    &mut self,
+    keywords: &Keywords,
+    number: f64,
+    instance_path: &str,
+    schema_path: &str,
+  ) {
+    if let Some(minimum) = keywords.minimum {
+      if number < minimum || (keywords.exclusive_minimum && number == minimum) {
+        self.error(
+          instance_path,
+          schema_path,
+          "minimum",
+          format!(
+            "Must be {} {}",
+            if keywords.exclusive_minimum {
+              ">"
+            } else {
+              ">="
+            },
+            format_number(minimum)
+          ),
+        );
+      }
+    }
+
+    if let Some(maximum) = keywords.maximum {
+      if number > maximum || (keywords.exclusive_maximum && number == maximum) {
+        self.error(
+          instance_path,
+          schema_path,
+          "maximum",
+          format!(
+            "Must be {} {}",
+            if keywords.exclusive_maximum {
+              "<"
+            } else {
+              "<="
+            },
+            format_number(maximum)
+          ),
+        );
+      }
+    }
+
+    if let Some(multiple_of) = keywords
+      .multiple_of
+      .filter(|multiple_of| *multiple_of > 0.0)
+    {
+      let quotient = number / multiple_of;
+
+      if (quotient - quotient.round()).abs() > f64::EPSILON * quotient.abs().max(1.0) {
+        self.error(
+          instance_path,
+          schema_path,
+          "multipleOf",
+          format!("Must be a multiple of {}", format_number(multiple_of)),
+        );
+      }
+    }
+  }
+
+  fn validate_object(
+    &mut self,
+    keywords: &Keywords<'a>,
+    map: &Map<String, Value>,
+    instance_path: &str,
+    schema_path: &str,
+  ) {
+    for name in keywords.required {
+      if !map.contains_key(name) {
+        self.error(
+          instance_path,
+          schema_path,
+          "required",
+          format!("Missing required property `{}`", name),
+        );
+      }
+    }
+
+    for (name, value) in map {
+      let property_instance_path = pointer_join(instance_path, &[name]);
+
+      match keywords
+        .properties
+        .iter()
+        .find(|(property_name, _)| *property_name == name)
+      {
+        Some((_, property)) => self.validate(
+          *property,
+          value,
+          &property_instance_path,
+          &pointer_join(schema_path, &["properties", name]),
+        ),
+        None => match keywords.additional_properties {
+          Some(AdditionalProperties::Any(false)) => self.error(
+            &property_instance_path,
+            schema_path,
+            "additionalProperties",
+            format!("Property `{}` isn't allowed", name),
+          ),
+          Some(AdditionalProperties::Schema(schema)) => self.validate(
+            schema.as_ref(),
+            value,
+            &property_instance_path,
+            &pointer_join(schema_path, &["additionalProperties"]),
+          ),
+          _ => {}
+        },
+      }
+    }
+
+    if let Some(min_properties) = keywords
+      .min_properties
+      .filter(|min_properties| map.len() < *min_properties)
+    {
+      self.error(
+        instance_path,
+        schema_path,
+        "minProperties",
+        format!("Must have at least {} properties", min_properties),
+      );
+    }
+
+    if let Some(max_properties) = keywords
+      .max_properties
+      .filter(|max_properties| map.len() > *max_properties)
+    {
+      self.error(
+        instance_path,
+        schema_path,
+        "maxProperties",
+        format!("Must have at most {} properties", max_properties),
+      );
+    }
+  }
+
+  fn validate_array(
+    &mut self,
+    keywords: &Keywords<'a>,
+    values: &[Value],
+    instance_path: &str,
+    schema_path: &str,
+  ) {
+    if let Some(items) = keywords.items {
+      let items_schema_path = pointer_join(schema_path, &["items"]);
+
+      for (index, value) in values.iter().enumerate() {
+        self.validate(
+          items,
+          value,
+          &pointer_join(instance_path, &[&index.to_string()]),
+          &items_schema_path,
+        );
+      }
+    }
+
+    if let Some(min_items) = keywords
+      .min_items
+      .filter(|min_items| values.len() < *min_items)
+    {
+      self.error(
+        instance_path,
+        schema_path,
+        "minItems",
+        format!("Must have at least {} items", min_items),
+      );
+    }
+
+    if let Some(max_items) = keywords
+      .max_items
+      .filter(|max_items| values.len() > *max_items)
+    {
+      self.error(
+        instance_path,
+        schema_path,
+        "maxItems",
+        format!("Must have at most {} items", max_items),
+      );
+    }
+
+    let has_duplicates = values
+      .iter()
+      .enumerate()
+      .any(|(index, value)| values[..index].contains(value));
+
+    if keywords.unique_items && has_duplicates {
+      self.error(
+        instance_path,
+        schema_path,
+        "uniqueItems",
+        "Must not have duplicate items".to_string(),
+      );
+    }
+  }
+
+  fn validate_composition(
+    &mut self,
+    keywords: &Keywords<'a>,
+    value: &Value,
+    instance_path: &str,
+    schema_path: &str,
+  ) {
+    for (index, schema) in keywords.all_of.iter().enumerate() {
+      self.validate(
+        schema,
+        value,
+        instance_path,
+        &pointer_join(schema_path, &["allOf", &index.to_string()]),
+      );
+    }
+
+    if !keywords.any_of.is_empty()
+      && !keywords
+        .any_of
+        .iter()
+        .any(|schema| self.is_valid(schema, value, instance_path))
+    {
+      self.error(
+        instance_path,
+        schema_path,
+        "anyOf",
+        "Must match at least one schema of `anyOf`".to_string(),
+      );
+    }
+
+    if !keywords.one_of.is_empty() {
+      let matches = keywords
+        .one_of
+        .iter()
+        .filter(|schema| self.is_valid(schema, value, instance_path))
+        .count();
+
+      if matches != 1 {
+        self.error(
+          instance_path,
+          schema_path,
+          "oneOf",
+          format!(
+            "Must match exactly one schema of `oneOf`, matches {}",
+            matches
+          ),
+        );
+      }
+    }
+
+    if let Some(not) = keywords.not {
+      if self.is_valid(not, value, instance_path) {
+        self.error(
+          instance_path,
+          schema_path,
+          "not",
+          "Must not match the schema of `not`".to_string(),
+        );
+      }
+    }
+  }
+}
+
+/// Errors of a value against a schema, `$ref`s like `#/components/schemas/User` are resolved in
+/// `components`.
+pub fn validate_value(
+  schema: &Schema,
+  value: &Value,
+  components: Option<&Components>,
+) -> Vec<ValidationError> {
+  let mut validator = Validator {
+    context: SchemaContext {
+      components,
+      ..Default::default()
+    },
+    root: schema,
+    active: vec![("#".to_string(), "#".to_string())],
+    errors: vec![],
+  };
+  validator.validate_schema(schema, value, "#", "#");
+
+  validator.errors
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_matches_format() {
+    assert!(matches_format("2024-01-31", "date"));
+    assert!(!matches_format("31/01/2024", "date"));
+    assert!(matches_format("2024-01-31T10:00:00Z", "date-time"));
+    assert!(matches_format("a@b.co", "email"));
+    assert!(!matches_format("a@b", "email"));
+    assert!(matches_format("::1", "ipv6"));
+    assert!(!matches_format("256.0.0.1", "ipv4"));
+    assert!(matches_format("anything", "password"));
+  }
+
+  #[test]
+  fn test_validate_value() {
+    let components: Components = serde_json::from_str(
+      r##"{
+      "schemas": {
+        "Tag": { "type": "string", "minLength": 2 }
+      }
+    }"##,
+    )
+    .unwrap();
+    let schema: Schema = serde_json::from_str(
+      r##"{
+      "type": "object",
+      "required": ["id", "email"],
+      "additionalProperties": false,
+      "properties": {
+        "id": { "type": "integer", "minimum": 1 },
+        "email": { "type": "string", "format": "email" },
+        "role": { "type": "string", "enum": ["admin", "user"] },
+        "code": { "type": "string", "pattern": "^[A-Z]{3}$" },
+        "tags": { "type": "array", "items": { "$ref": "#/components/schemas/Tag" }, "uniqueItems": true },
+        "owner": { "oneOf": [{ "type": "string" }, { "type": "integer" }] },
+        "parent": { "$ref": "#" },
+        "nickname": { "type": "string", "nullable": true }
+      }
+    }"##,
+    )
+    .unwrap();
+
+    let value = serde_json::json!({
+      "id": 0,
+      "role": "guest",
+      "code": "abc",
+      "tags": ["a", "ok", "ok"],
+      "owner": true,
+      "parent": { "id": 2, "email": "parent@example.com" },
+      "nickname": null,
+      "extra": 1
+    });
+
+    let errors = validate_value(&schema, &value, Some(&components));
+
+    let errors: Vec<(&str, &str, &str)> = errors
+      .iter()
+      .map(|error| {
+        (
+          error.instance_path.as_str(),
+          error.schema_path.as_str(),
+          error.keyword.as_str(),
+        )
+      })
+      .collect();
+
+    assert_eq!(
+      errors,
+      vec![
+        ("", "#/required", "required"),
+        ("/id", "#/properties/id/minimum", "minimum"),
+        ("/role", "#/properties/role/enum", "enum"),
+        ("/code", "#/properties/code/pattern", "pattern"),
+        ("/tags/0", "#/components/schemas/Tag/minLength", "minLength"),
+        ("/tags", "#/properties/tags/uniqueItems", "uniqueItems"),
+        ("/owner", "#/properties/owner/oneOf", "oneOf"),
+        ("/extra", "#/additionalProperties", "additionalProperties"),
+      ]
+    );
+
+    assert!(validate_value(
+      &schema,
+      &serde_json::json!({ "id": 1, "email": "a@b.co" }),
+      None
+    )
+    .is_empty());
+  }
+
+  #[test]
+  fn test_validate_self_referential_composition() {
+    let schema: Schema = serde_json::from_str(r##"{ "not": { "$ref": "#" } }"##).unwrap();
+    let errors = validate_value(&schema, &serde_json::json!(1), None);
+    assert_eq!(errors[0].keyword, "not");
+
+    let schema: Schema =
+      serde_json::from_str(r##"{ "type": "string", "allOf": [{ "$ref": "#" }] }"##).unwrap();
+    assert!(validate_value(&schema, &serde_json::json!("a"), None).is_empty());
+    assert_eq!(
+      validate_value(&schema, &serde_json::json!(1), None)[0].keyword,
+      "type"
+    );
+  }
+}
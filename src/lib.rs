@@ -1,21 +1,164 @@
 use napi::bindgen_prelude::*;
-use napi::{Env, JsObject, JsString};
+use napi::{Env, JsObject, JsString, JsUnknown};
 use napi_derive::napi;
-use openapiv3::{OpenAPI, Schema};
+use openapiv3::{Components, OpenAPI, Schema};
+mod diagnostics;
+mod json_schema_mock;
 mod json_schema_to_typescript;
+mod json_schema_validate;
+mod open_api_diff;
+mod open_api_filter;
+mod open_api_to_module;
+mod open_api_to_msw;
+mod open_api_to_server;
 mod open_api_to_typescript;
-use json_schema_to_typescript::SchemaTypeOptions;
-use open_api_to_typescript::{open_api_to_typescript, OpenApiOutput};
+mod open_api_validate;
+mod schema_diff;
+use diagnostics::{ensure_lossless, Diagnostic, Diagnostics};
+use json_schema_mock::MockOptions;
+use json_schema_to_typescript::{
+  normalize_schema, schema_to_typescript_with_diagnostics, SchemaTypeOptions, SchemaTypeOutput,
+};
+use json_schema_validate::ValidationError;
+use open_api_diff::OpenApiDiff;
+use open_api_to_module::open_api_to_typescript_module;
+use open_api_to_msw::{open_api_to_msw_module, MswOptions};
+use open_api_to_server::{open_api_to_server_module, ServerOptions};
+use open_api_to_typescript::{
+  normalize_open_api, open_api_to_typescript, OpenApiOptions, OpenApiOutput,
+};
+use schema_diff::SchemaDiffOutput;
 use serde_json::Value;
 
 #[napi]
-pub fn open_api_to_types(env: Env, open_api_input: JsObject) -> Result<OpenApiOutput> {
-  let open_api_json = js_object_to_serde_value(env, open_api_input)?;
+pub fn open_api_to_types(
+  env: Env,
+  open_api_input: JsObject,
+  options: Option<OpenApiOptions>,
+) -> Result<OpenApiOutput> {
+  let (open_api, diagnostics) = js_object_to_open_api(env, open_api_input)?;
+  let strict = options.as_ref().and_then(|options| options.strict);
+  let output = open_api_to_typescript(open_api, diagnostics, options);
+  ensure_strict(strict, &output.diagnostics)?;
+
+  Ok(output)
+}
+
+#[napi]
+pub fn open_api_to_module(
+  env: Env,
+  open_api_input: JsObject,
+  options: Option<OpenApiOptions>,
+) -> Result<String> {
+  let (open_api, diagnostics) = js_object_to_open_api(env, open_api_input)?;
+  let strict = options.as_ref().and_then(|options| options.strict);
+  let (module, diagnostics) = open_api_to_typescript_module(&open_api, diagnostics, options);
+  ensure_strict(strict, &diagnostics)?;
+
+  Ok(module)
+}
+
+#[napi]
+pub fn open_api_to_msw_handlers(
+  env: Env,
+  open_api_input: JsObject,
+  options: Option<OpenApiOptions>,
+  msw_options: Option<MswOptions>,
+) -> Result<String> {
+  let (open_api, diagnostics) = js_object_to_open_api(env, open_api_input)?;
+  let strict = options.as_ref().and_then(|options| options.strict);
+  let (module, diagnostics) = open_api_to_msw_module(&open_api, diagnostics, options, msw_options);
+  ensure_strict(strict, &diagnostics)?;
+
+  Ok(module)
+}
+
+#[napi]
+pub fn open_api_to_server_types(
+  env: Env,
+  open_api_input: JsObject,
+  options: Option<OpenApiOptions>,
+  server_options: Option<ServerOptions>,
+) -> Result<String> {
+  let (open_api, diagnostics) = js_object_to_open_api(env, open_api_input)?;
+  let strict = options.as_ref().and_then(|options| options.strict);
+  let (module, diagnostics) =
+    open_api_to_server_module(&open_api, diagnostics, options, server_options);
+  ensure_strict(strict, &diagnostics)?;
+
+  Ok(module)
+}
 
-  let open_api: OpenAPI = serde_json::from_value(open_api_json)
-    .map_err(|e| napi::Error::new(napi::Status::InvalidArg, format!("Invalid open api: {}", e)))?;
+#[napi]
+pub fn validate_open_api(env: Env, open_api_input: JsObject) -> Result<Vec<Diagnostic>> {
+  // Validated as written, so the pointers don't name the nodes added by the normalization
+  let document = js_object_to_serde_value(env, open_api_input)?;
+  let mut open_api_json = document.clone();
+  normalize_open_api(&mut open_api_json);
+  let open_api = parse_open_api(open_api_json)?;
+
+  Ok(open_api_validate::validate_open_api(&open_api, &document))
+}
+
+#[napi]
+pub fn diff_open_api(
+  env: Env,
+  old_open_api_input: JsObject,
+  new_open_api_input: JsObject,
+) -> Result<OpenApiDiff> {
+  let (old_open_api, _) = js_object_to_open_api(env, old_open_api_input)?;
+  let (new_open_api, _) = js_object_to_open_api(env, new_open_api_input)?;
+
+  Ok(open_api_diff::diff_open_api(&old_open_api, &new_open_api))
+}
+
+#[napi]
+pub fn diff_schemas(
+  env: Env,
+  old_schema_input: JsObject,
+  new_schema_input: JsObject,
+) -> Result<SchemaDiffOutput> {
+  let (old_schema, _) = js_object_to_schema(env, old_schema_input)?;
+  let (new_schema, _) = js_object_to_schema(env, new_schema_input)?;
 
-  Ok(open_api_to_typescript(open_api))
+  Ok(schema_diff::diff_schemas(&old_schema, &new_schema))
+}
+
+#[napi]
+pub fn validate(
+  env: Env,
+  schema_input: JsObject,
+  value: JsUnknown,
+  components_input: Option<JsObject>,
+) -> Result<Vec<ValidationError>> {
+  let (schema, _) = js_object_to_schema(env, schema_input)?;
+  let value = js_value_to_serde_value(env, value)?;
+  let components = components_input
+    .map(|components_input| js_object_to_components(env, components_input))
+    .transpose()?;
+
+  Ok(json_schema_validate::validate_value(
+    &schema,
+    &value,
+    components.as_ref(),
+  ))
+}
+
+#[napi]
+pub fn mock_from_schema(
+  env: Env,
+  schema_input: JsObject,
+  options: Option<MockOptions>,
+  components_input: Option<JsObject>,
+) -> Result<JsUnknown> {
+  let (schema, _) = js_object_to_schema(env, schema_input)?;
+  let components = components_input
+    .map(|components_input| js_object_to_components(env, components_input))
+    .transpose()?;
+  let value =
+    json_schema_mock::mock_from_schema(&schema, components.as_ref(), &options.unwrap_or_default());
+
+  serde_value_to_js_value(env, &value)
 }
 
 #[napi]
@@ -24,26 +167,106 @@ pub fn schema_to_type(
   schema_input: JsObject,
   options: Option<SchemaTypeOptions>,
 ) -> Result<String> {
-  let schema_json = js_object_to_serde_value(env, schema_input)?;
-
-  let schema: Schema = serde_json::from_value(schema_json)
-    .map_err(|e| napi::Error::new(napi::Status::InvalidArg, format!("Invalid schema: {}", e)))?;
+  if options.as_ref().and_then(|options| options.strict) == Some(true) {
+    return schema_to_type_with_diagnostics(env, schema_input, options)
+      .map(|output| output.ts_type);
+  }
 
+  let (schema, _) = js_object_to_schema(env, schema_input)?;
   let interface =
     json_schema_to_typescript::schema_to_typescript(openapiv3::ReferenceOr::Item(schema), options);
 
   Ok(interface.to_string())
 }
 
+#[napi]
+pub fn schema_to_type_with_diagnostics(
+  env: Env,
+  schema_input: JsObject,
+  options: Option<SchemaTypeOptions>,
+) -> Result<SchemaTypeOutput> {
+  let (schema, diagnostics) = js_object_to_schema(env, schema_input)?;
+  let strict = options.as_ref().and_then(|options| options.strict);
+  let mut output =
+    schema_to_typescript_with_diagnostics(openapiv3::ReferenceOr::Item(schema), options);
+  output.diagnostics.splice(0..0, diagnostics);
+  ensure_strict(strict, &output.diagnostics)?;
+
+  Ok(output)
+}
+
+/// Fails with the lossy conversions in `strict` mode, checked here for every function.
+fn ensure_strict(strict: Option<bool>, diagnostics: &[Diagnostic]) -> Result<()> {
+  if strict.unwrap_or(false) {
+    ensure_lossless(diagnostics)
+      .map_err(|error| napi::Error::new(napi::Status::InvalidArg, error.to_string()))?;
+  }
+
+  Ok(())
+}
+
+/// Schema with the keywords its deserialization ignores.
+fn js_object_to_schema(env: Env, schema_input: JsObject) -> Result<(Schema, Vec<Diagnostic>)> {
+  let mut schema_json = js_object_to_serde_value(env, schema_input)?;
+  let diagnostics = Diagnostics::default();
+  normalize_schema(&mut schema_json, "#", &diagnostics);
+
+  let schema = serde_json::from_value(schema_json)
+    .map_err(|e| napi::Error::new(napi::Status::InvalidArg, format!("Invalid schema: {}", e)))?;
+
+  Ok((schema, diagnostics.into_vec()))
+}
+
+/// Document with the keywords its deserialization ignores.
+fn js_object_to_open_api(env: Env, open_api_input: JsObject) -> Result<(OpenAPI, Vec<Diagnostic>)> {
+  let mut open_api_json = js_object_to_serde_value(env, open_api_input)?;
+  let diagnostics = normalize_open_api(&mut open_api_json);
+
+  Ok((parse_open_api(open_api_json)?, diagnostics))
+}
+
+fn parse_open_api(open_api_json: Value) -> Result<OpenAPI> {
+  serde_json::from_value(open_api_json)
+    .map_err(|e| napi::Error::new(napi::Status::InvalidArg, format!("Invalid open api: {}", e)))
+}
+
+fn js_object_to_components(env: Env, components_input: JsObject) -> Result<Components> {
+  let mut components_json = js_object_to_serde_value(env, components_input)?;
+
+  if let Some(Value::Object(schemas)) = components_json.get_mut("schemas") {
+    // The ignored keywords only matter to the conversion into TypeScript
+    let diagnostics = Diagnostics::default();
+    for schema in schemas.values_mut() {
+      normalize_schema(schema, "#", &diagnostics);
+    }
+  }
+
+  serde_json::from_value(components_json).map_err(|e| {
+    napi::Error::new(
+      napi::Status::InvalidArg,
+      format!("Invalid components: {}", e),
+    )
+  })
+}
+
 fn js_object_to_serde_value(env: Env, obj: JsObject) -> Result<Value> {
+  js_value_to_serde_value(env, obj.into_unknown())
+}
+
+/// `undefined` and other values without a JSON representation become `null`.
+fn js_value_to_serde_value(env: Env, value: JsUnknown) -> Result<Value> {
   let global = env.get_global()?;
   let json = global.get_named_property::<JsObject>("JSON")?;
 
   let stringify = json.get_named_property::<JsFunction>("stringify")?;
 
-  let json_string: JsString = stringify
-    .call(None, &[obj.into_unknown()])?
-    .coerce_to_string()?;
+  let json = stringify.call(None, &[value])?;
+
+  if json.get_type()? == ValueType::Undefined {
+    return Ok(Value::Null);
+  }
+
+  let json_string: JsString = json.coerce_to_string()?;
 
   let json_rust_str = json_string.into_utf8()?.into_owned()?;
 
@@ -54,3 +277,13 @@ fn js_object_to_serde_value(env: Env, obj: JsObject) -> Result<Value> {
     )
   })
 }
+
+fn serde_value_to_js_value(env: Env, value: &Value) -> Result<JsUnknown> {
+  let global = env.get_global()?;
+  let json = global.get_named_property::<JsObject>("JSON")?;
+
+  let parse = json.get_named_property::<JsFunction>("parse")?;
+  let json_string = env.create_string(&value.to_string())?;
+
+  parse.call(None, &[json_string])
+}
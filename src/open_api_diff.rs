@@ -0,0 +1,592 @@
+use crate::diagnostics::pointer_join;
+use crate::json_schema_to_typescript::SchemaContext;
+use crate::open_api_to_typescript::{
+  default_is_success, is_success_status, media_type_kind, merge_parameters, parameter_location,
+  resolve_component_reference, status_responses, MediaTypeKind,
+};
+use crate::schema_diff::{SchemaChange, SchemaDiff};
+
+use napi_derive::napi;
+use openapiv3::{
+  Components, Content, OpenAPI, Operation, Parameter, ParameterSchemaOrContent, PathItem,
+  ReferenceOr, Schema,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+#[napi(object)]
+pub struct OpenApiChange {
+  /// JSON pointer in the new document, or in the old one for removals. Changes of a referenced
+  /// schema point to its definition, like `#/components/schemas/User/properties/id`
+  pub pointer: String,
+  /// Operation of the change, like `GET /users/{id}`
+  pub operation: Option<String>,
+  /// Stable identifier, like `property-removed`
+  pub code: String,
+  /// True when clients of the old document may break
+  pub breaking: bool,
+  pub message: String,
+}
+
+#[derive(Debug)]
+#[napi(object)]
+pub struct OpenApiDiff {
+  /// True when at least one change is breaking
+  pub breaking: bool,
+  pub changes: Vec<OpenApiChange>,
+}
+
+type DocumentOperation<'a> = (&'a String, &'a str, &'a PathItem, &'a Operation);
+
+fn document_operations(open_api: &OpenAPI) -> Vec<DocumentOperation<'_>> {
+  open_api
+    .paths
+    .iter()
+    .filter_map(|(path, path_item)| path_item.as_item().map(|path_item| (path, path_item)))
+    .flat_map(|(path, path_item)| {
+      path_item
+        .iter()
+        .map(move |(method, operation)| (path, method, path_item, operation))
+    })
+    .collect()
+}
+
+/// Schema of the first JSON media type, or of the first media type when there is none.
+fn preferred_schema(content: &Content) -> Option<(&String, &ReferenceOr<Schema>)> {
+  let schemas: Vec<(&String, &ReferenceOr<Schema>)> = content
+    .iter()
+    .filter_map(|(media_type, media_type_object)| {
+      media_type_object
+        .schema
+        .as_ref()
+        .map(|schema| (media_type, schema))
+    })
+    .collect();
+
+  schemas
+    .iter()
+    .find(|(media_type, _)| matches!(media_type_kind(media_type), MediaTypeKind::Json))
+    .or(schemas.first())
+    .copied()
+}
+
+/// Schema of a parameter, or of its preferred media type, with its pointer from the parameter.
+fn parameter_schema(
+  format: &ParameterSchemaOrContent,
+) -> Option<(Vec<&str>, &ReferenceOr<Schema>)> {
+  match format {
+    ParameterSchemaOrContent::Schema(schema) => Some((vec!["schema"], schema)),
+    ParameterSchemaOrContent::Content(content) => preferred_schema(content)
+      .map(|(media_type, schema)| (vec!["content", media_type.as_str(), "schema"], schema)),
+  }
+}
+
+struct OperationDiff<'a> {
+  old: SchemaContext<'a>,
+  new: SchemaContext<'a>,
+  operation: String,
+  pointer: String,
+  changes: Vec<OpenApiChange>,
+}
+
+impl<'a> OperationDiff<'a> {
+  fn push(&mut self, pointer: &str, code: &str, breaking: bool, message: String) {
+    self.changes.push(OpenApiChange {
+      pointer: pointer.to_string(),
+      operation: Some(self.operation.clone()),
+      code: code.to_string(),
+      breaking,
+      message,
+    });
+  }
+
+  /// Request schemas break when the new schema rejects old data, response schemas when old clients
+  /// reject new data.
+  fn compare_schemas(
+    &mut self,
+    old: &ReferenceOr<Schema>,
+    new: &ReferenceOr<Schema>,
+    pointer: &str,
+    is_request: bool,
+  ) {
+    let mut schema_diff = SchemaDiff::new(self.old.components, self.new.components);
+    schema_diff.compare(old, new, pointer);
+
+    for SchemaChange {
+      pointer,
+      code,
+      message,
+      backward,
+      forward,
+    } in schema_diff.changes
+    {
+      let breaking = if is_request { !backward } else { !forward };
+      self.push(&pointer, &code, breaking, message);
+    }
+  }
+
+  /// Parameters come with the pointer of their definition, from `merge_parameters`.
+  fn compare_parameters(&mut self, old: Vec<(String, &Parameter)>, new: Vec<(String, &Parameter)>) {
+    let parameter_key = |parameter: &Parameter| {
+      (
+        parameter_location(parameter),
+        parameter.parameter_data_ref().name.clone(),
+      )
+    };
+
+    for (old_pointer, old_parameter) in &old {
+      let (location, name) = parameter_key(old_parameter);
+
+      let Some((pointer, new_parameter)) = new
+        .iter()
+        .find(|(_, new_parameter)| parameter_key(new_parameter) == (location, name.clone()))
+      else {
+        self.push(
+          old_pointer,
+          "parameter-removed",
+          false,
+          format!("{} parameter `{}` removed", location, name),
+        );
+        continue;
+      };
+
+      let old_data = old_parameter.parameter_data_ref();
+      let new_data = new_parameter.parameter_data_ref();
+
+      if !old_data.required && new_data.required {
+        self.push(
+          pointer,
+          "parameter-required",
+          true,
+          format!("{} parameter `{}` became required", location, name),
+        );
+      }
+
+      if let (Some((_, old_schema)), Some((segments, new_schema))) = (
+        parameter_schema(&old_data.format),
+        parameter_schema(&new_data.format),
+      ) {
+        self.compare_schemas(
+          old_schema,
+          new_schema,
+          &pointer_join(pointer, &segments),
+          true,
+        );
+      }
+    }
+
+    for (pointer, new_parameter) in &new {
+      let (location, name) = parameter_key(new_parameter);
+
+      if !old
+        .iter()
+        .any(|(_, old_parameter)| parameter_key(old_parameter) == (location, name.clone()))
+      {
+        let required = new_parameter.parameter_data_ref().required;
+        self.push(
+          pointer,
+          "parameter-added",
+          required,
+          format!(
+            "{} {} parameter `{}` added",
+            if required { "Required" } else { "Optional" },
+            location,
+            name
+          ),
+        );
+      }
+    }
+  }
+
+  fn compare_request_bodies(&mut self, old_operation: &'a Operation, new_operation: &'a Operation) {
+    let pointer = pointer_join(&self.pointer, &["requestBody"]);
+    let resolve = |operation: &'a Operation, context: &SchemaContext<'a>| {
+      operation.request_body.as_ref().and_then(|request_body| {
+        resolve_component_reference(
+          request_body,
+          "requestBodies",
+          |components, name| components.request_bodies.get(name),
+          "",
+          context,
+        )
+      })
+    };
+
+    match (
+      resolve(old_operation, &self.old),
+      resolve(new_operation, &self.new),
+    ) {
+      (None, None) => {}
+      (None, Some(new_body)) => self.push(
+        &pointer,
+        "request-body-added",
+        new_body.required,
+        "Request body added".to_string(),
+      ),
+      (Some(_), None) => self.push(
+        &pointer,
+        "request-body-removed",
+        false,
+        "Request body removed".to_string(),
+      ),
+      (Some(old_body), Some(new_body)) => {
+        if !old_body.required && new_body.required {
+          self.push(
+            &pointer,
+            "request-body-required",
+            true,
+            "Request body became required".to_string(),
+          );
+        }
+
+        if let (Some((_, old_schema)), Some((media_type, new_schema))) = (
+          preferred_schema(&old_body.content),
+          preferred_schema(&new_body.content),
+        ) {
+          let schema_pointer = pointer_join(&pointer, &["content", media_type, "schema"]);
+          self.compare_schemas(old_schema, new_schema, &schema_pointer, true);
+        }
+      }
+    }
+  }
+
+  fn compare_responses(&mut self, old_operation: &'a Operation, new_operation: &'a Operation) {
+    let old_responses = status_responses(&old_operation.responses);
+    let new_responses = status_responses(&new_operation.responses);
+    let resolve = |response, context: &SchemaContext<'a>| {
+      resolve_component_reference(
+        response,
+        "responses",
+        |components: &'a Components, name| components.responses.get(name),
+        "",
+        context,
+      )
+    };
+
+    for (status_code, old_response) in &old_responses {
+      let pointer = pointer_join(&self.pointer, &["responses", status_code]);
+
+      let Some((_, new_response)) = new_responses
+        .iter()
+        .find(|(new_status_code, _)| new_status_code == status_code)
+      else {
+        self.push(
+          &pointer,
+          "response-removed",
+          is_success_status(status_code, default_is_success(&old_operation.responses)),
+          format!("`{}` response removed", status_code),
+        );
+        continue;
+      };
+
+      let (Some(old_response), Some(new_response)) = (
+        resolve(old_response, &self.old),
+        resolve(new_response, &self.new),
+      ) else {
+        continue;
+      };
+
+      match (
+        preferred_schema(&old_response.content),
+        preferred_schema(&new_response.content),
+      ) {
+        (Some((_, old_schema)), Some((media_type, new_schema))) => {
+          let schema_pointer = pointer_join(&pointer, &["content", media_type, "schema"]);
+          self.compare_schemas(old_schema, new_schema, &schema_pointer, false);
+        }
+        (Some(_), None) => self.push(
+          &pointer,
+          "response-body-removed",
+          true,
+          format!("`{}` response body removed", status_code),
+        ),
+        (None, Some(_)) => self.push(
+          &pointer,
+          "response-body-added",
+          false,
+          format!("`{}` response body added", status_code),
+        ),
+        (None, None) => {}
+      }
+    }
+
+    for (status_code, _) in &new_responses {
+      if !old_responses
+        .iter()
+        .any(|(old_status_code, _)| old_status_code == status_code)
+      {
+        self.push(
+          &pointer_join(&self.pointer, &["responses", status_code]),
+          "response-added",
+          false,
+          format!("`{}` response added", status_code),
+        );
+      }
+    }
+  }
+}
+
+/// Changes between two versions of a document, classified by whether they break existing clients.
+pub fn diff_open_api(old: &OpenAPI, new: &OpenAPI) -> OpenApiDiff {
+  let old_context = SchemaContext {
+    components: old.components.as_ref(),
+    ..Default::default()
+  };
+  let new_context = SchemaContext {
+    components: new.components.as_ref(),
+    ..Default::default()
+  };
+  let old_operations = document_operations(old);
+  let new_operations = document_operations(new);
+  let mut changes: Vec<OpenApiChange> = vec![];
+
+  let operation_change =
+    |path: &str, method: &str, code: &str, breaking: bool, message: &str| OpenApiChange {
+      pointer: pointer_join("#/paths", &[path, method]),
+      operation: Some(format!("{} {}", method.to_uppercase(), path)),
+      code: code.to_string(),
+      breaking,
+      message: message.to_string(),
+    };
+
+  for (path, method, old_path_item, old_operation) in &old_operations {
+    let Some((_, _, new_path_item, new_operation)) = new_operations
+      .iter()
+      .find(|(new_path, new_method, _, _)| new_path == path && new_method == method)
+    else {
+      changes.push(operation_change(
+        path,
+        method,
+        "operation-removed",
+        true,
+        "Operation removed",
+      ));
+      continue;
+    };
+
+    let path_pointer = pointer_join("#/paths", &[path]);
+    let operation_pointer = pointer_join(&path_pointer, &[method]);
+    let mut operation_diff = OperationDiff {
+      old: old_context,
+      new: new_context,
+      operation: format!("{} {}", method.to_uppercase(), path),
+      pointer: operation_pointer.clone(),
+      changes: vec![],
+    };
+
+    operation_diff.compare_parameters(
+      merge_parameters(
+        &old_path_item.parameters,
+        &old_context.at(&path_pointer),
+        &old_operation.parameters,
+        &old_context.at(&operation_pointer),
+      ),
+      merge_parameters(
+        &new_path_item.parameters,
+        &new_context.at(&path_pointer),
+        &new_operation.parameters,
+        &new_context.at(&operation_pointer),
+      ),
+    );
+    operation_diff.compare_request_bodies(old_operation, new_operation);
+    operation_diff.compare_responses(old_operation, new_operation);
+
+    changes.extend(operation_diff.changes);
+  }
+
+  for (path, method, _, _) in &new_operations {
+    if !old_operations
+      .iter()
+      .any(|(old_path, old_method, _, _)| old_path == path && old_method == method)
+    {
+      changes.push(operation_change(
+        path,
+        method,
+        "operation-added",
+        false,
+        "Operation added",
+      ));
+    }
+  }
+
+  OpenApiDiff {
+    breaking: changes.iter().any(|change| change.breaking),
+    changes,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_diff_open_api() {
+    let old: OpenAPI = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {
+        "/users": {
+          "post": {
+            "parameters": [
+              {
+                "name": "filter",
+                "in": "query",
+                "content": {
+                  "application/json": {
+                    "schema": { "type": "object", "properties": { "role": { "type": "string" } } }
+                  }
+                }
+              }
+            ],
+            "requestBody": {
+              "content": {
+                "application/json": {
+                  "schema": {
+                    "type": "object",
+                    "properties": { "name": { "type": "string" } }
+                  }
+                }
+              }
+            },
+            "responses": {
+              "200": {
+                "description": "User",
+                "content": {
+                  "application/json": { "schema": { "$ref": "#/components/schemas/User" } }
+                }
+              }
+            }
+          }
+        },
+        "/users/{id}": {
+          "delete": { "responses": { "204": { "description": "Deleted" } } }
+        }
+      },
+      "components": {
+        "schemas": {
+          "User": {
+            "type": "object",
+            "required": ["id", "status"],
+            "properties": {
+              "id": { "type": "string" },
+              "status": { "type": "string", "enum": ["active", "inactive"] }
+            }
+          }
+        }
+      }
+    }"##,
+    )
+    .unwrap();
+    let new: OpenAPI = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "2.0.0" },
+      "paths": {
+        "/users": {
+          "post": {
+            "parameters": [
+              { "name": "dryRun", "in": "query", "schema": { "type": "boolean" } },
+              {
+                "name": "filter",
+                "in": "query",
+                "content": {
+                  "application/json": {
+                    "schema": { "type": "object", "properties": { "role": { "type": "integer" } } }
+                  }
+                }
+              }
+            ],
+            "requestBody": {
+              "content": {
+                "application/json": {
+                  "schema": {
+                    "type": "object",
+                    "required": ["name", "email"],
+                    "properties": {
+                      "name": { "type": "string" },
+                      "email": { "type": "string" }
+                    }
+                  }
+                }
+              }
+            },
+            "responses": {
+              "200": {
+                "description": "User",
+                "content": {
+                  "application/json": { "schema": { "$ref": "#/components/schemas/User" } }
+                }
+              }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "User": {
+            "type": "object",
+            "required": ["status"],
+            "properties": {
+              "status": { "type": "string", "enum": ["active", "inactive", "banned"] }
+            }
+          }
+        }
+      }
+    }"##,
+    )
+    .unwrap();
+
+    let diff = diff_open_api(&old, &new);
+
+    let changes: Vec<(&str, &str, bool)> = diff
+      .changes
+      .iter()
+      .map(|change| {
+        (
+          change.pointer.as_str(),
+          change.code.as_str(),
+          change.breaking,
+        )
+      })
+      .collect();
+
+    assert!(diff.breaking);
+    assert_eq!(
+      changes,
+      vec![
+        (
+          "#/paths/~1users/post/parameters/1/content/application~1json/schema/properties/role",
+          "type-changed",
+          true
+        ),
+        (
+          "#/paths/~1users/post/parameters/0",
+          "parameter-added",
+          false
+        ),
+        (
+          "#/paths/~1users/post/requestBody/content/application~1json/schema/properties/name",
+          "property-required",
+          true
+        ),
+        (
+          "#/paths/~1users/post/requestBody/content/application~1json/schema/properties/email",
+          "property-added",
+          true
+        ),
+        (
+          "#/components/schemas/User/properties/id",
+          "property-removed",
+          true
+        ),
+        (
+          "#/components/schemas/User/properties/status/enum",
+          "enum-widened",
+          true
+        ),
+        ("#/paths/~1users~1{id}/delete", "operation-removed", true),
+      ]
+    );
+    assert_eq!(diff.changes[0].operation.as_deref(), Some("POST /users"));
+  }
+}
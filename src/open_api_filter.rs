@@ -0,0 +1,256 @@
+use napi_derive::napi;
+use openapiv3::{OpenAPI, Operation, PathItem};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+#[derive(Debug, Default, Clone)]
+#[napi(object)]
+pub struct FilterOptions {
+  pub include_tags: Option<Vec<String>>,
+  pub exclude_tags: Option<Vec<String>>,
+  /// Path globs, `*` matches within a path segment and `**` across segments
+  pub include_paths: Option<Vec<String>>,
+  pub exclude_paths: Option<Vec<String>>,
+  pub include_methods: Option<Vec<String>>,
+  pub exclude_methods: Option<Vec<String>>,
+  pub include_operation_ids: Option<Vec<String>>,
+  pub exclude_operation_ids: Option<Vec<String>>,
+  pub exclude_deprecated: Option<bool>,
+}
+
+fn matches_glob(pattern: &str, value: &str) -> bool {
+  if let Some(rest) = pattern.strip_prefix("**") {
+    return value
+      .char_indices()
+      .map(|(index, _)| index)
+      .chain([value.len()])
+      .any(|index| matches_glob(rest, &value[index..]));
+  }
+
+  if let Some(rest) = pattern.strip_prefix('*') {
+    return value
+      .char_indices()
+      .take_while(|(_, c)| *c != '/')
+      .map(|(index, _)| index)
+      .chain([value.find('/').unwrap_or(value.len())])
+      .any(|index| matches_glob(rest, &value[index..]));
+  }
+
+  match (pattern.chars().next(), value.chars().next()) {
+    (None, None) => true,
+    (Some(p), Some(v)) if p == v => matches_glob(&pattern[p.len_utf8()..], &value[v.len_utf8()..]),
+    _ => false,
+  }
+}
+
+/// An empty or missing list includes everything.
+fn is_included(patterns: &Option<Vec<String>>, matches: impl Fn(&str) -> bool) -> bool {
+  match patterns {
+    Some(patterns) if !patterns.is_empty() => patterns.iter().any(|pattern| matches(pattern)),
+    _ => true,
+  }
+}
+
+fn is_excluded(patterns: &Option<Vec<String>>, matches: impl Fn(&str) -> bool) -> bool {
+  patterns
+    .as_ref()
+    .is_some_and(|patterns| patterns.iter().any(|pattern| matches(pattern)))
+}
+
+impl FilterOptions {
+  /// An operation is kept when it matches every include list and none of the exclude lists.
+  pub(crate) fn includes(&self, path: &str, method: &str, operation: &Operation) -> bool {
+    let has_tag = |tag: &str| operation.tags.iter().any(|t| t == tag);
+    let is_path = |pattern: &str| matches_glob(pattern, path);
+    let is_method = |m: &str| m.eq_ignore_ascii_case(method);
+    let is_operation_id = |id: &str| operation.operation_id.as_deref() == Some(id);
+
+    is_included(&self.include_tags, has_tag)
+      && is_included(&self.include_paths, is_path)
+      && is_included(&self.include_methods, is_method)
+      && is_included(&self.include_operation_ids, is_operation_id)
+      && !is_excluded(&self.exclude_tags, has_tag)
+      && !is_excluded(&self.exclude_paths, is_path)
+      && !is_excluded(&self.exclude_methods, is_method)
+      && !is_excluded(&self.exclude_operation_ids, is_operation_id)
+      && !(self.exclude_deprecated.unwrap_or(false) && operation.deprecated)
+  }
+}
+
+fn collect_references(value: &Value, references: &mut Vec<String>) {
+  match value {
+    Value::Object(map) => {
+      for (key, value) in map {
+        match (key.as_str(), value) {
+          ("$ref", Value::String(reference)) => references.push(reference.clone()),
+          // Discriminator mappings reference schemas without a `$ref`, or by their bare name. A
+          // property can be named `discriminator` too, so its value is still walked.
+          ("discriminator", Value::Object(discriminator)) => {
+            if let Some(Value::Object(mapping)) = discriminator.get("mapping") {
+              references.extend(mapping.values().filter_map(Value::as_str).map(|value| {
+                match value.contains('/') {
+                  true => value.to_string(),
+                  false => format!("#/components/schemas/{}", value),
+                }
+              }));
+            }
+            collect_references(value, references);
+          }
+          _ => collect_references(value, references),
+        }
+      }
+    }
+    Value::Array(values) => values
+      .iter()
+      .for_each(|value| collect_references(value, references)),
+    _ => {}
+  }
+}
+
+/// Section and name (`schemas`, `User`) of the components used by the operations, following
+/// references between components and including the security schemes they require.
+pub(crate) fn reachable_components(
+  open_api: &OpenAPI,
+  operations: &[(&PathItem, &Operation)],
+) -> BTreeSet<(String, String)> {
+  let components = serde_json::to_value(&open_api.components).unwrap_or_default();
+  let mut pending: Vec<String> = vec![];
+
+  for (path_item, operation) in operations {
+    if let Ok(parameters) = serde_json::to_value(&path_item.parameters) {
+      collect_references(&parameters, &mut pending);
+    }
+    if let Ok(operation_json) = serde_json::to_value(operation) {
+      collect_references(&operation_json, &mut pending);
+    }
+
+    let security = operation.security.as_ref().or(open_api.security.as_ref());
+    pending.extend(
+      security
+        .into_iter()
+        .flatten()
+        .flat_map(|requirement| requirement.keys())
+        .map(|name| format!("#/components/securitySchemes/{}", name)),
+    );
+  }
+
+  let mut reachable = BTreeSet::new();
+
+  while let Some(reference) = pending.pop() {
+    let Some((section, name)) = reference
+      .strip_prefix("#/components/")
+      .and_then(|pointer| pointer.split_once('/'))
+    else {
+      continue;
+    };
+    let name = name.replace("~1", "/").replace("~0", "~");
+    let key = (section.to_string(), name.clone());
+
+    if reachable.contains(&key) {
+      continue;
+    }
+
+    if let Some(component) = components
+      .get(section)
+      .and_then(|section| section.get(&name))
+    {
+      collect_references(component, &mut pending);
+      reachable.insert(key);
+    }
+  }
+
+  reachable
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_matches_glob() {
+    assert!(matches_glob("/users/*", "/users/{id}"));
+    assert!(!matches_glob("/users/*", "/users/{id}/posts"));
+    assert!(matches_glob("/users/**", "/users/{id}/posts"));
+    assert!(matches_glob("/**/posts", "/users/{id}/posts"));
+    assert!(matches_glob("/users*", "/users"));
+    assert!(!matches_glob("/users", "/users/{id}"));
+  }
+
+  #[test]
+  fn test_reachable_components() {
+    let open_api: OpenAPI = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "security": [{ "apiKey": [] }],
+      "paths": {
+        "/users": {
+          "get": {
+            "responses": { "200": { "$ref": "#/components/responses/Users" } }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "User": {
+            "type": "object",
+            "properties": { "pet": { "$ref": "#/components/schemas/Pet" } }
+          },
+          "Pet": {
+            "oneOf": [{ "$ref": "#/components/schemas/Cat" }],
+            "discriminator": {
+              "propertyName": "kind",
+              "mapping": { "cat": "#/components/schemas/Cat", "dog": "Dog" }
+            }
+          },
+          "Cat": {
+            "type": "object",
+            "properties": {
+              "mapping": { "type": "array", "items": { "$ref": "#/components/schemas/Toy" } },
+              "discriminator": { "additionalProperties": { "$ref": "#/components/schemas/Bowl" } }
+            }
+          },
+          "Toy": { "type": "string" },
+          "Bowl": { "type": "string" },
+          "Dog": { "type": "object" },
+          "Unused": { "type": "string" }
+        },
+        "responses": {
+          "Users": {
+            "description": "Users",
+            "content": {
+              "application/json": {
+                "schema": { "type": "array", "items": { "$ref": "#/components/schemas/User" } }
+              }
+            }
+          }
+        },
+        "securitySchemes": {
+          "apiKey": { "type": "apiKey", "in": "header", "name": "X-API-Key" }
+        }
+      }
+    }"##,
+    )
+    .unwrap();
+
+    let path_item = open_api.paths.paths["/users"].as_item().unwrap();
+    let operation = path_item.get.as_ref().unwrap();
+
+    assert_eq!(
+      reachable_components(&open_api, &[(path_item, operation)]),
+      BTreeSet::from(
+        [
+          ("responses", "Users"),
+          ("schemas", "Bowl"),
+          ("schemas", "Cat"),
+          ("schemas", "Dog"),
+          ("schemas", "Pet"),
+          ("schemas", "Toy"),
+          ("schemas", "User"),
+          ("securitySchemes", "apiKey"),
+        ]
+        .map(|(section, name)| (section.to_string(), name.to_string()))
+      )
+    );
+  }
+}
@@ -0,0 +1,421 @@
+use crate::diagnostics::Diagnostic;
+use crate::open_api_to_typescript::{
+  convert_open_api, default_is_success, is_error_status, is_success_status, to_pascal_case,
+  OpenApiOptions, OpenApiPath,
+};
+
+use openapiv3::{OpenAPI, Operation};
+
+/// Text of a comment line, `*/` would end the comment early.
+fn comment_text(text: &str) -> String {
+  text.replace("*/", "*\\/")
+}
+
+pub(crate) fn banner(open_api: &OpenAPI) -> String {
+  format!(
+    "/**\n * {}\n * Version: {}\n *\n * This file was auto-generated by schematype, do not edit it manually.\n */",
+    comment_text(&open_api.info.title),
+    comment_text(&open_api.info.version)
+  )
+}
+
+fn operation_comment(path: &OpenApiPath) -> String {
+  let lines: Vec<String> = [
+    path.summary.as_deref().map(comment_text),
+    Some(format!("{} {}", path.method.to_uppercase(), path.path)),
+    path.deprecated.then(|| "@deprecated".to_string()),
+  ]
+  .into_iter()
+  .flatten()
+  .collect();
+
+  if lines.len() == 1 {
+    format!("/** {} */", lines[0])
+  } else {
+    format!(
+      "/**\n{}\n */",
+      lines
+        .iter()
+        .map(|line| format!(" * {}", line))
+        .collect::<Vec<String>>()
+        .join("\n")
+    )
+  }
+}
+
+fn operation_types(path: &OpenApiPath, operation: &Operation, statement_end: &str) -> Vec<String> {
+  let name = to_pascal_case(&path.name);
+  let export_type = |suffix: &str, ts_type: &str| {
+    format!(
+      "export type {}{} = {}{}",
+      name, suffix, ts_type, statement_end
+    )
+  };
+
+  let mut types = vec![operation_comment(path)];
+
+  types.extend(
+    [
+      ("Params", &path.path_parameters),
+      ("Query", &path.query_parameters),
+      ("Headers", &path.header_parameters),
+      ("Cookies", &path.cookie_parameters),
+      ("Body", &path.request_body),
+    ]
+    .into_iter()
+    .filter_map(|(suffix, ts_type)| ts_type.as_ref().map(|ts_type| export_type(suffix, ts_type))),
+  );
+
+  let default_is_success = default_is_success(&operation.responses);
+  let mut success_responses: Vec<String> = vec![];
+  let mut error_responses: Vec<String> = vec![];
+
+  for (status_code, ts_type) in &path.responses {
+    let suffix = format!("Response{}", to_pascal_case(status_code));
+
+    if is_success_status(status_code, default_is_success) {
+      success_responses.push(format!("{}{}", name, suffix));
+    } else if is_error_status(status_code, default_is_success) {
+      error_responses.push(format!("{}{}", name, suffix));
+    }
+
+    types.push(export_type(&suffix, ts_type));
+  }
+
+  for (status_code, ts_type) in &path.response_headers {
+    let suffix = format!("Response{}Headers", to_pascal_case(status_code));
+
+    types.push(export_type(&suffix, ts_type));
+  }
+
+  let response = if success_responses.is_empty() {
+    "void".to_string()
+  } else {
+    success_responses.join(" | ")
+  };
+
+  types.push(export_type("Response", &response));
+
+  if !error_responses.is_empty() {
+    types.push(export_type("Error", &error_responses.join(" | ")));
+  }
+
+  types
+}
+
+/// Complete TypeScript module for an OpenAPI document: components and per-operation types, with
+/// the diagnostics of the conversion.
+pub fn open_api_to_typescript_module(
+  open_api: &OpenAPI,
+  document_diagnostics: Vec<Diagnostic>,
+  options: Option<OpenApiOptions>,
+) -> (String, Vec<Diagnostic>) {
+  let options = options.unwrap_or_default();
+  let statement_end = options.format.clone().unwrap_or_default().statement_end();
+  let converted = convert_open_api(open_api, document_diagnostics, &options);
+
+  let mut sections = vec![banner(open_api)];
+
+  if !converted.components.is_empty() {
+    sections.push(
+      converted
+        .components
+        .iter()
+        .map(|component| {
+          let type_guard = component
+            .type_guard
+            .as_ref()
+            .map(|type_guard| format!("\n\n{}", type_guard))
+            .unwrap_or_default();
+
+          format!(
+            "export type {} = {}{}{}",
+            component.name, component.ts_type, statement_end, type_guard
+          )
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n"),
+    );
+  }
+
+  sections.extend(
+    converted
+      .operations
+      .iter()
+      .map(|(path, operation)| operation_types(path, operation, statement_end).join("\n")),
+  );
+
+  (
+    format!("{}\n", sections.join("\n\n")),
+    converted.diagnostics,
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::diagnostics::ensure_lossless;
+
+  #[test]
+  fn test_open_api_to_module() {
+    let openapi: OpenAPI = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Pet Store", "version": "2.1.0" },
+      "paths": {
+        "/pets/{petId}": {
+          "get": {
+            "operationId": "getPet",
+            "parameters": [
+              { "in": "path", "name": "petId", "required": true, "schema": { "type": "string" } },
+              { "in": "query", "name": "expand", "schema": { "type": "boolean" } }
+            ],
+            "responses": {
+              "200": {
+                "description": "Pet",
+                "content": {
+                  "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } }
+                }
+              },
+              "404": {
+                "description": "Not found",
+                "content": {
+                  "application/json": {
+                    "schema": { "type": "object", "properties": { "message": { "type": "string" } } }
+                  }
+                }
+              }
+            }
+          },
+          "delete": {
+            "summary": "Delete a pet",
+            "deprecated": true,
+            "responses": { "204": { "description": "Deleted" } }
+          }
+        },
+        "/pets": {
+          "post": {
+            "operationId": "create-pet",
+            "requestBody": {
+              "content": {
+                "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } }
+              }
+            },
+            "responses": {
+              "201": {
+                "description": "Created",
+                "content": {
+                  "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } }
+                }
+              }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "Pet": {
+            "type": "object",
+            "properties": { "id": { "type": "string" }, "name": { "type": "string" } },
+            "required": ["id"]
+          }
+        }
+      }
+    }"##,
+    )
+    .unwrap();
+
+    let expected = r##"/**
+ * Pet Store
+ * Version: 2.1.0
+ *
+ * This file was auto-generated by schematype, do not edit it manually.
+ */
+
+export type Pet = {
+  id: string;
+  name?: string;
+};
+
+/** GET /pets/{petId} */
+export type GetPetParams = {
+  petId: string;
+};
+export type GetPetQuery = {
+  expand?: boolean;
+};
+export type GetPetResponse200 = Pet;
+export type GetPetResponse404 = {
+  message?: string;
+};
+export type GetPetResponse = GetPetResponse200;
+export type GetPetError = GetPetResponse404;
+
+/**
+ * Delete a pet
+ * DELETE /pets/{petId}
+ * @deprecated
+ */
+export type DeletePetsByPetIdResponse = void;
+
+/** POST /pets */
+export type CreatePetBody = Pet;
+export type CreatePetResponse201 = Pet;
+export type CreatePetResponse = CreatePetResponse201;
+"##;
+
+    assert_eq!(
+      open_api_to_typescript_module(&openapi, vec![], None).0,
+      expected
+    );
+  }
+
+  #[test]
+  fn test_escaped_banner_and_component_names() {
+    let openapi: OpenAPI = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Users */ API", "version": "1.0.0" },
+      "paths": {},
+      "components": {
+        "schemas": {
+          "User-Profile": { "type": "object", "properties": { "id": { "type": "string" } } },
+          "2fa.Settings": { "type": "boolean" },
+          "Team": {
+            "type": "object",
+            "properties": {
+              "owner": { "$ref": "#/components/schemas/User-Profile" },
+              "twoFactor": { "$ref": "#/components/schemas/2fa.Settings" }
+            }
+          }
+        }
+      }
+    }"##,
+    )
+    .unwrap();
+
+    let expected = r##"/**
+ * Users *\/ API
+ * Version: 1.0.0
+ *
+ * This file was auto-generated by schematype, do not edit it manually.
+ */
+
+export type User_Profile = {
+  id?: string;
+};
+
+export type _2fa_Settings = boolean;
+
+export type Team = {
+  owner?: User_Profile;
+  twoFactor?: _2fa_Settings;
+};
+"##;
+
+    assert_eq!(
+      open_api_to_typescript_module(&openapi, vec![], None).0,
+      expected
+    );
+  }
+
+  #[test]
+  fn test_strict_module() {
+    let openapi: OpenAPI = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {
+        "/files": {
+          "post": {
+            "parameters": [
+              {
+                "in": "query",
+                "name": "filter",
+                "content": { "application/json": { "schema": { "type": "object" } } }
+              }
+            ],
+            "requestBody": {
+              "content": { "application/xml": {} }
+            },
+            "responses": {
+              "200": {
+                "description": "File",
+                "content": {
+                  "application/json": { "schema": { "$ref": "#/components/schemas/File" } }
+                }
+              }
+            }
+          }
+        }
+      }
+    }"##,
+    )
+    .unwrap();
+
+    let (_, diagnostics) = open_api_to_typescript_module(&openapi, vec![], None);
+    let error = ensure_lossless(&diagnostics).unwrap_err();
+    let codes: Vec<&str> = error
+      .violations
+      .iter()
+      .map(|violation| violation.code.as_str())
+      .collect();
+
+    assert_eq!(
+      codes,
+      vec![
+        "unsupported-media-type",
+        "unsupported-parameter-content",
+        "unresolved-reference"
+      ]
+    );
+  }
+
+  #[test]
+  fn test_operation_type_collision() {
+    let openapi: OpenAPI = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {
+        "/users/{id}": {
+          "get": {
+            "operationId": "getUser",
+            "responses": {
+              "200": {
+                "description": "User",
+                "content": {
+                  "application/json": { "schema": { "$ref": "#/components/schemas/GetUserResponse" } }
+                }
+              }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "GetUserResponse": { "type": "object", "properties": { "id": { "type": "string" } } }
+        }
+      }
+    }"##,
+    )
+    .unwrap();
+
+    let (module, diagnostics) = open_api_to_typescript_module(&openapi, vec![], None);
+
+    assert_eq!(module.matches("export type GetUserResponse =").count(), 1);
+    assert!(module.contains("export type GetUser2Response200 = GetUserResponse;"));
+    assert!(module.contains("export type GetUser2Response = GetUser2Response200;"));
+    assert_eq!(
+      diagnostics
+        .iter()
+        .map(|d| (d.pointer.as_str(), d.code.as_str(), d.message.as_str()))
+        .collect::<Vec<_>>(),
+      vec![(
+        "#/paths/~1users~1{id}/get",
+        "type-name-collision",
+        "`GetUserResponse` is already a component, the operation is renamed `getUser2`"
+      )]
+    );
+  }
+}
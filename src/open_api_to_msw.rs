@@ -0,0 +1,455 @@
+use crate::diagnostics::Diagnostic;
+use crate::json_schema_mock::MockOptions;
+use crate::json_schema_to_typescript::FormatOptions;
+use crate::open_api_to_module::banner;
+use crate::open_api_to_typescript::{
+  convert_open_api, default_is_success, is_error_status, is_success_status, status_responses,
+  to_pascal_case, OpenApiOptions, OpenApiPath,
+};
+
+use napi_derive::napi;
+use openapiv3::{OpenAPI, Operation};
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+use serde_json::Value;
+
+#[derive(Debug, Default, Clone)]
+#[napi(object)]
+pub struct MswOptions {
+  /// Module generated by `openApiToModule` with the same options, default to `./types`
+  pub types_module: Option<String>,
+  /// Prefix of the handler paths, like `https://api.example.com` or `*` for any origin
+  pub base_url: Option<String>,
+}
+
+/// `/users/{id}` becomes `/users/:id`, escaping the characters MSW would read as patterns, or as
+/// the rest of a parameter name, like `.json` in `/files/{name}.json`.
+fn msw_path(path: &str) -> String {
+  let mut msw_path = String::new();
+  let mut is_after_parameter = false;
+
+  for c in path.chars() {
+    match c {
+      '{' => msw_path.push(':'),
+      '}' => {
+        is_after_parameter = true;
+        continue;
+      }
+      ':' | '*' | '?' | '+' | '(' | ')' | '.' | '\\' => {
+        msw_path.push('\\');
+        msw_path.push(c);
+      }
+      c if is_after_parameter && (c.is_alphanumeric() || c == '_') => {
+        msw_path.push('\\');
+        msw_path.push(c);
+      }
+      c => msw_path.push(c),
+    }
+    is_after_parameter = false;
+  }
+
+  msw_path
+}
+
+/// `2XX` is `200`, and `default` is `200` when it describes the success, `500` otherwise.
+fn status_number(status_code: &str, default_is_success: bool) -> u16 {
+  match status_code.parse::<u16>() {
+    Ok(status) => status,
+    Err(_) if status_code == "default" && default_is_success => 200,
+    Err(_) if status_code == "default" => 500,
+    Err(_) => status_code[..1].parse::<u16>().unwrap_or(2) * 100,
+  }
+}
+
+/// The first success response, or the first declared one.
+fn default_status(operation: &Operation) -> Option<String> {
+  let status_codes: Vec<String> = status_responses(&operation.responses)
+    .into_iter()
+    .map(|(status_code, _)| status_code)
+    .collect();
+
+  status_codes
+    .iter()
+    .find(|status_code| is_success_status(status_code, default_is_success(&operation.responses)))
+    .or(status_codes.first())
+    .cloned()
+}
+
+fn pretty_json(value: &Value, format: &FormatOptions, depth: usize) -> String {
+  let indentation = format.indentation(1);
+  let mut bytes = vec![];
+  let mut serializer = serde_json::Serializer::with_formatter(
+    &mut bytes,
+    PrettyFormatter::with_indent(indentation.as_bytes()),
+  );
+
+  match value.serialize(&mut serializer) {
+    Ok(()) => String::from_utf8_lossy(&bytes)
+      .lines()
+      .collect::<Vec<&str>>()
+      .join(&format!("\n{}", format.indentation(depth))),
+    Err(_) => "null".to_string(),
+  }
+}
+
+/// `{Name}Resolver` type and `{name}Handler` function, defaulting to the mock response.
+fn operation_handler(
+  path: &OpenApiPath,
+  operation: &Operation,
+  msw_options: &MswOptions,
+  format: &FormatOptions,
+) -> String {
+  let name = to_pascal_case(&path.name);
+  let statement_end = format.statement_end();
+  let trailing_comma = if format.trailing_comma.unwrap_or(true) {
+    ","
+  } else {
+    ""
+  };
+
+  let params = match &path.path_parameters {
+    Some(_) => format!("Record<keyof Api.{}Params, string>", name),
+    None => "never".to_string(),
+  };
+  let body = match &path.request_body {
+    Some(_) => format!("Api.{}Body", name),
+    None => "never".to_string(),
+  };
+  let default_is_success = default_is_success(&operation.responses);
+  let mut response_types: Vec<String> = vec![];
+
+  if path
+    .responses
+    .keys()
+    .any(|status| is_success_status(status, default_is_success))
+  {
+    response_types.push(format!("Api.{}Response", name));
+  }
+
+  if path
+    .responses
+    .keys()
+    .any(|status| is_error_status(status, default_is_success))
+  {
+    response_types.push(format!("Api.{}Error", name));
+  }
+
+  let response = match response_types.is_empty() {
+    true => "undefined".to_string(),
+    false => response_types.join(" | "),
+  };
+
+  let status_code = default_status(operation).unwrap_or_else(|| "200".to_string());
+  let status = status_number(&status_code, default_is_success);
+  let default_resolver = match path.mock_responses.get(&status_code) {
+    Some(mock) => {
+      let body = pretty_json(&serde_json::from_str(mock).unwrap_or_default(), format, 2);
+
+      // Responses with examples but no schema have no type to satisfy
+      match path.responses.contains_key(&status_code) {
+        true => format!(
+          "HttpResponse.json({} satisfies Api.{}Response{}, {{ status: {} }})",
+          body,
+          name,
+          to_pascal_case(&status_code),
+          status
+        ),
+        false => format!("HttpResponse.json({}, {{ status: {} }})", body, status),
+      }
+    }
+    None => format!("new HttpResponse(null, {{ status: {} }})", status),
+  };
+
+  let url = format!(
+    "{}{}",
+    msw_options.base_url.as_deref().unwrap_or_default(),
+    msw_path(&path.path)
+  );
+
+  [
+    format!(
+      "/** {} {} */",
+      path.method.to_uppercase(),
+      path.path
+    ),
+    format!(
+      "export type {}Resolver = HttpResponseResolver<{}, {}, {}>{}",
+      name, params, body, response, statement_end
+    ),
+    format!(
+      "export function {}Handler(\n{}resolver: {}Resolver = () =>\n{}{}{}\n) {{\n{}return http.{}({}, resolver){}\n}}",
+      path.name,
+      format.indentation(1),
+      name,
+      format.indentation(2),
+      default_resolver,
+      trailing_comma,
+      format.indentation(1),
+      path.method,
+      format.quote(&url),
+      statement_end
+    ),
+  ]
+  .join("\n")
+}
+
+/// Mock Service Worker handlers for the operations of an OpenAPI document, typed with the module of
+/// `open_api_to_typescript_module`, and responding with the examples or mock data by default.
+pub fn open_api_to_msw_module(
+  open_api: &OpenAPI,
+  document_diagnostics: Vec<Diagnostic>,
+  options: Option<OpenApiOptions>,
+  msw_options: Option<MswOptions>,
+) -> (String, Vec<Diagnostic>) {
+  let mut options = options.unwrap_or_default();
+  options.mocks.get_or_insert_with(MockOptions::default);
+  let msw_options = msw_options.unwrap_or_default();
+  let format = options.format.clone().unwrap_or_default();
+  let statement_end = format.statement_end();
+  let converted = convert_open_api(open_api, document_diagnostics, &options);
+
+  // MSW has no `trace` handler
+  let operations: Vec<&(OpenApiPath, &Operation)> = converted
+    .operations
+    .iter()
+    .filter(|(path, _)| path.method != "trace")
+    .collect();
+
+  let mut sections = vec![
+    banner(open_api),
+    [
+      format!(
+        "import {{ http, HttpResponse }} from {}{}",
+        format.quote("msw"),
+        statement_end
+      ),
+      format!(
+        "import type {{ HttpResponseResolver }} from {}{}",
+        format.quote("msw"),
+        statement_end
+      ),
+      format!(
+        "import type * as Api from {}{}",
+        format.quote(msw_options.types_module.as_deref().unwrap_or("./types")),
+        statement_end
+      ),
+    ]
+    .join("\n"),
+  ];
+
+  sections.extend(
+    operations
+      .iter()
+      .map(|(path, operation)| operation_handler(path, operation, &msw_options, &format)),
+  );
+
+  sections.push(format!(
+    "export const handlers = [\n{}\n]{}",
+    operations
+      .iter()
+      .map(|(path, _)| format!("{}{}Handler(),", format.indentation(1), path.name))
+      .collect::<Vec<String>>()
+      .join("\n"),
+    statement_end
+  ));
+
+  (
+    format!("{}\n", sections.join("\n\n")),
+    converted.diagnostics,
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_msw_path() {
+    assert_eq!(
+      msw_path("/users/{userId}/posts/{postId}"),
+      "/users/:userId/posts/:postId"
+    );
+    assert_eq!(msw_path("/files/{name}.json"), "/files/:name\\.json");
+    assert_eq!(
+      msw_path("/reports/{id}v2:export"),
+      "/reports/:id\\v2\\:export"
+    );
+    assert_eq!(status_number("201", false), 201);
+    assert_eq!(status_number("2XX", false), 200);
+    assert_eq!(status_number("default", false), 500);
+    assert_eq!(status_number("default", true), 200);
+  }
+
+  #[test]
+  fn test_open_api_to_msw_module() {
+    let openapi: OpenAPI = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Pet Store", "version": "2.1.0" },
+      "paths": {
+        "/pets/{petId}": {
+          "get": {
+            "operationId": "getPet",
+            "parameters": [
+              { "in": "path", "name": "petId", "required": true, "schema": { "type": "string" } }
+            ],
+            "responses": {
+              "200": {
+                "description": "Pet",
+                "content": {
+                  "application/json": {
+                    "schema": { "$ref": "#/components/schemas/Pet" },
+                    "example": { "id": "42", "name": "Rex" }
+                  }
+                }
+              },
+              "404": {
+                "description": "Not found",
+                "content": {
+                  "application/json": {
+                    "schema": { "type": "object", "properties": { "message": { "type": "string" } } }
+                  }
+                }
+              }
+            }
+          },
+          "delete": {
+            "responses": { "204": { "description": "Deleted" } }
+          }
+        },
+        "/pets": {
+          "post": {
+            "operationId": "createPet",
+            "requestBody": {
+              "content": {
+                "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } }
+              }
+            },
+            "responses": {
+              "201": {
+                "description": "Created",
+                "content": {
+                  "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } }
+                }
+              }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "Pet": {
+            "type": "object",
+            "properties": {
+              "id": { "type": "string", "enum": ["42"] },
+              "name": { "type": "string" }
+            },
+            "required": ["id"]
+          }
+        }
+      }
+    }"##,
+    )
+    .unwrap();
+
+    let expected = r##"/**
+ * Pet Store
+ * Version: 2.1.0
+ *
+ * This file was auto-generated by schematype, do not edit it manually.
+ */
+
+import { http, HttpResponse } from "msw";
+import type { HttpResponseResolver } from "msw";
+import type * as Api from "./api";
+
+/** GET /pets/{petId} */
+export type GetPetResolver = HttpResponseResolver<Record<keyof Api.GetPetParams, string>, never, Api.GetPetResponse | Api.GetPetError>;
+export function getPetHandler(
+  resolver: GetPetResolver = () =>
+    HttpResponse.json({
+      "id": "42",
+      "name": "Rex"
+    } satisfies Api.GetPetResponse200, { status: 200 }),
+) {
+  return http.get("*/pets/:petId", resolver);
+}
+
+/** DELETE /pets/{petId} */
+export type DeletePetsByPetIdResolver = HttpResponseResolver<never, never, undefined>;
+export function deletePetsByPetIdHandler(
+  resolver: DeletePetsByPetIdResolver = () =>
+    new HttpResponse(null, { status: 204 }),
+) {
+  return http.delete("*/pets/:petId", resolver);
+}
+
+/** POST /pets */
+export type CreatePetResolver = HttpResponseResolver<never, Api.CreatePetBody, Api.CreatePetResponse>;
+export function createPetHandler(
+  resolver: CreatePetResolver = () =>
+    HttpResponse.json({
+      "id": "42",
+      "name": "magna do"
+    } satisfies Api.CreatePetResponse201, { status: 201 }),
+) {
+  return http.post("*/pets", resolver);
+}
+
+export const handlers = [
+  getPetHandler(),
+  deletePetsByPetIdHandler(),
+  createPetHandler(),
+];
+"##;
+
+    assert_eq!(
+      open_api_to_msw_module(
+        &openapi,
+        vec![],
+        None,
+        Some(MswOptions {
+          types_module: Some("./api".to_string()),
+          base_url: Some("*".to_string()),
+        })
+      )
+      .0,
+      expected
+    );
+  }
+
+  #[test]
+  fn test_msw_example_without_schema() {
+    let openapi: OpenAPI = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Health", "version": "1.0.0" },
+      "paths": {
+        "/health": {
+          "get": {
+            "operationId": "getHealth",
+            "responses": {
+              "200": {
+                "description": "Healthy",
+                "content": {
+                  "application/json": { "example": { "status": "ok" } }
+                }
+              }
+            }
+          }
+        }
+      }
+    }"##,
+    )
+    .unwrap();
+
+    let (module, _) = open_api_to_msw_module(&openapi, vec![], None, None);
+
+    assert!(module.contains(
+      r#"HttpResponse.json({
+      "status": "ok"
+    }, { status: 200 })"#
+    ));
+    assert!(!module.contains("satisfies"));
+  }
+}
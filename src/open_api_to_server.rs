@@ -0,0 +1,405 @@
+use crate::diagnostics::Diagnostic;
+use crate::json_schema_to_typescript::FormatOptions;
+use crate::open_api_to_module::banner;
+use crate::open_api_to_typescript::{
+  convert_open_api, status_responses, to_pascal_case, OpenApiOptions, OpenApiPath,
+};
+
+use napi_derive::napi;
+use openapiv3::{OpenAPI, Operation};
+
+#[derive(Debug, Default, Clone)]
+#[napi(object)]
+pub struct ServerOptions {
+  /// Module generated by `openApiToModule` with the same options, default to `./types`
+  pub types_module: Option<String>,
+  /// Also generate a `{Name}Handler` Express request handler type for every operation
+  pub express: Option<bool>,
+}
+
+/// Reply key of a status code: `200` stays a number, `2XX` becomes `"2xx"` like Fastify, and
+/// `default` catches the other statuses.
+fn reply_key(status_code: &str, format: &FormatOptions) -> String {
+  match status_code.parse::<u16>() {
+    Ok(status) => status.to_string(),
+    Err(_) if status_code == "default" => status_code.to_string(),
+    Err(_) => format.quote(&status_code.to_ascii_lowercase()),
+  }
+}
+
+/// Fastify route generic of an operation, with `Params`, `Querystring`, `Body`, `Headers` and a
+/// `Reply` body by status code. Responses without a body reply `undefined`.
+fn operation_route(path: &OpenApiPath, operation: &Operation, format: &FormatOptions) -> String {
+  let name = to_pascal_case(&path.name);
+  let indentation = format.indentation(1);
+
+  let replies: Vec<(String, String)> = status_responses(&operation.responses)
+    .into_iter()
+    .map(|(status_code, _)| {
+      let ts_type = match path.responses.contains_key(&status_code) {
+        true => format!("Api.{}Response{}", name, to_pascal_case(&status_code)),
+        false => "undefined".to_string(),
+      };
+
+      (reply_key(&status_code, format), ts_type)
+    })
+    .collect();
+
+  let reply = match replies.is_empty() {
+    true => "unknown".to_string(),
+    false => format!(
+      "{{\n{}\n{}}}",
+      replies
+        .iter()
+        .enumerate()
+        .map(|(index, (key, ts_type))| format!(
+          "{}{}: {}{}",
+          format.indentation(2),
+          key,
+          ts_type,
+          format.member_end(index + 1 == replies.len())
+        ))
+        .collect::<Vec<String>>()
+        .join("\n"),
+      indentation
+    ),
+  };
+
+  let mut members: Vec<(&str, String)> = [
+    ("Params", &path.path_parameters, "Params"),
+    ("Querystring", &path.query_parameters, "Query"),
+    ("Body", &path.request_body, "Body"),
+    ("Headers", &path.header_parameters, "Headers"),
+  ]
+  .into_iter()
+  .filter(|(_, ts_type, _)| ts_type.is_some())
+  .map(|(member, _, suffix)| (member, format!("Api.{}{}", name, suffix)))
+  .collect();
+
+  members.push(("Reply", reply));
+
+  format!(
+    "/** {} {} */\nexport interface {}Route {{\n{}\n}}",
+    path.method.to_uppercase(),
+    path.path,
+    name,
+    members
+      .iter()
+      .enumerate()
+      .map(|(index, (member, ts_type))| format!(
+        "{}{}: {}{}",
+        indentation,
+        member,
+        ts_type,
+        format.member_end(index + 1 == members.len())
+      ))
+      .collect::<Vec<String>>()
+      .join("\n")
+  )
+}
+
+/// `TypedResponse` and `RouteHandler` helpers, shared by the Express handler types. Reply keys map to
+/// status codes like Fastify's: `"4xx"` is every code from 400 to 499, and `default` any code.
+fn express_helpers(format: &FormatOptions) -> String {
+  let statement_end = format.statement_end();
+  let [indentation, indentation_2] = [format.indentation(1), format.indentation(2)];
+
+  [
+    format!(
+      "import type {{ NextFunction, Request, Response }} from {}{}",
+      format.quote("express"),
+      statement_end
+    ),
+    String::new(),
+    format!(
+      "type Digit = 0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9{}",
+      statement_end
+    ),
+    format!(
+      "type StringAsNumber<Value> = Value extends `${{infer Code extends number}}` ? Code : never{}",
+      statement_end
+    ),
+    String::new(),
+    "/** Status codes of a reply key, `\"4xx\"` is every code from 400 to 499 and `default` any code */".to_string(),
+    "export type ReplyStatus<Key> = Key extends number".to_string(),
+    format!("{}? Key", indentation),
+    format!(
+      "{}: Key extends `${{infer Class extends 1 | 2 | 3 | 4 | 5}}xx`",
+      indentation
+    ),
+    format!(
+      "{}? StringAsNumber<`${{Class}}${{Digit}}${{Digit}}`>",
+      indentation_2
+    ),
+    format!("{}: number{}", indentation_2, statement_end),
+    String::new(),
+    "/** Reply body of a status code, from its own key, then its class key like `\"4xx\"`, then `default` */".to_string(),
+    "export type ReplyBody<Reply, Status extends number> = Status extends keyof Reply".to_string(),
+    format!("{}? Reply[Status]", indentation),
+    format!(
+      "{}: `${{Status}}` extends `${{infer Class}}${{string}}`",
+      indentation
+    ),
+    format!("{}? `${{Class}}xx` extends keyof Reply", indentation_2),
+    format!(
+      "{}? Reply[`${{Class}}xx` & keyof Reply]",
+      format.indentation(3)
+    ),
+    format!(
+      "{}: Reply[{} & keyof Reply]",
+      format.indentation(3),
+      format.quote("default")
+    ),
+    format!("{}: never{}", indentation_2, statement_end),
+    String::new(),
+    "/** Express response whose `status` only accepts the declared status codes, and `json` and `send` their bodies */".to_string(),
+    format!(
+      "export type TypedResponse<Reply> = Omit<Response, {} | {} | {}> & {{",
+      format.quote("status"),
+      format.quote("json"),
+      format.quote("send")
+    ),
+    format!(
+      "{}status<Status extends ReplyStatus<keyof Reply>>(code: Status): {{",
+      indentation
+    ),
+    format!(
+      "{}json(body: ReplyBody<Reply, Status>): void{}",
+      indentation_2,
+      format.member_end(false)
+    ),
+    format!(
+      "{}send(body?: ReplyBody<Reply, Status>): void{}",
+      indentation_2,
+      format.member_end(true)
+    ),
+    format!("{}}}{}", indentation, format.member_end(true)),
+    format!("}}{}", statement_end),
+    String::new(),
+    "/** Express request handler of an operation */".to_string(),
+    "export type RouteHandler<Params, Query, Body, Reply> = (".to_string(),
+    format!(
+      "{}request: Request<Params, unknown, Body, Query>,",
+      indentation
+    ),
+    format!("{}response: TypedResponse<Reply>,", indentation),
+    format!("{}next: NextFunction,", indentation),
+    format!(") => void | Promise<void>{}", statement_end),
+  ]
+  .join("\n")
+}
+
+fn express_handler(path: &OpenApiPath, format: &FormatOptions) -> String {
+  let name = to_pascal_case(&path.name);
+  let route_member = |member: &str, ts_type: &Option<String>| match ts_type {
+    Some(_) => format!("{}Route[{}]", name, format.quote(member)),
+    None => "unknown".to_string(),
+  };
+
+  format!(
+    "export type {}Handler = RouteHandler<{}, {}, {}, {}Route[{}]>{}",
+    name,
+    route_member("Params", &path.path_parameters),
+    route_member("Querystring", &path.query_parameters),
+    route_member("Body", &path.request_body),
+    name,
+    format.quote("Reply"),
+    format.statement_end()
+  )
+}
+
+/// Server-side route types for the operations of an OpenAPI document, typed with the module of
+/// `open_api_to_typescript_module`: Fastify route generics, and Express request handlers on demand.
+pub fn open_api_to_server_module(
+  open_api: &OpenAPI,
+  document_diagnostics: Vec<Diagnostic>,
+  options: Option<OpenApiOptions>,
+  server_options: Option<ServerOptions>,
+) -> (String, Vec<Diagnostic>) {
+  let options = options.unwrap_or_default();
+  let server_options = server_options.unwrap_or_default();
+  let is_express = server_options.express.unwrap_or(false);
+  let format = options.format.clone().unwrap_or_default();
+  let converted = convert_open_api(open_api, document_diagnostics, &options);
+
+  let mut sections = vec![
+    banner(open_api),
+    format!(
+      "import type * as Api from {}{}",
+      format.quote(server_options.types_module.as_deref().unwrap_or("./types")),
+      format.statement_end()
+    ),
+  ];
+
+  if is_express {
+    sections.push(express_helpers(&format));
+  }
+
+  sections.extend(converted.operations.iter().map(|(path, operation)| {
+    let route = operation_route(path, operation, &format);
+
+    match is_express {
+      true => format!("{}\n{}", route, express_handler(path, &format)),
+      false => route,
+    }
+  }));
+
+  (
+    format!("{}\n", sections.join("\n\n")),
+    converted.diagnostics,
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_open_api_to_server_module() {
+    let openapi: OpenAPI = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Pet Store", "version": "2.1.0" },
+      "paths": {
+        "/pets/{petId}": {
+          "put": {
+            "operationId": "updatePet",
+            "parameters": [
+              { "in": "path", "name": "petId", "required": true, "schema": { "type": "string" } },
+              { "in": "query", "name": "notify", "schema": { "type": "boolean" } },
+              { "in": "header", "name": "x-request-id", "schema": { "type": "string" } }
+            ],
+            "requestBody": {
+              "content": {
+                "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } }
+              }
+            },
+            "responses": {
+              "200": {
+                "description": "Pet",
+                "content": {
+                  "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } }
+                }
+              },
+              "204": { "description": "Unchanged" },
+              "4XX": {
+                "description": "Client error",
+                "content": {
+                  "application/json": {
+                    "schema": { "type": "object", "properties": { "message": { "type": "string" } } }
+                  }
+                }
+              },
+              "default": { "description": "Error" }
+            }
+          }
+        },
+        "/pets": {
+          "get": {
+            "operationId": "listPets",
+            "responses": {
+              "200": {
+                "description": "Pets",
+                "content": {
+                  "application/json": {
+                    "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } }
+                  }
+                }
+              }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "Pet": { "type": "object", "properties": { "id": { "type": "string" } } }
+        }
+      }
+    }"##,
+    )
+    .unwrap();
+
+    let expected = r##"/**
+ * Pet Store
+ * Version: 2.1.0
+ *
+ * This file was auto-generated by schematype, do not edit it manually.
+ */
+
+import type * as Api from "./api";
+
+import type { NextFunction, Request, Response } from "express";
+
+type Digit = 0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9;
+type StringAsNumber<Value> = Value extends `${infer Code extends number}` ? Code : never;
+
+/** Status codes of a reply key, `"4xx"` is every code from 400 to 499 and `default` any code */
+export type ReplyStatus<Key> = Key extends number
+  ? Key
+  : Key extends `${infer Class extends 1 | 2 | 3 | 4 | 5}xx`
+    ? StringAsNumber<`${Class}${Digit}${Digit}`>
+    : number;
+
+/** Reply body of a status code, from its own key, then its class key like `"4xx"`, then `default` */
+export type ReplyBody<Reply, Status extends number> = Status extends keyof Reply
+  ? Reply[Status]
+  : `${Status}` extends `${infer Class}${string}`
+    ? `${Class}xx` extends keyof Reply
+      ? Reply[`${Class}xx` & keyof Reply]
+      : Reply["default" & keyof Reply]
+    : never;
+
+/** Express response whose `status` only accepts the declared status codes, and `json` and `send` their bodies */
+export type TypedResponse<Reply> = Omit<Response, "status" | "json" | "send"> & {
+  status<Status extends ReplyStatus<keyof Reply>>(code: Status): {
+    json(body: ReplyBody<Reply, Status>): void;
+    send(body?: ReplyBody<Reply, Status>): void;
+  };
+};
+
+/** Express request handler of an operation */
+export type RouteHandler<Params, Query, Body, Reply> = (
+  request: Request<Params, unknown, Body, Query>,
+  response: TypedResponse<Reply>,
+  next: NextFunction,
+) => void | Promise<void>;
+
+/** PUT /pets/{petId} */
+export interface UpdatePetRoute {
+  Params: Api.UpdatePetParams;
+  Querystring: Api.UpdatePetQuery;
+  Body: Api.UpdatePetBody;
+  Headers: Api.UpdatePetHeaders;
+  Reply: {
+    200: Api.UpdatePetResponse200;
+    204: undefined;
+    "4xx": Api.UpdatePetResponse4XX;
+    default: undefined;
+  };
+}
+export type UpdatePetHandler = RouteHandler<UpdatePetRoute["Params"], UpdatePetRoute["Querystring"], UpdatePetRoute["Body"], UpdatePetRoute["Reply"]>;
+
+/** GET /pets */
+export interface ListPetsRoute {
+  Reply: {
+    200: Api.ListPetsResponse200;
+  };
+}
+export type ListPetsHandler = RouteHandler<unknown, unknown, unknown, ListPetsRoute["Reply"]>;
+"##;
+
+    assert_eq!(
+      open_api_to_server_module(
+        &openapi,
+        vec![],
+        None,
+        Some(ServerOptions {
+          types_module: Some("./api".to_string()),
+          express: Some(true),
+        })
+      )
+      .0,
+      expected
+    );
+  }
+}
@@ -1,11 +1,23 @@
-use crate::json_schema_to_typescript::TypeInterface;
+use crate::json_schema_to_typescript::{
+  is_alphabetical_sort, normalize_schema, resolve_schema_reference,
+  schema_to_typescript_with_context, type_name, FormatOptions, SchemaContext, SchemaTypeOptions,
+  SortOrder,
+};
+
+use crate::diagnostics::{is_pointer_within, pointer_join, Diagnostic, Diagnostics};
+use crate::json_schema_mock::{derive_seed, MockOptions, Mocker};
+use crate::json_schema_validate::validate_value;
+use crate::open_api_filter::{reachable_components, FilterOptions};
 
+use indexmap::IndexMap;
 use napi_derive::napi;
 use openapiv3::{
-  OpenAPI, Operation, Parameter, ParameterData, ParameterSchemaOrContent, ReferenceOr, Schema,
+  Components, Content, ObjectType, OpenAPI, Operation, Parameter, ParameterData,
+  ParameterSchemaOrContent, PathItem, ReferenceOr, Response, Responses, Schema, SchemaData,
+  SchemaKind, SecurityScheme, StringType, Type,
 };
-use serde_json::json;
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 #[derive(Debug)]
 pub enum OpenApiMethod {
@@ -15,6 +27,28 @@ pub enum OpenApiMethod {
   Delete,
   Patch,
   Options,
+  Head,
+  Trace,
+}
+
+#[derive(Debug, Default)]
+#[napi(object)]
+pub struct OpenApiOptions {
+  pub merge_all_of: Option<bool>,
+  /// Default to `source`, applied to paths, methods, components, properties, status codes and
+  /// media types
+  pub sort: Option<SortOrder>,
+  pub format: Option<FormatOptions>,
+  /// Only keep the matching operations, and the components they use
+  pub filter: Option<FilterOptions>,
+  /// Remove the components no operation uses, default to true with a `filter` and false otherwise
+  pub tree_shake: Option<bool>,
+  /// Fail with every lossy conversion instead of degrading the types
+  pub strict: Option<bool>,
+  /// Generate an `is{Name}` type guard function for every schema component
+  pub type_guards: Option<bool>,
+  /// Generate a mock JSON body for every response of the operations
+  pub mocks: Option<MockOptions>,
 }
 
 #[derive(Debug)]
@@ -22,13 +56,45 @@ pub enum OpenApiMethod {
 pub struct OpenApiOutput {
   pub paths: Vec<OpenApiPath>,
   pub components: Vec<OpenApiComponent>,
+  /// Names of the components removed by `tree_shake`
+  pub removed_components: Vec<String>,
+  /// Warnings and degradations of the conversion
+  pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, PartialEq)]
+#[napi(string_enum = "camelCase")]
+pub enum ComponentKind {
+  Schema,
+  Response,
+  Parameter,
+  RequestBody,
+  Header,
+  SecurityScheme,
+}
+
+impl ComponentKind {
+  /// Suffix of a component name already taken, like `UserResponse`.
+  fn suffix(&self) -> &'static str {
+    match self {
+      ComponentKind::Schema => "Schema",
+      ComponentKind::Response => "Response",
+      ComponentKind::Parameter => "Parameter",
+      ComponentKind::RequestBody => "RequestBody",
+      ComponentKind::Header => "Header",
+      ComponentKind::SecurityScheme => "SecurityScheme",
+    }
+  }
 }
 
 #[derive(Debug)]
 #[napi(object)]
 pub struct OpenApiComponent {
   pub name: String,
+  pub kind: ComponentKind,
   pub ts_type: String,
+  /// `is{Name}` type guard function of schemas, with the `type_guards` option
+  pub type_guard: Option<String>,
 }
 
 #[derive(Debug)]
@@ -36,10 +102,34 @@ pub struct OpenApiComponent {
 pub struct OpenApiPath {
   pub path: String,
   pub method: String,
+  pub operation_id: Option<String>,
+  /// Unique camelCase identifier, from `operationId` or synthesized from the method and path
+  pub name: String,
+  pub summary: Option<String>,
+  pub description: Option<String>,
+  pub tags: Vec<String>,
+  pub deprecated: bool,
   pub query_parameters: Option<String>,
   pub path_parameters: Option<String>,
+  pub header_parameters: Option<String>,
+  pub cookie_parameters: Option<String>,
+  /// Type of the JSON request body, or of the first media type when there is no JSON one
   pub request_body: Option<String>,
-  pub responses: HashMap<String, String>,
+  /// Request body type by media type
+  pub request_body_content: IndexMap<String, String>,
+  /// Response type by status code (`200`, `2XX` or `default`), picked like `request_body`
+  pub responses: IndexMap<String, String>,
+  /// Union of the `2XX` response types, or of the `default` one when there is no `2XX` response
+  pub success_response: Option<String>,
+  /// Union of the `4XX`, `5XX` and `default` response types, `default` being the success when
+  /// there is no `2XX` response
+  pub error_response: Option<String>,
+  /// Response types by status code, then by media type
+  pub response_content: IndexMap<String, IndexMap<String, String>>,
+  /// Response headers type by status code
+  pub response_headers: IndexMap<String, String>,
+  /// JSON response body by status code, from the valid examples or generated, with the `mocks` option
+  pub mock_responses: IndexMap<String, String>,
 }
 
 impl OpenApiOutput {
@@ -51,255 +141,1503 @@ impl OpenApiOutput {
       OpenApiMethod::Delete => "delete",
       OpenApiMethod::Patch => "patch",
       OpenApiMethod::Options => "options",
+      OpenApiMethod::Head => "head",
+      OpenApiMethod::Trace => "trace",
     }
   }
 }
 
-fn extract_parameters(
-  parameters: &[&Parameter],
-  parameter_type: fn(&Parameter) -> Option<&ParameterData>,
-) -> (serde_json::Map<String, serde_json::Value>, Vec<String>) {
-  let props = parameters
+/// Responses by status code (`200`, `2XX`), followed by the `default` one.
+pub(crate) fn status_responses(responses: &Responses) -> Vec<(String, &ReferenceOr<Response>)> {
+  responses
+    .responses
     .iter()
-    .filter_map(|parameter| {
-      parameter_type(parameter).and_then(|parameter_data| {
-        if let ParameterSchemaOrContent::Schema(schema) = &parameter_data.format {
-          serde_json::to_value(schema)
-            .ok()
-            .map(|schema_json| (parameter_data.name.clone(), schema_json))
-        } else {
-          None
-        }
-      })
+    .map(|(status_code, response)| (status_code.to_string(), response))
+    .chain(
+      responses
+        .default
+        .iter()
+        .map(|response| ("default".to_string(), response)),
+    )
+    .collect()
+}
+
+/// `default` describes the success of the operations declaring no `2XX` response, and the errors
+/// otherwise.
+pub(crate) fn default_is_success(responses: &Responses) -> bool {
+  !responses
+    .responses
+    .keys()
+    .any(|status_code| status_code.to_string().starts_with('2'))
+}
+
+pub(crate) fn is_success_status(status_code: &str, default_is_success: bool) -> bool {
+  status_code.starts_with('2') || (status_code == "default" && default_is_success)
+}
+
+pub(crate) fn is_error_status(status_code: &str, default_is_success: bool) -> bool {
+  status_code.starts_with('4')
+    || status_code.starts_with('5')
+    || (status_code == "default" && !default_is_success)
+}
+
+fn responses_union(
+  responses: &IndexMap<String, String>,
+  is_status: impl Fn(&str) -> bool,
+) -> Option<String> {
+  let mut ts_types: Vec<&str> = vec![];
+
+  for (_, ts_type) in responses
+    .iter()
+    .filter(|(status_code, _)| is_status(status_code))
+  {
+    if !ts_types.contains(&ts_type.as_str()) {
+      ts_types.push(ts_type);
+    }
+  }
+
+  (!ts_types.is_empty()).then(|| ts_types.join(" | "))
+}
+
+/// Leading underscores are kept, they keep names starting with a digit valid identifiers.
+pub(crate) fn to_pascal_case(value: &str) -> String {
+  let underscores = &value[..value.len() - value.trim_start_matches('_').len()];
+
+  let pascal_case: String = value
+    .split(|c: char| !c.is_ascii_alphanumeric())
+    .filter(|part| !part.is_empty())
+    .map(|part| {
+      let mut chars = part.chars();
+      match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+      }
     })
     .collect();
 
-  let required = parameters
+  match pascal_case.is_empty() {
+    true => pascal_case,
+    false => format!("{}{}", underscores, pascal_case),
+  }
+}
+
+fn to_camel_case(value: &str) -> String {
+  let pascal_case = to_pascal_case(value);
+  let name = pascal_case.trim_start_matches('_');
+  let underscores = match &pascal_case[..pascal_case.len() - name.len()] {
+    "" if name.starts_with(|c: char| c.is_ascii_digit()) => "_",
+    underscores => underscores,
+  };
+  let mut chars = name.chars();
+
+  match chars.next() {
+    Some(first) => format!(
+      "{}{}{}",
+      underscores,
+      first.to_ascii_lowercase(),
+      chars.as_str()
+    ),
+    None => String::new(),
+  }
+}
+
+/// `getUsersById` for `GET /users/{id}`, used when an operation has no `operationId`.
+fn synthesize_operation_name(method: &str, path: &str) -> String {
+  let segments: String = path
+    .split('/')
+    .filter(|segment| !segment.is_empty())
+    .map(
+      |segment| match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+        Some(parameter) => format!("By{}", to_pascal_case(parameter)),
+        None => to_pascal_case(segment),
+      },
+    )
+    .collect();
+
+  to_camel_case(&format!("{} {}", method, segments))
+}
+
+fn operation_name(method: &str, path: &str, operation: &Operation) -> String {
+  operation
+    .operation_id
+    .as_deref()
+    .map(to_camel_case)
+    .filter(|name| !name.is_empty())
+    .unwrap_or_else(|| synthesize_operation_name(method, path))
+}
+
+/// Suffixes of the types `openApiToModule` declares for an operation, like `Params` or
+/// `Response200`.
+fn operation_type_suffixes(path: &OpenApiPath) -> Vec<String> {
+  [
+    ("Params", &path.path_parameters),
+    ("Query", &path.query_parameters),
+    ("Headers", &path.header_parameters),
+    ("Cookies", &path.cookie_parameters),
+    ("Body", &path.request_body),
+  ]
+  .into_iter()
+  .filter(|(_, ts_type)| ts_type.is_some())
+  .map(|(suffix, _)| suffix.to_string())
+  .chain(
+    path
+      .responses
+      .keys()
+      .map(|status_code| format!("Response{}", to_pascal_case(status_code))),
+  )
+  .chain(
+    path
+      .response_headers
+      .keys()
+      .map(|status_code| format!("Response{}Headers", to_pascal_case(status_code))),
+  )
+  .chain(["Response".to_string(), "Error".to_string()])
+  .collect()
+}
+
+/// First operation type named like a component, which would declare the same type twice.
+fn colliding_type_name(path: &OpenApiPath, component_names: &[&str]) -> Option<String> {
+  let name = to_pascal_case(&path.name);
+
+  operation_type_suffixes(path)
+    .into_iter()
+    .map(|suffix| format!("{}{}", name, suffix))
+    .find(|type_name| component_names.contains(&type_name.as_str()))
+}
+
+/// Suffixes duplicated operation names with a counter. Names from an `operationId` are reserved
+/// first, so synthesized names get the suffix, then the first occurrence is kept untouched. Names
+/// whose types collide with a component, like `getUser` and a `GetUserResponse` schema, are
+/// suffixed too, with a warning.
+fn deduplicate_operation_names(
+  operations: &mut [(OpenApiPath, &Operation)],
+  component_names: &[&str],
+  context: &SchemaContext,
+) {
+  let is_explicit = |path: &OpenApiPath| {
+    path
+      .operation_id
+      .as_deref()
+      .is_some_and(|operation_id| !to_camel_case(operation_id).is_empty())
+  };
+  let mut seen: HashSet<String> = HashSet::new();
+
+  for explicit in [true, false] {
+    for (path, _) in operations
+      .iter_mut()
+      .filter(|(path, _)| is_explicit(path) == explicit)
+    {
+      let base = path.name.clone();
+      let collision = colliding_type_name(path, component_names);
+      let mut index = 1;
+
+      while seen.contains(&path.name) || colliding_type_name(path, component_names).is_some() {
+        index += 1;
+        path.name = format!("{}{}", base, index);
+      }
+
+      if let Some(type_name) = collision {
+        let pointer = context.child_pointer(&["paths", &path.path, &path.method]);
+        context.at(&pointer).warning(
+          "type-name-collision",
+          format!(
+            "`{}` is already a component, the operation is renamed `{}`",
+            type_name, path.name
+          ),
+        );
+      }
+
+      seen.insert(path.name.clone());
+    }
+  }
+}
+
+pub(crate) fn parameter_location(parameter: &Parameter) -> &'static str {
+  match parameter {
+    Parameter::Query { .. } => "query",
+    Parameter::Header { .. } => "header",
+    Parameter::Path { .. } => "path",
+    Parameter::Cookie { .. } => "cookie",
+  }
+}
+
+/// Follows references to a components section, `None` when a reference can't be resolved. Reports
+/// unresolved references at `pointer`.
+pub(crate) fn resolve_component_reference<'a, T>(
+  item: &'a ReferenceOr<T>,
+  section: &str,
+  get: impl Fn(&'a Components, &str) -> Option<&'a ReferenceOr<T>>,
+  pointer: &str,
+  context: &SchemaContext<'a>,
+) -> Option<&'a T> {
+  let prefix = format!("#/components/{}/", section);
+  let mut visited: Vec<&str> = vec![];
+  let mut current = item;
+
+  loop {
+    match current {
+      ReferenceOr::Item(item) => return Some(item),
+      ReferenceOr::Reference { reference } => {
+        let resolved = (!visited.contains(&reference.as_str()))
+          .then(|| reference.strip_prefix(&prefix))
+          .flatten()
+          .and_then(|name| get(context.components?, name));
+
+        match resolved {
+          Some(resolved) => {
+            visited.push(reference);
+            current = resolved;
+          }
+          None => {
+            context.at(pointer).warning(
+              "unresolved-reference",
+              format!("`{}` can't be resolved, it is ignored", reference),
+            );
+            return None;
+          }
+        }
+      }
+    }
+  }
+}
+
+/// Pointer of the definition of an item, its reference when it is one.
+fn definition_pointer<T>(item: &ReferenceOr<T>, pointer: String) -> String {
+  match item {
+    ReferenceOr::Reference { reference } => reference.clone(),
+    ReferenceOr::Item(_) => pointer,
+  }
+}
+
+/// Path item parameters followed by the operation ones, an operation parameter overrides the path
+/// item parameter with the same name and location, header names being case-insensitive. Each
+/// parameter comes with the pointer of its definition.
+pub(crate) fn merge_parameters<'a>(
+  path_item_parameters: &'a [ReferenceOr<Parameter>],
+  path_item_context: &SchemaContext<'a>,
+  operation_parameters: &'a [ReferenceOr<Parameter>],
+  context: &SchemaContext<'a>,
+) -> Vec<(String, &'a Parameter)> {
+  let mut parameters: Vec<(String, &Parameter)> = vec![];
+
+  let all_parameters = path_item_parameters
     .iter()
-    .filter_map(|parameter| {
-      parameter_type(parameter).and_then(|parameter_data| {
-        if parameter_data.required {
-          Some(parameter_data.name.clone())
-        } else {
-          None
+    .enumerate()
+    .map(|(index, parameter)| (index, parameter, path_item_context))
+    .chain(
+      operation_parameters
+        .iter()
+        .enumerate()
+        .map(|(index, parameter)| (index, parameter, context)),
+    );
+
+  for (pointer, parameter) in all_parameters.filter_map(|(index, reference, context)| {
+    let pointer = pointer_join(context.pointer, &["parameters", &index.to_string()]);
+    let parameter = resolve_component_reference(
+      reference,
+      "parameters",
+      |components, name| components.parameters.get(name),
+      &pointer,
+      context,
+    )?;
+    Some((definition_pointer(reference, pointer), parameter))
+  }) {
+    let same_parameter = parameters.iter().position(|(_, existing)| {
+      let (name, existing_name) = (
+        &parameter.parameter_data_ref().name,
+        &existing.parameter_data_ref().name,
+      );
+
+      parameter_location(existing) == parameter_location(parameter)
+        && match parameter {
+          // HTTP header names are case-insensitive
+          Parameter::Header { .. } => name.eq_ignore_ascii_case(existing_name),
+          _ => name == existing_name,
         }
-      })
-    })
-    .collect();
+    });
+
+    match same_parameter {
+      Some(index) => parameters[index] = (pointer, parameter),
+      None => parameters.push((pointer, parameter)),
+    }
+  }
 
-  (props, required)
+  parameters
 }
 
 fn generate_parameters_ts_type(
-  parameters: &[&Parameter],
+  parameters: &[(String, &Parameter)],
   parameter_type: fn(&Parameter) -> Option<&ParameterData>,
+  schema_options: &SchemaTypeOptions,
+  context: &SchemaContext,
 ) -> Option<String> {
-  let has_parameters = parameters.iter().any(|p| parameter_type(p).is_some());
+  let has_parameters = parameters.iter().any(|(_, p)| parameter_type(p).is_some());
 
   if !has_parameters {
     return None;
   }
 
-  let (props, required) = extract_parameters(parameters, parameter_type);
+  let mut properties: Vec<(String, String, ReferenceOr<Schema>)> = vec![];
+  let mut required: Vec<String> = vec![];
 
-  let schema_json = json!({
-      "type": "object",
-      "properties": props,
-      "required": required
-  });
+  for (pointer, parameter_data) in parameters
+    .iter()
+    .filter_map(|(pointer, p)| Some((pointer, parameter_type(p)?)))
+  {
+    let ParameterSchemaOrContent::Schema(schema) = &parameter_data.format else {
+      context.at(&pointer_join(pointer, &["content"])).warning(
+        "unsupported-parameter-content",
+        format!(
+          "Parameter `{}` with a `content` isn't supported, it is ignored",
+          parameter_data.name
+        ),
+      );
+      continue;
+    };
 
-  if let Ok(schema) = serde_json::from_value(schema_json) {
-    Some(
-      crate::json_schema_to_typescript::schema_to_typescript(ReferenceOr::Item(schema), None)
-        .to_string(),
-    )
-  } else {
-    None
+    if parameter_data.required {
+      required.push(parameter_data.name.clone());
+    }
+    properties.push((
+      parameter_data.name.clone(),
+      pointer_join(pointer, &["schema"]),
+      schema.clone(),
+    ));
   }
+
+  Some(object_ts_type(
+    properties,
+    required,
+    schema_options,
+    context,
+  ))
 }
 
-fn get_open_api_path(path: &str, method: OpenApiMethod, operation: &Operation) -> OpenApiPath {
-  let request_body: Option<ReferenceOr<Schema>> = match &operation.request_body {
-    Some(request_body) => match request_body {
-      ReferenceOr::Item(request_body) => match request_body.content.get("application/json") {
-        Some(content) => content.schema.clone(),
-        None => None,
-      },
-      ReferenceOr::Reference { reference } => {
-        panic!("Reference not implemented for path: {}", reference);
-      }
-    },
-    None => None,
+/// Object type of named schemas, in their declaration order. The diagnostics of each schema are
+/// reported at its own pointer rather than under the object.
+fn object_ts_type(
+  properties: Vec<(String, String, ReferenceOr<Schema>)>,
+  required: Vec<String>,
+  schema_options: &SchemaTypeOptions,
+  context: &SchemaContext,
+) -> String {
+  let mut pointers: Vec<(String, String)> = vec![];
+  let mut object_properties = IndexMap::new();
+
+  for (name, pointer, schema) in properties {
+    pointers.push((
+      pointer_join(context.pointer, &["properties", &name]),
+      pointer,
+    ));
+    let schema = match schema {
+      ReferenceOr::Item(schema) => ReferenceOr::boxed_item(schema),
+      ReferenceOr::Reference { reference } => ReferenceOr::Reference { reference },
+    };
+    object_properties.insert(name, schema);
+  }
+
+  let schema = Schema {
+    schema_data: SchemaData::default(),
+    schema_kind: SchemaKind::Type(Type::Object(ObjectType {
+      properties: object_properties,
+      required,
+      ..Default::default()
+    })),
   };
 
-  let request_body_type: Option<TypeInterface> = request_body.map(|request_body| {
-    crate::json_schema_to_typescript::schema_to_typescript(request_body.clone(), None)
-  });
+  let diagnostics = Diagnostics::default();
+  let object_context = SchemaContext {
+    diagnostics: context.diagnostics.map(|_| &diagnostics),
+    ..*context
+  };
 
-  let parameters: Vec<&Parameter> = operation
-    .parameters
-    .iter()
-    .filter_map(|parameter| match parameter {
-      ReferenceOr::Item(parameter) => Some(parameter),
-      ReferenceOr::Reference { reference } => {
-        eprintln!("Warning: Reference not implemented for path: {}", reference);
-        None
+  let ts_type = schema_to_typescript_with_context(
+    ReferenceOr::Item(schema),
+    Some(schema_options.clone()),
+    &object_context,
+  )
+  .to_string();
+
+  if let Some(context_diagnostics) = context.diagnostics {
+    context_diagnostics.extend(diagnostics.into_vec().into_iter().map(|mut diagnostic| {
+      let property = pointers
+        .iter()
+        .find(|(object_pointer, _)| is_pointer_within(&diagnostic.pointer, object_pointer));
+      if let Some((object_pointer, pointer)) = property {
+        diagnostic.pointer = format!("{}{}", pointer, &diagnostic.pointer[object_pointer.len()..]);
       }
-    })
-    .collect();
+      diagnostic
+    }));
+  }
 
-  let query_parameters = generate_parameters_ts_type(&parameters, |p| match p {
-    Parameter::Query { parameter_data, .. } => Some(parameter_data),
-    _ => None,
-  });
+  ts_type
+}
 
-  let path_parameters = generate_parameters_ts_type(&parameters, |p| match p {
-    Parameter::Path { parameter_data, .. } => Some(parameter_data),
-    _ => None,
-  });
+/// Object type of the headers of a response, the `Content-Type` header is ignored.
+fn response_headers_ts_type(
+  response: &Response,
+  schema_options: &SchemaTypeOptions,
+  context: &SchemaContext,
+) -> Option<String> {
+  let mut properties: Vec<(String, String, ReferenceOr<Schema>)> = vec![];
+  let mut required: Vec<String> = vec![];
 
-  let responses: HashMap<String, String> = operation
-    .responses
-    .responses
-    .iter()
-    .filter_map(|(status_code, response)| {
-      let res = match response {
-        ReferenceOr::Item(response) => response,
-        ReferenceOr::Reference { reference } => {
-          panic!("Reference not implemented for path: {}", reference);
-        }
-      };
+  for (name, header) in &response.headers {
+    if name.eq_ignore_ascii_case("content-type") {
+      continue;
+    }
 
-      let res_schema = match res.content.get("application/json") {
-        Some(content) => match &content.schema {
-          Some(schema) => schema,
-          None => return None,
-        },
-        None => return None,
-      };
+    let pointer = context.child_pointer(&["headers", name]);
+    let Some(resolved) = resolve_component_reference(
+      header,
+      "headers",
+      |components, name| components.headers.get(name),
+      &pointer,
+      context,
+    ) else {
+      continue;
+    };
+    let pointer = pointer_join(&definition_pointer(header, pointer), &["schema"]);
+    let header = resolved;
 
-      let res_schema_interface =
-        crate::json_schema_to_typescript::schema_to_typescript(res_schema.clone(), None);
+    let ParameterSchemaOrContent::Schema(schema) = &header.format else {
+      continue;
+    };
 
-      Some((status_code.to_string(), res_schema_interface.to_string()))
-    })
-    .collect();
+    let mut schema = schema.clone();
 
-  OpenApiPath {
-    path: path.to_string(),
-    method: OpenApiOutput::open_api_method_to_string(&method).to_string(),
-    query_parameters,
-    path_parameters,
-    request_body: request_body_type.map(|request_body_type| request_body_type.to_string()),
-    responses,
+    if let ReferenceOr::Item(schema) = &mut schema {
+      if schema.schema_data.description.is_none() {
+        schema.schema_data.description = header.description.clone();
+      }
+      if header.deprecated == Some(true) {
+        schema.schema_data.deprecated = true;
+      }
+    }
+
+    if header.required {
+      required.push(name.clone());
+    }
+    properties.push((name.clone(), pointer, schema));
   }
+
+  if properties.is_empty() {
+    return None;
+  }
+
+  Some(object_ts_type(
+    properties,
+    required,
+    schema_options,
+    context,
+  ))
 }
 
-pub fn open_api_to_typescript(open_api: OpenAPI) -> OpenApiOutput {
-  let components: Vec<OpenApiComponent> = open_api
-    .components
+pub(crate) enum MediaTypeKind {
+  Json,
+  Form,
+  Text,
+  Binary,
+  Other,
+}
+
+pub(crate) fn media_type_kind(media_type: &str) -> MediaTypeKind {
+  let essence = media_type
+    .split(';')
+    .next()
     .unwrap_or_default()
-    .schemas
+    .trim()
+    .to_ascii_lowercase();
+
+  match essence.as_str() {
+    "application/json" => MediaTypeKind::Json,
+    "multipart/form-data" | "application/x-www-form-urlencoded" => MediaTypeKind::Form,
+    "application/octet-stream" | "application/pdf" => MediaTypeKind::Binary,
+    _ if essence.ends_with("+json") => MediaTypeKind::Json,
+    _ if essence.starts_with("text/") => MediaTypeKind::Text,
+    _ if ["image/", "audio/", "video/"]
+      .iter()
+      .any(|prefix| essence.starts_with(prefix)) =>
+    {
+      MediaTypeKind::Binary
+    }
+    _ => MediaTypeKind::Other,
+  }
+}
+
+fn media_type_ts_type(
+  media_type: &str,
+  schema: &Option<ReferenceOr<Schema>>,
+  schema_options: &SchemaTypeOptions,
+  context: &SchemaContext,
+) -> Option<String> {
+  let schema_pointer = context.child_pointer(&["schema"]);
+  let schema_ts_type = |context: &SchemaContext| {
+    schema.as_ref().map(|schema| {
+      schema_to_typescript_with_context(
+        schema.clone(),
+        Some(schema_options.clone()),
+        &context.at(&schema_pointer),
+      )
+      .to_string()
+    })
+  };
+
+  match media_type_kind(media_type) {
+    MediaTypeKind::Json => schema_ts_type(context),
+    MediaTypeKind::Other => {
+      let ts_type = schema_ts_type(context);
+      if ts_type.is_none() {
+        context.warning(
+          "unsupported-media-type",
+          format!(
+            "`{}` has no schema and isn't supported, it is ignored",
+            media_type
+          ),
+        );
+      }
+      ts_type
+    }
+    MediaTypeKind::Form => schema_ts_type(&SchemaContext {
+      binary_as_blob: true,
+      ..*context
+    })
+    .or_else(|| Some("Record<string, string | Blob>".to_string())),
+    MediaTypeKind::Text => schema_ts_type(context).or_else(|| Some("string".to_string())),
+    MediaTypeKind::Binary => Some("Blob".to_string()),
+  }
+}
+
+/// Types of every media type of a request or response `content`, along with the preferred one: the
+/// first JSON media type, or the first media type when there is none.
+fn content_ts_types(
+  content: &Content,
+  schema_options: &SchemaTypeOptions,
+  context: &SchemaContext,
+) -> (Option<String>, IndexMap<String, String>) {
+  let ts_types: Vec<(&String, String)> = content
     .iter()
-    .map(|(name, schema)| OpenApiComponent {
-      name: name.clone(),
-      ts_type: crate::json_schema_to_typescript::schema_to_typescript(schema.clone(), None)
-        .to_string(),
+    .filter_map(|(media_type, media_type_object)| {
+      let pointer = context.child_pointer(&["content", media_type]);
+      media_type_ts_type(
+        media_type,
+        &media_type_object.schema,
+        schema_options,
+        &context.at(&pointer),
+      )
+      .map(|ts_type| (media_type, ts_type))
     })
     .collect();
 
-  let paths: Vec<OpenApiPath> = open_api
-    .paths
+  let preferred = ts_types
     .iter()
-    .flat_map(|(path, path_item_ref)| {
-      let path_item = match path_item_ref {
-        ReferenceOr::Item(path_item) => path_item,
-        ReferenceOr::Reference { reference } => {
-          panic!("Reference not implemented for path: {}", reference);
-        }
-      };
+    .find(|(media_type, _)| matches!(media_type_kind(media_type), MediaTypeKind::Json))
+    .or(ts_types.first())
+    .map(|(_, ts_type)| ts_type.clone());
 
-      vec![
-        (OpenApiMethod::Get, &path_item.get),
-        (OpenApiMethod::Put, &path_item.put),
-        (OpenApiMethod::Post, &path_item.post),
-        (OpenApiMethod::Delete, &path_item.delete),
-        (OpenApiMethod::Patch, &path_item.patch),
-        (OpenApiMethod::Options, &path_item.options),
-      ]
+  (
+    preferred,
+    ts_types
       .into_iter()
-      .filter_map(|(method, operation)| {
-        operation
-          .as_ref()
-          .map(|op| get_open_api_path(path, method, op))
-      })
-    })
-    .collect();
+      .map(|(media_type, ts_type)| (media_type.clone(), ts_type))
+      .collect(),
+  )
+}
 
-  OpenApiOutput { paths, components }
+fn schema_or_content_ts_type(
+  format: &ParameterSchemaOrContent,
+  schema_options: &SchemaTypeOptions,
+  context: &SchemaContext,
+) -> Option<String> {
+  match format {
+    ParameterSchemaOrContent::Schema(schema) => {
+      let pointer = context.child_pointer(&["schema"]);
+      Some(
+        schema_to_typescript_with_context(
+          schema.clone(),
+          Some(schema_options.clone()),
+          &context.at(&pointer),
+        )
+        .to_string(),
+      )
+    }
+    ParameterSchemaOrContent::Content(content) => {
+      content_ts_types(content, schema_options, context).0
+    }
+  }
 }
 
-#[cfg(test)]
-mod tests {
-  use super::*;
-  use serde_json::json;
+/// Credentials a client sends for a security scheme. An API key is named after its parameter and
+/// HTTP basic has a `username` and `password`, the other schemes have no name for their credential:
+/// it is a `token` for the other HTTP schemes, like bearer, and an `accessToken` for OAuth2 and
+/// OpenID Connect.
+fn security_scheme_ts_type(
+  security_scheme: &SecurityScheme,
+  schema_options: &SchemaTypeOptions,
+  context: &SchemaContext,
+) -> Option<String> {
+  let credentials: Vec<&str> = match security_scheme {
+    SecurityScheme::APIKey { name, .. } => vec![name],
+    SecurityScheme::HTTP { scheme, .. } if scheme.eq_ignore_ascii_case("basic") => {
+      vec!["username", "password"]
+    }
+    SecurityScheme::HTTP { .. } => vec!["token"],
+    SecurityScheme::OAuth2 { .. } | SecurityScheme::OpenIDConnect { .. } => vec!["accessToken"],
+  };
 
-  fn create_openapi_json(paths_json: &str) -> OpenAPI {
-    let base = json!({
-      "openapi": "3.0.0",
-      "info": {
-        "title": "Test API",
-        "version": "1.0.0"
-      }
-    });
+  let string_schema = Schema {
+    schema_data: SchemaData::default(),
+    schema_kind: SchemaKind::Type(Type::String(StringType::default())),
+  };
 
-    let paths: serde_json::Value = serde_json::from_str(paths_json).unwrap();
+  Some(object_ts_type(
+    credentials
+      .iter()
+      .map(|credential| {
+        (
+          credential.to_string(),
+          context.child_pointer(&[credential]),
+          ReferenceOr::Item(string_schema.clone()),
+        )
+      })
+      .collect(),
+    credentials
+      .iter()
+      .map(|credential| credential.to_string())
+      .collect(),
+    schema_options,
+    context,
+  ))
+}
 
-    let complete_json = json!({
-      "openapi": base["openapi"],
-      "info": base["info"],
-      "paths": paths
-    });
+/// References TypeScript resolves eagerly: the schema itself and its composition members, unlike
+/// the ones nested in objects and arrays.
+fn alias_references(schema: &ReferenceOr<Schema>) -> Vec<&str> {
+  let members: Vec<&ReferenceOr<Schema>> = match schema {
+    ReferenceOr::Reference { reference } => return vec![reference],
+    ReferenceOr::Item(schema) => match &schema.schema_kind {
+      SchemaKind::AllOf { all_of: members }
+      | SchemaKind::OneOf { one_of: members }
+      | SchemaKind::AnyOf { any_of: members } => members.iter().collect(),
+      SchemaKind::Any(any_schema) => any_schema
+        .all_of
+        .iter()
+        .chain(&any_schema.one_of)
+        .chain(&any_schema.any_of)
+        .collect(),
+      _ => vec![],
+    },
+  };
 
-    serde_json::from_value(complete_json).unwrap()
-  }
+  members.into_iter().flat_map(alias_references).collect()
+}
 
-  #[test]
-  fn test_basic_get() {
-    let openapi = create_openapi_json(
-      r#"{
-      "/test": {
-        "get": {
-          "responses": {
-            "200": {
-              "description": "Success response",
-              "content": {
-                "application/json": {
-                  "schema": {
-                    "type": "object",
-                    "properties": {
-                      "message": {
-                        "type": "string"
-                      }
-                    }
-                  }
-                }
-              }
-            }
-          }
-        }
+/// Names of a cycle of aliases from a schema component back to itself, like `A`, `B`, `A`.
+fn circular_alias<'a>(name: &'a str, components: &'a Components) -> Option<Vec<&'a str>> {
+  let mut visited: Vec<&str> = vec![];
+  let mut stack: Vec<Vec<&str>> = vec![vec![name]];
+
+  while let Some(cycle) = stack.pop() {
+    let Some(schema) = components.schemas.get(cycle[cycle.len() - 1]) else {
+      continue;
+    };
+
+    for reference in alias_references(schema) {
+      let Some(target) = reference.strip_prefix("#/components/schemas/") else {
+        continue;
+      };
+
+      if target == name {
+        return Some([cycle, vec![target]].concat());
       }
-    }"#,
-    );
-    let result = open_api_to_typescript(openapi);
 
-    assert_eq!(result.paths.len(), 1);
-    assert_eq!(result.paths[0].method, "get");
-    assert_eq!(result.paths[0].path, "/test");
-    assert!(result.paths[0].responses.contains_key("200"));
+      if !visited.contains(&target) {
+        visited.push(target);
+        stack.push([cycle.clone(), vec![target]].concat());
+      }
+    }
   }
 
-  #[test]
+  None
+}
+
+/// Type names of the components, by reference.
+type ComponentNames = BTreeMap<String, String>;
+
+/// PascalCase type name of a component outside of the schemas, suffixed with its kind when it is
+/// already taken, then with a counter.
+fn component_type_name(name: &str, kind: ComponentKind, taken: &[String]) -> String {
+  let is_taken = |type_name: &str| type_name.is_empty() || taken.iter().any(|t| t == type_name);
+  let mut type_name = type_name(&to_pascal_case(name));
+
+  if is_taken(&type_name) {
+    type_name = format!("{}{}", type_name, kind.suffix());
+  }
+
+  let base = type_name.clone();
+  let mut index = 1;
+
+  while is_taken(&type_name) {
+    index += 1;
+    type_name = format!("{}{}", base, index);
+  }
+
+  type_name
+}
+
+/// Types of every components section, schemas keep their name and other sections get a
+/// [`component_type_name`]. Only the `reachable` components are converted, when given, along with
+/// the names of the other ones.
+fn components_ts_types(
+  components: &Components,
+  reachable: Option<&BTreeSet<(String, String)>>,
+  schema_options: &SchemaTypeOptions,
+  context: &SchemaContext,
+) -> (Vec<OpenApiComponent>, ComponentNames, Vec<String>) {
+  let is_kept = |section: &str, name: &str| {
+    reachable.is_none_or(|reachable| reachable.contains(&(section.to_string(), name.to_string())))
+  };
+  let mut removed: Vec<(&str, ComponentKind, &String)> = vec![];
+
+  let mut ts_types: Vec<OpenApiComponent> = components
+    .schemas
+    .iter()
+    .filter(|(name, _)| {
+      let is_kept = is_kept("schemas", name);
+      if !is_kept {
+        removed.push(("schemas", ComponentKind::Schema, name));
+      }
+      is_kept
+    })
+    .map(|(name, schema)| {
+      let pointer = context.child_pointer(&["components", "schemas", name]);
+      let context = &context.at(&pointer);
+
+      if let Some(cycle) = circular_alias(name, components) {
+        context.warning(
+          "circular-alias",
+          format!(
+            "`{}` is a circular type alias ({}), TypeScript rejects it",
+            name,
+            cycle.join(" -> ")
+          ),
+        );
+      }
+
+      let interface =
+        schema_to_typescript_with_context(schema.clone(), Some(schema_options.clone()), context);
+
+      let name = type_name(name);
+      let type_guard = schema_options
+        .type_guard
+        .unwrap_or(false)
+        .then(|| interface.type_guard(&name));
+
+      OpenApiComponent {
+        name,
+        kind: ComponentKind::Schema,
+        ts_type: interface.to_string(),
+        type_guard,
+      }
+    })
+    .collect();
+  let mut names: ComponentNames = components
+    .schemas
+    .keys()
+    .filter(|name| is_kept("schemas", name))
+    .map(|name| (format!("#/components/schemas/{}", name), type_name(name)))
+    .collect();
+
+  let mut section_ts_types: Vec<(&str, ComponentKind, &String, Option<String>)> = vec![];
+
+  for (name, response) in &components.responses {
+    if !is_kept("responses", name) {
+      removed.push(("responses", ComponentKind::Response, name));
+      continue;
+    }
+
+    let pointer = context.child_pointer(&["components", "responses", name]);
+    let context = &context.at(&pointer);
+    let ts_type = resolve_component_reference(
+      response,
+      "responses",
+      |components, name| components.responses.get(name),
+      context.pointer,
+      context,
+    )
+    .and_then(|response| content_ts_types(&response.content, schema_options, context).0);
+    section_ts_types.push(("responses", ComponentKind::Response, name, ts_type));
+  }
+
+  for (name, parameter) in &components.parameters {
+    if !is_kept("parameters", name) {
+      removed.push(("parameters", ComponentKind::Parameter, name));
+      continue;
+    }
+
+    let pointer = context.child_pointer(&["components", "parameters", name]);
+    let context = &context.at(&pointer);
+    let ts_type = resolve_component_reference(
+      parameter,
+      "parameters",
+      |components, name| components.parameters.get(name),
+      context.pointer,
+      context,
+    )
+    .and_then(|parameter| {
+      schema_or_content_ts_type(
+        &parameter.parameter_data_ref().format,
+        schema_options,
+        context,
+      )
+    });
+    section_ts_types.push(("parameters", ComponentKind::Parameter, name, ts_type));
+  }
+
+  for (name, request_body) in &components.request_bodies {
+    if !is_kept("requestBodies", name) {
+      removed.push(("requestBodies", ComponentKind::RequestBody, name));
+      continue;
+    }
+
+    let pointer = context.child_pointer(&["components", "requestBodies", name]);
+    let context = &context.at(&pointer);
+    let ts_type = resolve_component_reference(
+      request_body,
+      "requestBodies",
+      |components, name| components.request_bodies.get(name),
+      context.pointer,
+      context,
+    )
+    .and_then(|request_body| content_ts_types(&request_body.content, schema_options, context).0);
+    section_ts_types.push(("requestBodies", ComponentKind::RequestBody, name, ts_type));
+  }
+
+  for (name, header) in &components.headers {
+    if !is_kept("headers", name) {
+      removed.push(("headers", ComponentKind::Header, name));
+      continue;
+    }
+
+    let pointer = context.child_pointer(&["components", "headers", name]);
+    let context = &context.at(&pointer);
+    let ts_type = resolve_component_reference(
+      header,
+      "headers",
+      |components, name| components.headers.get(name),
+      context.pointer,
+      context,
+    )
+    .and_then(|header| schema_or_content_ts_type(&header.format, schema_options, context));
+    section_ts_types.push(("headers", ComponentKind::Header, name, ts_type));
+  }
+
+  for (name, security_scheme) in &components.security_schemes {
+    if !is_kept("securitySchemes", name) {
+      removed.push(("securitySchemes", ComponentKind::SecurityScheme, name));
+      continue;
+    }
+
+    let pointer = context.child_pointer(&["components", "securitySchemes", name]);
+    let context = &context.at(&pointer);
+    let ts_type = resolve_component_reference(
+      security_scheme,
+      "securitySchemes",
+      |components, name| components.security_schemes.get(name),
+      context.pointer,
+      context,
+    )
+    .and_then(|security_scheme| security_scheme_ts_type(security_scheme, schema_options, context));
+    section_ts_types.push((
+      "securitySchemes",
+      ComponentKind::SecurityScheme,
+      name,
+      ts_type,
+    ));
+  }
+
+  let mut taken: Vec<String> = ts_types.iter().map(|c| c.name.clone()).collect();
+
+  for (section, kind, name, ts_type) in section_ts_types {
+    let Some(ts_type) = ts_type else {
+      continue;
+    };
+
+    let type_name = component_type_name(name, kind, &taken);
+    taken.push(type_name.clone());
+
+    names.insert(
+      format!("#/components/{}/{}", section, name),
+      type_name.clone(),
+    );
+    ts_types.push(OpenApiComponent {
+      name: type_name,
+      kind,
+      ts_type,
+      type_guard: None,
+    });
+  }
+
+  // Removed components are named after the kept ones, which keep the names they'd have without them
+  let removed_names = removed
+    .into_iter()
+    .map(|(section, kind, name)| {
+      let type_name = match section {
+        "schemas" => type_name(name),
+        _ => component_type_name(name, kind, &taken),
+      };
+      taken.push(type_name.clone());
+      type_name
+    })
+    .collect();
+
+  (ts_types, names, removed_names)
+}
+
+/// Type name of a referenced component, operations use it instead of repeating its type.
+fn referenced_name<T>(item: &ReferenceOr<T>, component_names: &ComponentNames) -> Option<String> {
+  match item {
+    ReferenceOr::Reference { reference } => component_names.get(reference).cloned(),
+    ReferenceOr::Item(_) => None,
+  }
+}
+
+fn get_open_api_path<'a>(
+  path: &str,
+  method: OpenApiMethod,
+  path_item: &'a PathItem,
+  operation: &'a Operation,
+  component_names: &ComponentNames,
+  schema_options: &SchemaTypeOptions,
+  context: &SchemaContext<'a>,
+) -> OpenApiPath {
+  let method = OpenApiOutput::open_api_method_to_string(&method);
+  let path_item_pointer = context.child_pointer(&["paths", path]);
+  let path_item_context = &context.at(&path_item_pointer);
+  let operation_pointer = path_item_context.child_pointer(&[method]);
+  let context = &context.at(&operation_pointer);
+
+  let (request_body, mut request_body_content) = match &operation.request_body {
+    Some(request_body) => {
+      let pointer = context.child_pointer(&["requestBody"]);
+
+      let (preferred, content) = resolve_component_reference(
+        request_body,
+        "requestBodies",
+        |components, name| components.request_bodies.get(name),
+        &pointer,
+        context,
+      )
+      .map(|resolved| {
+        let pointer = definition_pointer(request_body, pointer.clone());
+        content_ts_types(&resolved.content, schema_options, &context.at(&pointer))
+      })
+      .unwrap_or_default();
+
+      (
+        preferred.map(|ts_type| referenced_name(request_body, component_names).unwrap_or(ts_type)),
+        content,
+      )
+    }
+    None => (None, IndexMap::new()),
+  };
+
+  let parameters = merge_parameters(
+    &path_item.parameters,
+    path_item_context,
+    &operation.parameters,
+    context,
+  );
+  let query_parameters = generate_parameters_ts_type(
+    &parameters,
+    |p| match p {
+      Parameter::Query { parameter_data, .. } => Some(parameter_data),
+      _ => None,
+    },
+    schema_options,
+    context,
+  );
+
+  let path_parameters = generate_parameters_ts_type(
+    &parameters,
+    |p| match p {
+      Parameter::Path { parameter_data, .. } => Some(parameter_data),
+      _ => None,
+    },
+    schema_options,
+    context,
+  );
+
+  let header_parameters = generate_parameters_ts_type(
+    &parameters,
+    |p| match p {
+      Parameter::Header { parameter_data, .. } => Some(parameter_data),
+      _ => None,
+    },
+    schema_options,
+    context,
+  );
+
+  let cookie_parameters = generate_parameters_ts_type(
+    &parameters,
+    |p| match p {
+      Parameter::Cookie { parameter_data, .. } => Some(parameter_data),
+      _ => None,
+    },
+    schema_options,
+    context,
+  );
+
+  let mut responses: IndexMap<String, String> = IndexMap::new();
+  let mut response_content: IndexMap<String, IndexMap<String, String>> = IndexMap::new();
+  let mut response_headers: IndexMap<String, String> = IndexMap::new();
+
+  for (status_code, response) in status_responses(&operation.responses) {
+    let pointer = context.child_pointer(&["responses", &status_code]);
+    let Some(res) = resolve_component_reference(
+      response,
+      "responses",
+      |components, name| components.responses.get(name),
+      &pointer,
+      context,
+    ) else {
+      continue;
+    };
+
+    let pointer = definition_pointer(response, pointer);
+    let response_context = &context.at(&pointer);
+
+    let (preferred, content) = content_ts_types(&res.content, schema_options, response_context);
+    let preferred =
+      preferred.map(|ts_type| referenced_name(response, component_names).unwrap_or(ts_type));
+
+    if let Some(headers) = response_headers_ts_type(res, schema_options, response_context) {
+      response_headers.insert(status_code.to_string(), headers);
+    }
+
+    if let Some(preferred) = preferred {
+      responses.insert(status_code.to_string(), preferred);
+      response_content.insert(status_code.to_string(), content);
+    }
+  }
+
+  let default_is_success = default_is_success(&operation.responses);
+
+  if context.sort_alphabetically {
+    request_body_content.sort_keys();
+    responses.sort_keys();
+    response_content.sort_keys();
+    response_content.values_mut().for_each(IndexMap::sort_keys);
+    response_headers.sort_keys();
+  }
+
+  OpenApiPath {
+    path: path.to_string(),
+    method: method.to_string(),
+    operation_id: operation.operation_id.clone(),
+    name: operation_name(method, path, operation),
+    summary: operation.summary.clone(),
+    description: operation.description.clone(),
+    tags: operation.tags.clone(),
+    deprecated: operation.deprecated,
+    query_parameters,
+    path_parameters,
+    header_parameters,
+    cookie_parameters,
+    request_body,
+    request_body_content,
+    success_response: responses_union(&responses, |status_code| {
+      is_success_status(status_code, default_is_success)
+    }),
+    error_response: responses_union(&responses, |status_code| {
+      is_error_status(status_code, default_is_success)
+    }),
+    responses,
+    response_content,
+    response_headers,
+    mock_responses: IndexMap::new(),
+  }
+}
+
+/// JSON body of every response with a JSON content, with a seed derived from the operation.
+fn mock_responses(
+  open_api_path: &OpenApiPath,
+  operation: &Operation,
+  options: &MockOptions,
+  components: &Components,
+) -> IndexMap<String, String> {
+  let context = SchemaContext {
+    components: Some(components),
+    ..Default::default()
+  };
+
+  status_responses(&operation.responses)
+    .into_iter()
+    .filter_map(|(status_code, response)| {
+      let response = resolve_component_reference(
+        response,
+        "responses",
+        |components, name| components.responses.get(name),
+        "",
+        &context,
+      )?;
+      let key = format!(
+        "{} {} {}",
+        open_api_path.method, open_api_path.path, status_code
+      );
+
+      mock_body(&response.content, options, &key, components).map(|body| (status_code, body))
+    })
+    .collect()
+}
+
+/// JSON body of a content, from the first example valid against the schema, or generated from the
+/// schema with a seed derived from `key`.
+fn mock_body(
+  content: &Content,
+  options: &MockOptions,
+  key: &str,
+  components: &Components,
+) -> Option<String> {
+  let media_type = content
+    .iter()
+    .find(|(media_type, _)| matches!(media_type_kind(media_type), MediaTypeKind::Json))
+    .map(|(_, media_type)| media_type)?;
+  let context = SchemaContext {
+    components: Some(components),
+    ..Default::default()
+  };
+  let schema = match &media_type.schema {
+    Some(ReferenceOr::Item(schema)) => Some(schema),
+    Some(ReferenceOr::Reference { reference }) => resolve_schema_reference(reference, &context),
+    None => None,
+  };
+
+  let example = media_type
+    .example
+    .iter()
+    .chain(
+      media_type
+        .examples
+        .values()
+        .filter_map(|example| match example {
+          ReferenceOr::Item(example) => example.value.as_ref(),
+          ReferenceOr::Reference { .. } => None,
+        }),
+    )
+    .find(|example| {
+      schema.is_none_or(|schema| validate_value(schema, example, Some(components)).is_empty())
+    });
+
+  let value = match example {
+    Some(example) => example.clone(),
+    None => {
+      let options = MockOptions {
+        seed: Some(derive_seed(options.seed, key)),
+        ..options.clone()
+      };
+
+      Mocker::new(context, None, &options).mock(media_type.schema.as_ref()?, 0)
+    }
+  };
+
+  serde_json::to_string(&value).ok()
+}
+
+/// Applies [`normalize_schema`] to every schema of an OpenAPI document, returning the keywords it
+/// ignores.
+pub fn normalize_open_api(open_api: &mut Value) -> Vec<Diagnostic> {
+  fn normalize_schemas(value: &mut Value, pointer: &str, diagnostics: &Diagnostics) {
+    match value {
+      Value::Object(map) => {
+        for (key, value) in map {
+          let pointer = pointer_join(pointer, &[key]);
+
+          match key.as_str() {
+            "schema" => normalize_schema(value, &pointer, diagnostics),
+            "schemas" if pointer == "#/components/schemas" => {
+              for (name, schema) in value.as_object_mut().into_iter().flatten() {
+                normalize_schema(schema, &pointer_join(&pointer, &[name]), diagnostics);
+              }
+            }
+            "example" | "examples" => {}
+            _ => normalize_schemas(value, &pointer, diagnostics),
+          }
+        }
+      }
+      Value::Array(values) => {
+        for (index, value) in values.iter_mut().enumerate() {
+          normalize_schemas(
+            value,
+            &pointer_join(pointer, &[&index.to_string()]),
+            diagnostics,
+          );
+        }
+      }
+      _ => {}
+    }
+  }
+
+  let diagnostics = Diagnostics::default();
+  normalize_schemas(open_api, "#", &diagnostics);
+
+  diagnostics.into_vec()
+}
+
+/// Components and operations of a document, each operation kept next to its definition.
+pub(crate) struct ConvertedOpenApi<'a> {
+  pub components: Vec<OpenApiComponent>,
+  pub removed_components: Vec<String>,
+  pub operations: Vec<(OpenApiPath, &'a Operation)>,
+  pub diagnostics: Vec<Diagnostic>,
+}
+
+/// `document_diagnostics` are the ones of [`normalize_open_api`], only kept for the operations and
+/// components that are converted.
+pub(crate) fn convert_open_api<'a>(
+  open_api: &'a OpenAPI,
+  document_diagnostics: Vec<Diagnostic>,
+  options: &OpenApiOptions,
+) -> ConvertedOpenApi<'a> {
+  let default_components = Components::default();
+  let open_api_components = open_api.components.as_ref().unwrap_or(&default_components);
+  let diagnostics = Diagnostics::default();
+  let context = SchemaContext {
+    components: Some(open_api_components),
+    merge_all_of: options.merge_all_of.unwrap_or(false),
+    sort_alphabetically: is_alphabetical_sort(&options.sort),
+    diagnostics: Some(&diagnostics),
+    ..Default::default()
+  };
+  let schema_options = SchemaTypeOptions {
+    format: options.format.clone(),
+    type_guard: options.type_guards,
+    ..Default::default()
+  };
+
+  let included_operations: Vec<(&String, OpenApiMethod, &PathItem, &Operation)> = open_api
+    .paths
+    .iter()
+    .flat_map(|(path, path_item_ref)| {
+      let path_item = match path_item_ref {
+        ReferenceOr::Item(path_item) => path_item,
+        ReferenceOr::Reference { reference } => {
+          let pointer = context.child_pointer(&["paths", path]);
+          context.at(&pointer).warning(
+            "unresolved-reference",
+            format!(
+              "Path item reference `{}` isn't supported, it is ignored",
+              reference
+            ),
+          );
+          return vec![];
+        }
+      };
+
+      vec![
+        (OpenApiMethod::Get, &path_item.get),
+        (OpenApiMethod::Put, &path_item.put),
+        (OpenApiMethod::Post, &path_item.post),
+        (OpenApiMethod::Delete, &path_item.delete),
+        (OpenApiMethod::Patch, &path_item.patch),
+        (OpenApiMethod::Options, &path_item.options),
+        (OpenApiMethod::Head, &path_item.head),
+        (OpenApiMethod::Trace, &path_item.trace),
+      ]
+      .into_iter()
+      .filter_map(|(method, operation)| {
+        operation
+          .as_ref()
+          .filter(|op| {
+            options.filter.as_ref().is_none_or(|filter| {
+              filter.includes(path, OpenApiOutput::open_api_method_to_string(&method), op)
+            })
+          })
+          .map(|op| (path, method, path_item, op))
+      })
+      .collect::<Vec<_>>()
+    })
+    .collect();
+
+  let reachable = options
+    .tree_shake
+    .unwrap_or(options.filter.is_some())
+    .then(|| {
+      reachable_components(
+        open_api,
+        &included_operations
+          .iter()
+          .map(|(_, _, path_item, op)| (*path_item, *op))
+          .collect::<Vec<_>>(),
+      )
+    });
+
+  // The document diagnostics of the filtered out operations and components aren't reported.
+  let converted_pointers: Vec<String> = included_operations
+    .iter()
+    .flat_map(|(path, method, _, _)| {
+      [
+        OpenApiOutput::open_api_method_to_string(method),
+        "parameters",
+      ]
+      .map(|segment| pointer_join("#/paths", &[path, segment]))
+    })
+    .chain(
+      reachable
+        .iter()
+        .flatten()
+        .map(|(section, name)| pointer_join("#/components", &[section, name])),
+    )
+    .collect();
+
+  diagnostics.extend(document_diagnostics.into_iter().filter(|diagnostic| {
+    let is_filtered = is_pointer_within(&diagnostic.pointer, "#/paths")
+      || (reachable.is_some() && is_pointer_within(&diagnostic.pointer, "#/components"));
+
+    !is_filtered
+      || converted_pointers
+        .iter()
+        .any(|pointer| is_pointer_within(&diagnostic.pointer, pointer))
+  }));
+
+  let (mut components, component_names, mut removed_components) = components_ts_types(
+    open_api_components,
+    reachable.as_ref(),
+    &schema_options,
+    &context,
+  );
+
+  let mut operations: Vec<(OpenApiPath, &Operation)> = included_operations
+    .into_iter()
+    .map(|(path, method, path_item, op)| {
+      let mut open_api_path = get_open_api_path(
+        path,
+        method,
+        path_item,
+        op,
+        &component_names,
+        &schema_options,
+        &context,
+      );
+
+      if let Some(mock_options) = &options.mocks {
+        open_api_path.mock_responses =
+          mock_responses(&open_api_path, op, mock_options, open_api_components);
+
+        if context.sort_alphabetically {
+          open_api_path.mock_responses.sort_keys();
+        }
+      }
+
+      (open_api_path, op)
+    })
+    .collect();
+
+  let taken_names: Vec<&str> = components
+    .iter()
+    .map(|component| component.name.as_str())
+    .collect();
+  deduplicate_operation_names(&mut operations, &taken_names, &context);
+
+  if context.sort_alphabetically {
+    components.sort_by(|a, b| a.name.cmp(&b.name));
+    removed_components.sort();
+    operations.sort_by(|(a, _), (b, _)| (&a.path, &a.method).cmp(&(&b.path, &b.method)));
+  }
+
+  ConvertedOpenApi {
+    components,
+    removed_components,
+    operations,
+    diagnostics: diagnostics.into_vec(),
+  }
+}
+
+pub fn open_api_to_typescript(
+  open_api: OpenAPI,
+  document_diagnostics: Vec<Diagnostic>,
+  options: Option<OpenApiOptions>,
+) -> OpenApiOutput {
+  let converted = convert_open_api(
+    &open_api,
+    document_diagnostics,
+    &options.unwrap_or_default(),
+  );
+
+  OpenApiOutput {
+    paths: converted
+      .operations
+      .into_iter()
+      .map(|(path, _)| path)
+      .collect(),
+    components: converted.components,
+    removed_components: converted.removed_components,
+    diagnostics: converted.diagnostics,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::json_schema_to_typescript::MemberSeparator;
+  use serde_json::json;
+
+  fn create_openapi_json(paths_json: &str) -> OpenAPI {
+    let base = json!({
+      "openapi": "3.0.0",
+      "info": {
+        "title": "Test API",
+        "version": "1.0.0"
+      }
+    });
+
+    let paths: serde_json::Value = serde_json::from_str(paths_json).unwrap();
+
+    let complete_json = json!({
+      "openapi": base["openapi"],
+      "info": base["info"],
+      "paths": paths
+    });
+
+    serde_json::from_value(complete_json).unwrap()
+  }
+
+  #[test]
+  fn test_basic_get() {
+    let openapi = create_openapi_json(
+      r#"{
+      "/test": {
+        "get": {
+          "responses": {
+            "200": {
+              "description": "Success response",
+              "content": {
+                "application/json": {
+                  "schema": {
+                    "type": "object",
+                    "properties": {
+                      "message": {
+                        "type": "string"
+                      }
+                    }
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }"#,
+    );
+    let result = open_api_to_typescript(openapi, vec![], None);
+
+    assert_eq!(result.paths.len(), 1);
+    assert_eq!(result.paths[0].method, "get");
+    assert_eq!(result.paths[0].path, "/test");
+    assert!(result.paths[0].responses.contains_key("200"));
+  }
+
+  #[test]
   fn test_with_query_params() {
     let openapi = create_openapi_json(
       r#"{
@@ -308,29 +1646,803 @@ mod tests {
           "parameters": [
             {
               "in": "query",
-              "name": "search",
-              "required": true,
+              "name": "search",
+              "required": true,
+              "schema": {
+                "type": "string"
+              }
+            },
+            {
+              "in": "query",
+              "name": "limit",
+              "required": false,
+              "schema": {
+                "type": "integer"
+              }
+            }
+          ],
+          "responses": {
+            "200": {
+              "description": "Success response",
+              "content": {
+                "application/json": {
+                  "schema": {
+                    "type": "object"
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }"#,
+    );
+    let result = open_api_to_typescript(openapi, vec![], None);
+
+    assert_eq!(
+      result.paths[0].query_parameters,
+      Some("{\n  search: string;\n  limit?: number;\n}".to_string())
+    );
+    assert_eq!(result.paths[0].method, "get");
+    assert_eq!(result.paths[0].path, "/search");
+    assert!(result.paths[0].responses.contains_key("200"));
+  }
+
+  #[test]
+  fn test_with_path_params() {
+    let openapi = create_openapi_json(
+      r#"{
+      "/users/{id}": {
+        "delete": {
+          "parameters": [
+            {
+              "in": "path",
+              "name": "id",
+              "required": true,
+              "schema": {
+                "type": "integer"
+              }
+            }
+          ],
+          "responses": {
+            "204": {
+              "description": "User deleted successfully"
+            }
+          }
+        }
+      }
+    }"#,
+    );
+    let result = open_api_to_typescript(openapi, vec![], None);
+
+    assert_eq!(
+      result.paths[0].path_parameters,
+      Some("{\n  id: number;\n}".to_string())
+    );
+    assert_eq!(result.paths[0].method, "delete");
+    assert_eq!(result.paths[0].path, "/users/{id}");
+  }
+
+  #[test]
+  fn test_with_request_body() {
+    let openapi = create_openapi_json(
+      r#"{
+      "/users": {
+        "post": {
+          "requestBody": {
+            "required": true,
+            "content": {
+              "application/json": {
+                "schema": {
+                  "type": "object",
+                  "properties": {
+                    "name": {
+                      "type": "string"
+                    },
+                    "email": {
+                      "type": "string"
+                    }
+                  },
+                  "required": ["name", "email"]
+                }
+              }
+            }
+          },
+          "responses": {
+            "201": {
+              "description": "User created successfully",
+              "content": {
+                "application/json": {
+                  "schema": {
+                    "type": "object",
+                    "properties": {
+                      "id": {
+                        "type": "integer"
+                      }
+                    }
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }"#,
+    );
+    let result = open_api_to_typescript(openapi, vec![], None);
+
+    assert!(result.paths[0].request_body.is_some());
+    assert_eq!(result.paths[0].method, "post");
+    assert_eq!(result.paths[0].path, "/users");
+    assert!(result.paths[0].responses.contains_key("201"));
+  }
+
+  #[test]
+  fn test_multiple_responses() {
+    let openapi = create_openapi_json(
+      r#"{
+      "/users/{id}": {
+        "patch": {
+          "parameters": [
+            {
+              "in": "path",
+              "name": "id",
+              "required": true,
+              "schema": {
+                "type": "integer"
+              }
+            }
+          ],
+          "requestBody": {
+            "required": true,
+            "content": {
+              "application/json": {
+                "schema": {
+                  "type": "object",
+                  "properties": {
+                    "name": {
+                      "type": "string"
+                    }
+                  }
+                }
+              }
+            }
+          },
+          "responses": {
+            "200": {
+              "description": "Success response",
+              "content": {
+                "application/json": {
+                  "schema": {
+                    "type": "object",
+                    "properties": {
+                      "id": {
+                        "type": "integer"
+                      },
+                      "name": {
+                        "type": "string"
+                      }
+                    }
+                  }
+                }
+              }
+            },
+            "400": {
+              "description": "Bad request",
+              "content": {
+                "application/json": {
+                  "schema": {
+                    "type": "object",
+                    "properties": {
+                      "error": {
+                        "type": "string"
+                      }
+                    }
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }"#,
+    );
+    let result = open_api_to_typescript(openapi, vec![], None);
+
+    assert_eq!(result.paths[0].responses.len(), 2);
+    assert!(result.paths[0].responses.contains_key("200"));
+    assert!(result.paths[0].responses.contains_key("400"));
+    assert_eq!(result.paths[0].method, "patch");
+  }
+
+  #[test]
+  fn test_ref_property_descriptions() {
+    let mut openapi_json = json!({
+      "openapi": "3.1.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {},
+      "components": {
+        "schemas": {
+          "Status": {
+            "type": "string",
+            "description": "Account status",
+            "enum": ["active", "closed"]
+          },
+          "Account": {
+            "type": "object",
+            "properties": {
+              "status": { "$ref": "#/components/schemas/Status" },
+              "previousStatus": {
+                "$ref": "#/components/schemas/Status",
+                "description": "Status before the last change"
+              }
+            }
+          }
+        }
+      }
+    });
+
+    let diagnostics = normalize_open_api(&mut openapi_json);
+    let openapi: OpenAPI = serde_json::from_value(openapi_json).unwrap();
+    let result = open_api_to_typescript(openapi, diagnostics, None);
+
+    assert_eq!(
+      result.components[1].ts_type,
+      "{\n  /**\n   * Account status\n   */\n  status?: Status;\n  /**\n   * Status before the last change\n   */\n  previousStatus?: Status;\n}"
+    );
+  }
+
+  #[test]
+  fn test_recursive_components() {
+    let openapi: OpenAPI = serde_json::from_value(json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {},
+      "components": {
+        "schemas": {
+          "Node": {
+            "type": "object",
+            "properties": {
+              "children": {
+                "type": "array",
+                "items": { "$ref": "#/components/schemas/Node" }
+              },
+              "self": { "$ref": "#" }
+            }
+          },
+          "Parent": { "$ref": "#/components/schemas/Owner" },
+          "Owner": { "oneOf": [{ "$ref": "#/components/schemas/Parent" }, { "type": "string" }] }
+        }
+      }
+    }))
+    .unwrap();
+
+    let result = open_api_to_typescript(openapi, vec![], None);
+
+    assert_eq!(result.components[0].name, "Node");
+    // `#` is the document in OpenAPI, not the component
+    assert_eq!(
+      result.components[0].ts_type,
+      "{\n  children?: Node[];\n  self?: any;\n}"
+    );
+
+    let diagnostics: Vec<(&str, &str)> = result
+      .diagnostics
+      .iter()
+      .map(|d| (d.pointer.as_str(), d.code.as_str()))
+      .collect();
+
+    assert_eq!(
+      diagnostics,
+      vec![
+        (
+          "#/components/schemas/Node/properties/self",
+          "unresolved-reference"
+        ),
+        ("#/components/schemas/Parent", "circular-alias"),
+        ("#/components/schemas/Owner", "circular-alias"),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_format_options() {
+    let openapi = create_openapi_json(
+      r#"{
+      "/search": {
+        "get": {
+          "parameters": [
+            {
+              "in": "query",
+              "name": "sort",
               "schema": {
-                "type": "string"
+                "type": "string",
+                "enum": ["asc", "desc"]
+              }
+            }
+          ],
+          "responses": {
+            "200": {
+              "description": "Success response",
+              "content": {
+                "application/json": {
+                  "schema": {
+                    "type": "object",
+                    "properties": {
+                      "total": { "type": "integer" }
+                    }
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }"#,
+    );
+    let result = open_api_to_typescript(
+      openapi,
+      vec![],
+      Some(OpenApiOptions {
+        format: Some(FormatOptions {
+          tab_width: Some(4),
+          single_quote: Some(true),
+          member_separator: Some(MemberSeparator::Comma),
+          ..Default::default()
+        }),
+        ..Default::default()
+      }),
+    );
+
+    assert_eq!(
+      result.paths[0].query_parameters,
+      Some("{\n    sort?: 'asc' | 'desc',\n}".to_string())
+    );
+    assert_eq!(
+      result.paths[0].responses["200"],
+      "{\n    total?: number,\n}"
+    );
+  }
+
+  #[test]
+  fn test_alphabetical_sort() {
+    let openapi_json = r#"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {
+        "/users": {
+          "post": { "responses": { "201": { "description": "Created" } } },
+          "get": { "responses": { "200": { "description": "Success" } } }
+        },
+        "/accounts": {
+          "get": {
+            "parameters": [
+              { "in": "query", "name": "page", "schema": { "type": "integer" } },
+              { "in": "query", "name": "limit", "schema": { "type": "integer" } }
+            ],
+            "responses": {
+              "404": { "description": "Not found", "content": { "text/plain": {} } },
+              "200": { "description": "Success", "content": { "text/plain": {} } }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "User": { "type": "string" },
+          "Account": { "type": "string" }
+        }
+      }
+    }"#;
+
+    let openapi: OpenAPI = serde_json::from_str(openapi_json).unwrap();
+    let result = open_api_to_typescript(openapi.clone(), vec![], None);
+
+    let order = |result: &OpenApiOutput| {
+      result
+        .paths
+        .iter()
+        .map(|path| format!("{} {}", path.method, path.path))
+        .collect::<Vec<String>>()
+    };
+
+    assert_eq!(
+      order(&result),
+      vec!["get /users", "post /users", "get /accounts"]
+    );
+    assert_eq!(result.components[0].name, "User");
+    assert_eq!(
+      result.paths[2].query_parameters,
+      Some("{\n  page?: number;\n  limit?: number;\n}".to_string())
+    );
+    assert_eq!(
+      result.paths[2].responses.keys().collect::<Vec<_>>(),
+      vec!["404", "200"]
+    );
+
+    let result = open_api_to_typescript(
+      openapi,
+      vec![],
+      Some(OpenApiOptions {
+        sort: Some(SortOrder::Alphabetical),
+        ..Default::default()
+      }),
+    );
+
+    assert_eq!(
+      order(&result),
+      vec!["get /accounts", "get /users", "post /users"]
+    );
+    assert_eq!(result.components[0].name, "Account");
+    assert_eq!(
+      result.paths[0].query_parameters,
+      Some("{\n  limit?: number;\n  page?: number;\n}".to_string())
+    );
+    assert_eq!(
+      result.paths[0].responses.keys().collect::<Vec<_>>(),
+      vec!["200", "404"]
+    );
+  }
+
+  #[test]
+  fn test_source_order_through_value() {
+    // Node inputs are parsed to a `Value` first, which must keep the declaration order
+    let mut openapi_json: Value = serde_json::from_str(
+      r#"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {
+        "/users": {
+          "post": { "responses": { "201": { "description": "Created" } } },
+          "get": { "responses": { "200": { "description": "Success" } } }
+        },
+        "/accounts": {
+          "get": {
+            "responses": {
+              "404": { "description": "Not found", "content": { "text/plain": {} } },
+              "200": { "description": "Success", "content": { "text/plain": {} } }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "User": { "type": "object", "properties": { "name": { "type": "string" }, "id": { "type": "string" } } },
+          "Account": { "type": "string" }
+        }
+      }
+    }"#,
+    )
+    .unwrap();
+    let diagnostics = normalize_open_api(&mut openapi_json);
+    let openapi: OpenAPI = serde_json::from_value(openapi_json).unwrap();
+    let result = open_api_to_typescript(openapi, diagnostics, None);
+
+    assert_eq!(
+      result
+        .paths
+        .iter()
+        .map(|path| format!("{} {}", path.method, path.path))
+        .collect::<Vec<String>>(),
+      vec!["get /users", "post /users", "get /accounts"]
+    );
+    assert_eq!(
+      result.paths[2].responses.keys().collect::<Vec<_>>(),
+      vec!["404", "200"]
+    );
+    assert_eq!(
+      result
+        .components
+        .iter()
+        .map(|component| component.name.as_str())
+        .collect::<Vec<&str>>(),
+      vec!["User", "Account"]
+    );
+    assert_eq!(
+      result.components[0].ts_type,
+      "{\n  name?: string;\n  id?: string;\n}"
+    );
+  }
+
+  #[test]
+  fn test_operation_names() {
+    assert_eq!(to_pascal_case("users.list-all_items"), "UsersListAllItems");
+    assert_eq!(
+      synthesize_operation_name("get", "/users/{userId}/posts"),
+      "getUsersByUserIdPosts"
+    );
+
+    let openapi = create_openapi_json(
+      r#"{
+      "/users/{id}": {
+        "get": {
+          "summary": "Get a user",
+          "description": "Returns a single user",
+          "tags": ["users"],
+          "responses": {}
+        },
+        "put": {
+          "operationId": "Update-User",
+          "deprecated": true,
+          "responses": {}
+        }
+      },
+      "/users/by/{id}": {
+        "get": {
+          "operationId": "getUsersById",
+          "responses": {}
+        }
+      }
+    }"#,
+    );
+    let result = open_api_to_typescript(openapi, vec![], None);
+
+    let by_path = |method: &str, path: &str| {
+      result
+        .paths
+        .iter()
+        .find(|p| p.method == method && p.path == path)
+        .unwrap()
+    };
+
+    let get_user_by_path = by_path("get", "/users/by/{id}");
+    assert_eq!(get_user_by_path.name, "getUsersById");
+    assert_eq!(
+      get_user_by_path.operation_id,
+      Some("getUsersById".to_string())
+    );
+
+    let get_user = by_path("get", "/users/{id}");
+    assert_eq!(get_user.name, "getUsersById2");
+    assert_eq!(get_user.operation_id, None);
+    assert_eq!(get_user.summary, Some("Get a user".to_string()));
+    assert_eq!(
+      get_user.description,
+      Some("Returns a single user".to_string())
+    );
+    assert_eq!(get_user.tags, vec!["users".to_string()]);
+    assert!(!get_user.deprecated);
+
+    let update_user = by_path("put", "/users/{id}");
+    assert_eq!(update_user.name, "updateUser");
+    assert!(update_user.deprecated);
+
+    assert_eq!(to_camel_case("2fa-verify"), "_2faVerify");
+    assert_eq!(to_pascal_case("_2faVerify"), "_2faVerify");
+    assert_eq!(to_camel_case("_Private"), "_private");
+
+    // A synthesized name never takes the name of an `operationId`, wherever it is declared
+    let openapi: OpenAPI = serde_json::from_str(
+      r#"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {
+        "/users/{id}": { "get": { "responses": {} } },
+        "/users/by/{id}": { "get": { "operationId": "getUsersById", "responses": {} } }
+      }
+    }"#,
+    )
+    .unwrap();
+    let names: Vec<String> = open_api_to_typescript(openapi, vec![], None)
+      .paths
+      .into_iter()
+      .map(|path| path.name)
+      .collect();
+
+    assert_eq!(names, vec!["getUsersById2", "getUsersById"]);
+  }
+
+  #[test]
+  fn test_header_and_cookie_parameters() {
+    let openapi: OpenAPI = serde_json::from_value(json!({
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {
+        "/users/{id}": {
+          "parameters": [
+            { "in": "path", "name": "id", "required": true, "schema": { "type": "string" } },
+            { "in": "header", "name": "X-Request-Id", "schema": { "type": "string" } },
+            { "$ref": "#/components/parameters/Session" }
+          ],
+          "get": {
+            "parameters": [
+              { "in": "path", "name": "id", "required": true, "schema": { "type": "integer" } },
+              { "in": "query", "name": "id", "schema": { "type": "boolean" } },
+              { "in": "header", "name": "x-request-id", "required": true, "schema": { "type": "string" } }
+            ],
+            "responses": {}
+          }
+        }
+      },
+      "components": {
+        "parameters": {
+          "Session": { "in": "cookie", "name": "session", "required": true, "schema": { "type": "string" } }
+        }
+      }
+    }))
+    .unwrap();
+
+    let result = open_api_to_typescript(openapi, vec![], None);
+    let path = &result.paths[0];
+
+    assert_eq!(
+      path.path_parameters,
+      Some("{\n  id: number;\n}".to_string())
+    );
+    assert_eq!(
+      path.query_parameters,
+      Some("{\n  id?: boolean;\n}".to_string())
+    );
+    assert_eq!(
+      path.header_parameters,
+      Some("{\n  \"x-request-id\": string;\n}".to_string())
+    );
+    assert_eq!(
+      path.cookie_parameters,
+      Some("{\n  session: string;\n}".to_string())
+    );
+  }
+
+  #[test]
+  fn test_media_types() {
+    let openapi: OpenAPI = serde_json::from_str(
+      r#"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {
+        "/files": {
+          "post": {
+            "requestBody": {
+              "content": {
+                "multipart/form-data": {
+                  "schema": {
+                    "type": "object",
+                    "properties": {
+                      "name": { "type": "string" },
+                      "file": { "type": "string", "format": "binary" },
+                      "attachments": {
+                        "type": "array",
+                        "items": { "type": "string", "format": "binary" }
+                      }
+                    },
+                    "required": ["file"]
+                  }
+                },
+                "application/octet-stream": {}
+              }
+            },
+            "responses": {
+              "200": {
+                "description": "Created",
+                "content": {
+                  "text/plain": {},
+                  "application/vnd.api+json": {
+                    "schema": { "type": "object", "properties": { "id": { "type": "string" } } }
+                  }
+                }
+              },
+              "400": {
+                "description": "Invalid",
+                "content": {
+                  "application/problem+json; charset=utf-8": {
+                    "schema": { "type": "object", "properties": { "title": { "type": "string" } } }
+                  }
+                }
               }
+            }
+          }
+        }
+      }
+    }"#,
+    )
+    .unwrap();
+
+    let result = open_api_to_typescript(openapi, vec![], None);
+    let path = &result.paths[0];
+
+    let form_data =
+      "{\n  name?: string;\n  file: string | Blob;\n  attachments?: (string | Blob)[];\n}"
+        .to_string();
+    assert_eq!(path.request_body, Some(form_data.clone()));
+    assert_eq!(
+      path.request_body_content,
+      IndexMap::from([
+        ("application/octet-stream".to_string(), "Blob".to_string()),
+        ("multipart/form-data".to_string(), form_data),
+      ])
+    );
+
+    assert_eq!(path.responses["200"], "{\n  id?: string;\n}");
+    assert_eq!(path.responses["400"], "{\n  title?: string;\n}");
+    assert_eq!(path.response_content["200"]["text/plain"], "string");
+  }
+
+  #[test]
+  fn test_response_headers() {
+    let openapi: OpenAPI = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {
+        "/users": {
+          "get": {
+            "responses": {
+              "200": {
+                "description": "Users",
+                "headers": {
+                  "X-Total-Count": {
+                    "description": "Total number of users",
+                    "required": true,
+                    "schema": { "type": "integer" }
+                  },
+                  "Link": { "$ref": "#/components/headers/Link" },
+                  "Content-Type": { "schema": { "type": "string" } }
+                }
+              },
+              "204": { "description": "No users" }
+            }
+          }
+        }
+      },
+      "components": {
+        "headers": {
+          "Link": { "schema": { "type": "string" } }
+        }
+      }
+    }"##,
+    )
+    .unwrap();
+
+    let result = open_api_to_typescript(openapi, vec![], None);
+
+    assert_eq!(
+      result.paths[0].response_headers,
+      IndexMap::from([(
+        "200".to_string(),
+        "{\n  /**\n   * Total number of users\n   */\n  \"X-Total-Count\": number;\n  Link?: string;\n}"
+          .to_string()
+      )])
+    );
+  }
+
+  #[test]
+  fn test_head_trace_and_default_responses() {
+    let openapi = create_openapi_json(
+      r#"{
+      "/users": {
+        "head": { "responses": { "200": { "description": "Exists" } } },
+        "trace": { "responses": { "200": { "description": "Trace" } } },
+        "post": {
+          "responses": {
+            "400": {
+              "description": "Invalid",
+              "content": { "application/json": { "schema": { "type": "string" } } }
             },
-            {
-              "in": "query",
-              "name": "limit",
-              "required": false,
-              "schema": {
-                "type": "integer"
-              }
+            "default": {
+              "description": "Created",
+              "content": { "application/json": { "schema": { "type": "number" } } }
             }
-          ],
+          }
+        },
+        "get": {
           "responses": {
             "200": {
-              "description": "Success response",
+              "description": "Users",
+              "content": { "application/json": { "schema": { "type": "array", "items": { "type": "string" } } } }
+            },
+            "2XX": {
+              "description": "Other success",
+              "content": { "application/json": { "schema": { "type": "array", "items": { "type": "string" } } } }
+            },
+            "404": {
+              "description": "Not found",
+              "content": { "application/json": { "schema": { "type": "null" } } }
+            },
+            "default": {
+              "description": "Error",
               "content": {
                 "application/json": {
-                  "schema": {
-                    "type": "object"
-                  }
+                  "schema": { "type": "object", "properties": { "message": { "type": "string" } } }
                 }
               }
             }
@@ -339,181 +2451,516 @@ mod tests {
       }
     }"#,
     );
-    let result = open_api_to_typescript(openapi);
+    let result = open_api_to_typescript(openapi, vec![], None);
 
+    let methods: Vec<&str> = result.paths.iter().map(|p| p.method.as_str()).collect();
+    assert_eq!(methods, vec!["get", "post", "head", "trace"]);
+
+    let get_users = &result.paths[0];
     assert_eq!(
-      result.paths[0].query_parameters,
-      Some("{\n  limit?: number;\n  search: string;\n}".to_string())
+      get_users.responses.keys().collect::<Vec<&String>>(),
+      vec!["200", "2XX", "404", "default"]
     );
-    assert_eq!(result.paths[0].method, "get");
-    assert_eq!(result.paths[0].path, "/search");
-    assert!(result.paths[0].responses.contains_key("200"));
+    assert_eq!(get_users.success_response, Some("string[]".to_string()));
+    assert_eq!(
+      get_users.error_response,
+      Some("null | {\n  message?: string;\n}".to_string())
+    );
+
+    // Without a `2XX` response, `default` describes the success
+    let create_user = &result.paths[1];
+    assert_eq!(create_user.success_response, Some("number".to_string()));
+    assert_eq!(create_user.error_response, Some("string".to_string()));
+
+    assert_eq!(result.paths[2].success_response, None);
+    assert_eq!(result.paths[2].error_response, None);
   }
 
   #[test]
-  fn test_with_path_params() {
-    let openapi = create_openapi_json(
-      r#"{
-      "/users/{id}": {
-        "delete": {
-          "parameters": [
-            {
-              "in": "path",
-              "name": "id",
-              "required": true,
-              "schema": {
-                "type": "integer"
-              }
+  fn test_components_sections() {
+    let openapi: OpenAPI = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {
+        "/users": {
+          "post": {
+            "requestBody": { "$ref": "#/components/requestBodies/User" },
+            "responses": {
+              "201": { "$ref": "#/components/responses/User" },
+              "404": { "$ref": "#/components/responses/NotFound" }
             }
-          ],
-          "responses": {
-            "204": {
-              "description": "User deleted successfully"
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "User": { "type": "object", "properties": { "id": { "type": "string" } } }
+        },
+        "responses": {
+          "User": {
+            "description": "User",
+            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/User" } } }
+          },
+          "NotFound": {
+            "description": "Not found",
+            "content": {
+              "application/json": {
+                "schema": { "type": "object", "properties": { "message": { "type": "string" } } }
+              }
             }
           }
+        },
+        "parameters": {
+          "limit": { "in": "query", "name": "limit", "schema": { "type": "integer" } }
+        },
+        "requestBodies": {
+          "User": {
+            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/User" } } }
+          }
+        },
+        "headers": {
+          "X-Rate-Limit": { "schema": { "type": "integer" } },
+          "NotFoundHeader": { "schema": { "type": "string" } },
+          "NotFound": { "schema": { "type": "boolean" } }
+        },
+        "securitySchemes": {
+          "apiKey": { "type": "apiKey", "in": "header", "name": "X-API-Key" },
+          "basic": { "type": "http", "scheme": "basic" },
+          "bearer": { "type": "http", "scheme": "bearer" },
+          "oauth": { "type": "oauth2", "flows": {} }
         }
       }
-    }"#,
+    }"##,
+    )
+    .unwrap();
+
+    let result = open_api_to_typescript(openapi, vec![], None);
+
+    let components: Vec<(&str, ComponentKind, &str)> = result
+      .components
+      .iter()
+      .map(|c| (c.name.as_str(), c.kind, c.ts_type.as_str()))
+      .collect();
+
+    assert_eq!(
+      components,
+      vec![
+        ("User", ComponentKind::Schema, "{\n  id?: string;\n}"),
+        ("UserResponse", ComponentKind::Response, "User"),
+        (
+          "NotFound",
+          ComponentKind::Response,
+          "{\n  message?: string;\n}"
+        ),
+        ("Limit", ComponentKind::Parameter, "number"),
+        ("UserRequestBody", ComponentKind::RequestBody, "User"),
+        ("XRateLimit", ComponentKind::Header, "number"),
+        ("NotFoundHeader", ComponentKind::Header, "string"),
+        ("NotFoundHeader2", ComponentKind::Header, "boolean"),
+        (
+          "ApiKey",
+          ComponentKind::SecurityScheme,
+          "{\n  \"X-API-Key\": string;\n}"
+        ),
+        (
+          "Basic",
+          ComponentKind::SecurityScheme,
+          "{\n  username: string;\n  password: string;\n}"
+        ),
+        (
+          "Bearer",
+          ComponentKind::SecurityScheme,
+          "{\n  token: string;\n}"
+        ),
+        (
+          "Oauth",
+          ComponentKind::SecurityScheme,
+          "{\n  accessToken: string;\n}"
+        ),
+      ]
     );
-    let result = open_api_to_typescript(openapi);
 
+    let path = &result.paths[0];
+    assert_eq!(path.request_body, Some("UserRequestBody".to_string()));
+    assert_eq!(path.responses["201"], "UserResponse");
+    assert_eq!(path.responses["404"], "NotFound");
     assert_eq!(
-      result.paths[0].path_parameters,
-      Some("{\n  id: number;\n}".to_string())
+      path.response_content["404"]["application/json"],
+      "{\n  message?: string;\n}"
     );
-    assert_eq!(result.paths[0].method, "delete");
-    assert_eq!(result.paths[0].path, "/users/{id}");
   }
 
   #[test]
-  fn test_with_request_body() {
-    let openapi = create_openapi_json(
-      r#"{
-      "/users": {
-        "post": {
-          "requestBody": {
-            "required": true,
-            "content": {
-              "application/json": {
-                "schema": {
-                  "type": "object",
-                  "properties": {
-                    "name": {
-                      "type": "string"
-                    },
-                    "email": {
-                      "type": "string"
-                    }
-                  },
-                  "required": ["name", "email"]
-                }
+  fn test_filter_operations() {
+    let openapi: OpenAPI = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {
+        "/users": {
+          "get": {
+            "tags": ["users"],
+            "responses": {
+              "200": {
+                "description": "Users",
+                "content": { "application/json": { "schema": { "$ref": "#/components/schemas/User" } } }
               }
             }
           },
-          "responses": {
-            "201": {
-              "description": "User created successfully",
-              "content": {
-                "application/json": {
-                  "schema": {
-                    "type": "object",
-                    "properties": {
-                      "id": {
-                        "type": "integer"
-                      }
-                    }
-                  }
-                }
-              }
+          "post": { "tags": ["users"], "deprecated": true, "responses": {} }
+        },
+        "/admin/users/{id}": {
+          "delete": {
+            "tags": ["admin"],
+            "responses": {
+              "204": { "$ref": "#/components/responses/Deleted" }
             }
           }
         }
+      },
+      "components": {
+        "schemas": {
+          "User": { "type": "object", "properties": { "id": { "type": "string" } } },
+          "Admin": { "type": "string" }
+        },
+        "responses": {
+          "Deleted": {
+            "description": "Deleted",
+            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Admin" } } }
+          }
+        }
       }
-    }"#,
+    }"##,
+    )
+    .unwrap();
+
+    let filter = |filter: FilterOptions| {
+      let result = open_api_to_typescript(
+        openapi.clone(),
+        vec![],
+        Some(OpenApiOptions {
+          filter: Some(filter),
+          ..Default::default()
+        }),
+      );
+
+      (
+        result
+          .paths
+          .iter()
+          .map(|path| format!("{} {}", path.method, path.path))
+          .collect::<Vec<String>>(),
+        result
+          .components
+          .iter()
+          .map(|component| component.name.clone())
+          .collect::<Vec<String>>(),
+      )
+    };
+
+    assert_eq!(
+      filter(FilterOptions {
+        include_tags: Some(vec!["users".to_string()]),
+        exclude_deprecated: Some(true),
+        ..Default::default()
+      }),
+      (vec!["get /users".to_string()], vec!["User".to_string()])
     );
-    let result = open_api_to_typescript(openapi);
 
-    assert!(result.paths[0].request_body.is_some());
-    assert_eq!(result.paths[0].method, "post");
-    assert_eq!(result.paths[0].path, "/users");
-    assert!(result.paths[0].responses.contains_key("201"));
+    assert_eq!(
+      filter(FilterOptions {
+        include_paths: Some(vec!["/admin/**".to_string()]),
+        ..Default::default()
+      }),
+      (
+        vec!["delete /admin/users/{id}".to_string()],
+        vec!["Admin".to_string(), "Deleted".to_string()]
+      )
+    );
+
+    assert_eq!(
+      filter(FilterOptions {
+        exclude_methods: Some(vec!["GET".to_string(), "delete".to_string()]),
+        ..Default::default()
+      }),
+      (vec!["post /users".to_string()], vec![])
+    );
   }
 
   #[test]
-  fn test_multiple_responses() {
-    let openapi = create_openapi_json(
-      r#"{
-      "/users/{id}": {
-        "patch": {
-          "parameters": [
-            {
-              "in": "path",
-              "name": "id",
-              "required": true,
-              "schema": {
-                "type": "integer"
+  fn test_tree_shake() {
+    let mut openapi_json: Value = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {
+        "/users": {
+          "get": {
+            "responses": {
+              "200": {
+                "description": "Users",
+                "content": { "application/json": { "schema": { "$ref": "#/components/schemas/User" } } }
               }
             }
-          ],
-          "requestBody": {
-            "required": true,
-            "content": {
-              "application/json": {
-                "schema": {
-                  "type": "object",
-                  "properties": {
-                    "name": {
-                      "type": "string"
-                    }
-                  }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "User": { "type": "object", "properties": { "role": { "$ref": "#/components/schemas/Role" } } },
+          "Role": { "type": "string", "if": { "minLength": 1 } },
+          "Legacy": { "type": "object", "propertyNames": { "pattern": "^x-" } }
+        },
+        "parameters": {
+          "limit": { "in": "query", "name": "limit", "schema": { "$ref": "#/components/schemas/Missing" } }
+        }
+      }
+    }"##,
+    )
+    .unwrap();
+    let diagnostics = normalize_open_api(&mut openapi_json);
+    let openapi: OpenAPI = serde_json::from_value(openapi_json).unwrap();
+
+    let result = open_api_to_typescript(openapi.clone(), diagnostics.clone(), None);
+    assert_eq!(result.components.len(), 4);
+    assert!(result.removed_components.is_empty());
+    assert_eq!(result.diagnostics.len(), 3);
+
+    let result = open_api_to_typescript(
+      openapi,
+      diagnostics,
+      Some(OpenApiOptions {
+        tree_shake: Some(true),
+        ..Default::default()
+      }),
+    );
+
+    assert_eq!(
+      result
+        .components
+        .iter()
+        .map(|component| component.name.as_str())
+        .collect::<Vec<&str>>(),
+      vec!["User", "Role"]
+    );
+    assert_eq!(result.removed_components, vec!["Legacy", "Limit"]);
+    // Removed components aren't converted, and their ignored keywords aren't reported
+    assert_eq!(
+      result
+        .diagnostics
+        .iter()
+        .map(|d| (d.pointer.as_str(), d.code.as_str()))
+        .collect::<Vec<_>>(),
+      vec![("#/components/schemas/Role", "ignored-keyword")]
+    );
+  }
+
+  #[test]
+  fn test_diagnostics() {
+    let openapi: OpenAPI = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {
+        "/users/{id}": {
+          "get": {
+            "parameters": [
+              { "$ref": "#/components/parameters/Missing" },
+              { "name": "fields", "in": "query", "schema": { "type": "file" } }
+            ],
+            "responses": {
+              "200": {
+                "description": "User",
+                "headers": { "X-Avatar": { "schema": { "type": "file" } } },
+                "content": {
+                  "application/json": { "schema": { "$ref": "#/components/schemas/Missing" } },
+                  "application/xml": {}
                 }
               }
             }
+          }
+        },
+        "/legacy": { "$ref": "#/paths/~1users~1{id}" }
+      },
+      "components": {
+        "schemas": {
+          "User": { "type": "object", "properties": { "avatar": { "type": "file" } } }
+        }
+      }
+    }"##,
+    )
+    .unwrap();
+
+    let result = open_api_to_typescript(openapi, vec![], None);
+
+    let diagnostics: Vec<(&str, &str)> = result
+      .diagnostics
+      .iter()
+      .map(|d| (d.pointer.as_str(), d.code.as_str()))
+      .collect();
+
+    assert_eq!(
+      diagnostics,
+      vec![
+        ("#/paths/~1legacy", "unresolved-reference"),
+        (
+          "#/components/schemas/User/properties/avatar",
+          "unknown-type"
+        ),
+        (
+          "#/paths/~1users~1{id}/get/parameters/0",
+          "unresolved-reference"
+        ),
+        (
+          "#/paths/~1users~1{id}/get/parameters/1/schema",
+          "unknown-type"
+        ),
+        (
+          "#/paths/~1users~1{id}/get/responses/200/content/application~1json/schema",
+          "unresolved-reference"
+        ),
+        (
+          "#/paths/~1users~1{id}/get/responses/200/content/application~1xml",
+          "unsupported-media-type"
+        ),
+        (
+          "#/paths/~1users~1{id}/get/responses/200/headers/X-Avatar/schema",
+          "unknown-type"
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_type_guards() {
+    let openapi: OpenAPI = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {},
+      "components": {
+        "schemas": {
+          "Pet": {
+            "oneOf": [{ "$ref": "#/components/schemas/Cat" }, { "$ref": "#/components/schemas/Dog" }]
           },
-          "responses": {
-            "200": {
-              "description": "Success response",
-              "content": {
-                "application/json": {
-                  "schema": {
-                    "type": "object",
-                    "properties": {
-                      "id": {
-                        "type": "integer"
-                      },
-                      "name": {
-                        "type": "string"
-                      }
-                    }
-                  }
-                }
-              }
-            },
-            "400": {
-              "description": "Bad request",
-              "content": {
-                "application/json": {
-                  "schema": {
-                    "type": "object",
-                    "properties": {
-                      "error": {
-                        "type": "string"
-                      }
-                    }
-                  }
-                }
+          "Cat": { "type": "object", "properties": { "lives": { "type": "integer" } } },
+          "Dog": { "type": "object", "properties": { "barks": { "type": "boolean" } } }
+        },
+        "responses": {
+          "PetResponse": {
+            "description": "Pet",
+            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } }
+          }
+        }
+      }
+    }"##,
+    )
+    .unwrap();
+
+    let result = open_api_to_typescript(
+      openapi,
+      vec![],
+      Some(OpenApiOptions {
+        type_guards: Some(true),
+        ..Default::default()
+      }),
+    );
+
+    let type_guards: Vec<(&str, Option<&str>)> = result
+      .components
+      .iter()
+      .map(|component| (component.name.as_str(), component.type_guard.as_deref()))
+      .collect();
+
+    assert_eq!(
+      type_guards,
+      vec![
+        (
+          "Pet",
+          Some("export function isPet(value: unknown): value is Pet {\n  return isCat(value) || isDog(value);\n}")
+        ),
+        (
+          "Cat",
+          Some("export function isCat(value: unknown): value is Cat {\n  const object = value as Record<string, unknown>;\n  return (\n    typeof value === \"object\" &&\n    value !== null &&\n    !Array.isArray(value) &&\n    (object[\"lives\"] === undefined || typeof object[\"lives\"] === \"number\")\n  );\n}")
+        ),
+        (
+          "Dog",
+          Some("export function isDog(value: unknown): value is Dog {\n  const object = value as Record<string, unknown>;\n  return (\n    typeof value === \"object\" &&\n    value !== null &&\n    !Array.isArray(value) &&\n    (object[\"barks\"] === undefined || typeof object[\"barks\"] === \"boolean\")\n  );\n}")
+        ),
+        ("PetResponse", None),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_mock_responses() {
+    let openapi: OpenAPI = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {
+        "/users/{id}": {
+          "get": {
+            "responses": {
+              "200": { "$ref": "#/components/responses/UserResponse" },
+              "404": {
+                "description": "Not found",
+                "content": { "application/json": { "example": { "message": "Not found" } } }
+              },
+              "204": { "description": "No content" }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "User": {
+            "type": "object",
+            "required": ["id", "role"],
+            "properties": {
+              "id": { "type": "integer", "minimum": 1, "maximum": 1 },
+              "role": { "type": "string", "enum": ["admin"] }
+            }
+          }
+        },
+        "responses": {
+          "UserResponse": {
+            "description": "User",
+            "content": {
+              "application/json": {
+                "schema": { "$ref": "#/components/schemas/User" },
+                "examples": { "stale": { "value": { "id": "1", "role": "admin" } } }
               }
             }
           }
         }
       }
-    }"#,
-    );
-    let result = open_api_to_typescript(openapi);
+    }"##,
+    )
+    .unwrap();
 
-    assert_eq!(result.paths[0].responses.len(), 2);
-    assert!(result.paths[0].responses.contains_key("200"));
-    assert!(result.paths[0].responses.contains_key("400"));
-    assert_eq!(result.paths[0].method, "patch");
+    let mock_responses = |mocks: Option<MockOptions>| {
+      open_api_to_typescript(
+        openapi.clone(),
+        vec![],
+        Some(OpenApiOptions {
+          mocks,
+          ..Default::default()
+        }),
+      )
+      .paths[0]
+        .mock_responses
+        .clone()
+    };
+
+    assert!(mock_responses(None).is_empty());
+    assert_eq!(
+      mock_responses(Some(MockOptions::default())),
+      IndexMap::from([
+        ("200".to_string(), r#"{"id":1,"role":"admin"}"#.to_string()),
+        ("404".to_string(), r#"{"message":"Not found"}"#.to_string()),
+      ])
+    );
   }
 }
@@ -0,0 +1,360 @@
+use crate::diagnostics::{pointer_join, Diagnostic, Diagnostics};
+use crate::json_schema_to_typescript::SchemaContext;
+use crate::open_api_to_typescript::{merge_parameters, parameter_location};
+
+use openapiv3::{OpenAPI, ReferenceOr};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Local `$ref`s must point to an existing definition, external ones aren't checked.
+fn validate_references(document: &Value, value: &Value, pointer: &str, diagnostics: &Diagnostics) {
+  match value {
+    Value::Object(map) => {
+      if let Some(Value::String(reference)) = map.get("$ref") {
+        let target = reference.strip_prefix('#');
+
+        if target.is_some_and(|target| document.pointer(target).is_none()) {
+          diagnostics.error(
+            pointer,
+            "unresolved-reference",
+            format!("`{}` doesn't exist in the document", reference),
+          );
+        }
+      }
+
+      for (key, value) in map {
+        if !matches!(key.as_str(), "example" | "examples") {
+          validate_references(document, value, &pointer_join(pointer, &[key]), diagnostics);
+        }
+      }
+    }
+    Value::Array(values) => values.iter().enumerate().for_each(|(index, value)| {
+      validate_references(
+        document,
+        value,
+        &pointer_join(pointer, &[&index.to_string()]),
+        diagnostics,
+      )
+    }),
+    _ => {}
+  }
+}
+
+/// Names of the `{param}` segments of a path template.
+fn path_template_parameters(path: &str) -> Vec<&str> {
+  path
+    .split('{')
+    .skip(1)
+    .filter_map(|segment| segment.split_once('}').map(|(name, _)| name))
+    .collect()
+}
+
+/// Every templated path segment needs a path parameter, and operation ids must be unique.
+fn validate_operations(open_api: &OpenAPI, diagnostics: &Diagnostics) {
+  let context = SchemaContext {
+    components: open_api.components.as_ref(),
+    ..Default::default()
+  };
+  let mut operation_ids: BTreeMap<&str, String> = BTreeMap::new();
+
+  for (path, path_item) in open_api.paths.iter() {
+    let ReferenceOr::Item(path_item) = path_item else {
+      continue;
+    };
+
+    for (method, operation) in path_item.iter() {
+      let pointer = pointer_join("#/paths", &[path, method]);
+      let parameters = merge_parameters(
+        &path_item.parameters,
+        &context,
+        &operation.parameters,
+        &context,
+      );
+
+      for name in path_template_parameters(path) {
+        let is_declared = parameters.iter().any(|(_, parameter)| {
+          parameter_location(parameter) == "path" && parameter.parameter_data_ref().name == name
+        });
+
+        if !is_declared {
+          diagnostics.error(
+            &pointer,
+            "missing-path-parameter",
+            format!("`{{{}}}` has no matching path parameter", name),
+          );
+        }
+      }
+
+      if let Some(operation_id) = &operation.operation_id {
+        match operation_ids.get(operation_id.as_str()) {
+          Some(first_pointer) => diagnostics.error(
+            &pointer_join(&pointer, &["operationId"]),
+            "duplicate-operation-id",
+            format!(
+              "`{}` is already the operationId of `{}`",
+              operation_id, first_pointer
+            ),
+          ),
+          None => {
+            operation_ids.insert(operation_id, pointer);
+          }
+        }
+      }
+    }
+  }
+}
+
+fn matches_type(value: &Value, typ: &str) -> bool {
+  match typ {
+    "string" => value.is_string(),
+    "number" => value.is_number(),
+    "integer" => value.is_i64() || value.is_u64(),
+    "boolean" => value.is_boolean(),
+    "array" => value.is_array(),
+    "object" => value.is_object(),
+    "null" => value.is_null(),
+    // Unknown types are reported when converting
+    _ => true,
+  }
+}
+
+/// Required properties must be declared next to them, and enum values must match the type.
+fn validate_schema(schema: &Value, pointer: &str, diagnostics: &Diagnostics) {
+  let Value::Object(map) = schema else {
+    return;
+  };
+
+  // Without `properties`, `required` usually completes another `allOf` member.
+  if let (Some(Value::Array(required)), Some(Value::Object(properties))) =
+    (map.get("required"), map.get("properties"))
+  {
+    for name in required.iter().filter_map(Value::as_str) {
+      if !properties.contains_key(name) {
+        diagnostics.warning(
+          &pointer_join(pointer, &["required"]),
+          "undeclared-required-property",
+          format!("`{}` is required but isn't declared in `properties`", name),
+        );
+      }
+    }
+  }
+
+  if let Some(Value::String(typ)) = map.get("type") {
+    let is_nullable = map.get("nullable") == Some(&Value::Bool(true));
+    let enum_values = match map.get("enum") {
+      Some(Value::Array(values)) => values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| (pointer_join(pointer, &["enum", &index.to_string()]), value))
+        .collect(),
+      _ => vec![],
+    };
+    let const_value = map
+      .get("const")
+      .map(|value| (pointer_join(pointer, &["const"]), value));
+
+    for (pointer, value) in enum_values.into_iter().chain(const_value) {
+      if !(matches_type(value, typ) || value.is_null() && is_nullable) {
+        diagnostics.error(
+          &pointer,
+          "enum-type-mismatch",
+          format!("`{}` isn't a valid `{}` value", value, typ),
+        );
+      }
+    }
+  }
+
+  if let Some(Value::Object(properties)) = map.get("properties") {
+    for (name, property) in properties {
+      validate_schema(
+        property,
+        &pointer_join(pointer, &["properties", name]),
+        diagnostics,
+      );
+    }
+  }
+
+  for key in ["items", "additionalProperties", "not"] {
+    if let Some(value) = map.get(key) {
+      validate_schema(value, &pointer_join(pointer, &[key]), diagnostics);
+    }
+  }
+
+  for key in ["allOf", "anyOf", "oneOf"] {
+    if let Some(Value::Array(values)) = map.get(key) {
+      for (index, value) in values.iter().enumerate() {
+        validate_schema(
+          value,
+          &pointer_join(pointer, &[key, &index.to_string()]),
+          diagnostics,
+        );
+      }
+    }
+  }
+}
+
+fn validate_schemas(value: &Value, pointer: &str, diagnostics: &Diagnostics) {
+  match value {
+    Value::Object(map) => {
+      for (key, value) in map {
+        let pointer = pointer_join(pointer, &[key]);
+
+        match key.as_str() {
+          "schema" => validate_schema(value, &pointer, diagnostics),
+          "example" | "examples" => {}
+          _ => validate_schemas(value, &pointer, diagnostics),
+        }
+      }
+    }
+    Value::Array(values) => values.iter().enumerate().for_each(|(index, value)| {
+      validate_schemas(
+        value,
+        &pointer_join(pointer, &[&index.to_string()]),
+        diagnostics,
+      )
+    }),
+    _ => {}
+  }
+}
+
+/// Structural issues of a document: dangling `$ref`s, path templates without path parameters,
+/// duplicate operation ids, undeclared required properties and enum values not matching their type.
+/// `document` is the document as written, before [`normalize_open_api`], so that the pointers name
+/// its own nodes.
+///
+/// [`normalize_open_api`]: crate::open_api_to_typescript::normalize_open_api
+pub fn validate_open_api(open_api: &OpenAPI, document: &Value) -> Vec<Diagnostic> {
+  let diagnostics = Diagnostics::default();
+
+  validate_references(document, document, "#", &diagnostics);
+  validate_operations(open_api, &diagnostics);
+
+  if let Some(Value::Object(schemas)) = document.pointer("/components/schemas") {
+    for (name, schema) in schemas {
+      validate_schema(
+        schema,
+        &pointer_join("#/components/schemas", &[name]),
+        &diagnostics,
+      );
+    }
+  }
+
+  validate_schemas(document, "#", &diagnostics);
+
+  diagnostics.into_vec()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::diagnostics::Severity;
+  use crate::open_api_to_typescript::normalize_open_api;
+
+  #[test]
+  fn test_path_template_parameters() {
+    assert_eq!(
+      path_template_parameters("/users/{userId}/posts/{postId}"),
+      vec!["userId", "postId"]
+    );
+    assert!(path_template_parameters("/users").is_empty());
+  }
+
+  #[test]
+  fn test_validate_open_api() {
+    let document: Value = serde_json::from_str(
+      r##"{
+      "openapi": "3.0.0",
+      "info": { "title": "Test API", "version": "1.0.0" },
+      "paths": {
+        "/users/{id}": {
+          "get": {
+            "operationId": "getUser",
+            "responses": {
+              "200": {
+                "description": "User",
+                "content": {
+                  "application/json": { "schema": { "$ref": "#/components/schemas/Missing" } }
+                }
+              }
+            }
+          }
+        },
+        "/users/{id}/posts": {
+          "parameters": [{ "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }],
+          "get": {
+            "operationId": "getUser",
+            "responses": { "200": { "description": "Posts" } }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "User": {
+            "type": "object",
+            "required": ["id", "name"],
+            "properties": {
+              "id": { "type": "string" },
+              "role": { "type": "string", "enum": ["admin", 1] },
+              "manager": { "$ref": "#/components/schemas/Manager", "description": "Manager" },
+              "kind": { "type": "string", "const": 1 }
+            }
+          }
+        }
+      }
+    }"##,
+    )
+    .unwrap();
+    let mut normalized = document.clone();
+    normalize_open_api(&mut normalized);
+    let open_api: OpenAPI = serde_json::from_value(normalized).unwrap();
+
+    let result = validate_open_api(&open_api, &document);
+
+    let diagnostics: Vec<(&str, Severity, &str)> = result
+      .iter()
+      .map(|d| (d.pointer.as_str(), d.severity, d.code.as_str()))
+      .collect();
+
+    assert_eq!(
+      diagnostics,
+      vec![
+        (
+          "#/paths/~1users~1{id}/get/responses/200/content/application~1json/schema",
+          Severity::Error,
+          "unresolved-reference"
+        ),
+        // The `$ref` as written, not the `allOf` it is normalized to
+        (
+          "#/components/schemas/User/properties/manager",
+          Severity::Error,
+          "unresolved-reference"
+        ),
+        (
+          "#/paths/~1users~1{id}/get",
+          Severity::Error,
+          "missing-path-parameter"
+        ),
+        (
+          "#/paths/~1users~1{id}~1posts/get/operationId",
+          Severity::Error,
+          "duplicate-operation-id"
+        ),
+        (
+          "#/components/schemas/User/required",
+          Severity::Warning,
+          "undeclared-required-property"
+        ),
+        (
+          "#/components/schemas/User/properties/role/enum/1",
+          Severity::Error,
+          "enum-type-mismatch"
+        ),
+        (
+          "#/components/schemas/User/properties/kind/const",
+          Severity::Error,
+          "enum-type-mismatch"
+        ),
+      ]
+    );
+  }
+}
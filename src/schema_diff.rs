@@ -0,0 +1,541 @@
+use crate::diagnostics::pointer_join;
+use crate::json_schema_to_typescript::{
+  resolve_schema_reference, schema_to_typescript_with_context, SchemaContext, SchemaLike,
+};
+
+use napi_derive::napi;
+use openapiv3::{AnySchema, Components, ReferenceOr, Schema, SchemaKind, Type};
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq)]
+#[napi(object)]
+pub struct SchemaChange {
+  /// JSON pointer in the new schema, or in the old one for removals. Changes of a referenced schema
+  /// point to its definition
+  pub pointer: String,
+  /// Stable identifier, like `property-removed`
+  pub code: String,
+  pub message: String,
+  /// Data valid against the old schema is valid against the new one
+  pub backward: bool,
+  /// Data valid against the new schema is valid against the old one
+  pub forward: bool,
+}
+
+/// Overall compatibility of the changes, like schema registries.
+#[derive(Debug, PartialEq)]
+#[napi(string_enum = "lowercase")]
+pub enum Compatibility {
+  /// Backward and forward
+  Full,
+  /// Data valid against the old schema is valid against the new one
+  Backward,
+  /// Data valid against the new schema is valid against the old one
+  Forward,
+  None,
+}
+
+#[derive(Debug)]
+#[napi(object)]
+pub struct SchemaDiffOutput {
+  pub compatibility: Compatibility,
+  pub changes: Vec<SchemaChange>,
+}
+
+/// Compares two versions of a schema, following references into their own components, or to their
+/// root for `#`.
+pub(crate) struct SchemaDiff<'a> {
+  old: SchemaContext<'a>,
+  new: SchemaContext<'a>,
+  old_root: Option<&'a Schema>,
+  new_root: Option<&'a Schema>,
+  visited: Vec<(String, String)>,
+  pub changes: Vec<SchemaChange>,
+}
+
+fn schema_type(schema: &Schema) -> Option<&str> {
+  match &schema.schema_kind {
+    SchemaKind::Type(Type::String(_)) => Some("string"),
+    SchemaKind::Type(Type::Number(_)) => Some("number"),
+    SchemaKind::Type(Type::Integer(_)) => Some("integer"),
+    SchemaKind::Type(Type::Boolean(_)) => Some("boolean"),
+    SchemaKind::Type(Type::Object(_)) => Some("object"),
+    SchemaKind::Type(Type::Array(_)) => Some("array"),
+    SchemaKind::Any(AnySchema { typ: Some(typ), .. }) => Some(typ),
+    SchemaKind::Any(any_schema) if !any_schema.properties.is_empty() => Some("object"),
+    _ => None,
+  }
+}
+
+fn enum_values(schema: &Schema) -> Vec<Value> {
+  match serde_json::to_value(&schema.schema_kind) {
+    Ok(Value::Object(mut map)) => match map.remove("enum") {
+      Some(Value::Array(values)) => values,
+      _ => vec![],
+    },
+    _ => vec![],
+  }
+}
+
+type Properties<'s> = (
+  Vec<(&'s String, &'s ReferenceOr<Box<Schema>>)>,
+  &'s [String],
+);
+
+fn object_properties(schema: &Schema) -> Option<Properties<'_>> {
+  match &schema.schema_kind {
+    SchemaKind::Type(Type::Object(object)) => {
+      Some((object.properties.iter().collect(), &object.required))
+    }
+    SchemaKind::Any(any_schema) if schema_type(schema) == Some("object") => {
+      Some((any_schema.properties.iter().collect(), &any_schema.required))
+    }
+    _ => None,
+  }
+}
+
+fn array_items(schema: &Schema) -> Option<&ReferenceOr<Box<Schema>>> {
+  match &schema.schema_kind {
+    SchemaKind::Type(Type::Array(array)) => array.items.as_ref(),
+    SchemaKind::Any(any_schema) => any_schema.items.as_ref(),
+    _ => None,
+  }
+}
+
+fn to_schema<T: SchemaLike>(schema: &ReferenceOr<T>) -> ReferenceOr<Schema> {
+  match schema {
+    ReferenceOr::Item(schema) => ReferenceOr::Item(schema.as_schema().clone()),
+    ReferenceOr::Reference { reference } => ReferenceOr::Reference {
+      reference: reference.clone(),
+    },
+  }
+}
+
+fn format_values(values: &[&Value]) -> String {
+  values
+    .iter()
+    .map(|value| value.to_string())
+    .collect::<Vec<String>>()
+    .join(", ")
+}
+
+impl<'a> SchemaDiff<'a> {
+  pub fn new(old: Option<&'a Components>, new: Option<&'a Components>) -> Self {
+    SchemaDiff {
+      old: SchemaContext {
+        components: old,
+        ..Default::default()
+      },
+      new: SchemaContext {
+        components: new,
+        ..Default::default()
+      },
+      old_root: None,
+      new_root: None,
+      visited: vec![],
+      changes: vec![],
+    }
+  }
+
+  fn push(&mut self, pointer: &str, code: &str, message: String, compatibility: (bool, bool)) {
+    let (backward, forward) = compatibility;
+    self.changes.push(SchemaChange {
+      pointer: pointer.to_string(),
+      code: code.to_string(),
+      message,
+      backward,
+      forward,
+    });
+  }
+
+  fn resolve<'s, T: SchemaLike>(
+    schema: &'s ReferenceOr<T>,
+    context: &SchemaContext<'s>,
+    root: Option<&'s Schema>,
+  ) -> Option<&'s Schema> {
+    match schema {
+      ReferenceOr::Item(schema) => Some(schema.as_schema()),
+      ReferenceOr::Reference { reference } if reference == "#" => root,
+      ReferenceOr::Reference { reference } => resolve_schema_reference(reference, context),
+    }
+  }
+
+  /// Compares the TypeScript types of schemas that can't be compared keyword by keyword.
+  fn compare_ts_types<T: SchemaLike, U: SchemaLike>(
+    &mut self,
+    old: &ReferenceOr<T>,
+    new: &ReferenceOr<U>,
+    pointer: &str,
+  ) {
+    let old_ts_type =
+      schema_to_typescript_with_context(to_schema(old), None, &self.old).to_string();
+    let new_ts_type =
+      schema_to_typescript_with_context(to_schema(new), None, &self.new).to_string();
+
+    if old_ts_type != new_ts_type {
+      self.push(
+        pointer,
+        "type-changed",
+        format!("Type changed from `{}` to `{}`", old_ts_type, new_ts_type),
+        (false, false),
+      );
+    }
+  }
+
+  pub fn compare<T: SchemaLike, U: SchemaLike>(
+    &mut self,
+    old: &ReferenceOr<T>,
+    new: &ReferenceOr<U>,
+    pointer: &str,
+  ) {
+    // Recursive schemas are compared once per pair of references.
+    if let (
+      ReferenceOr::Reference { reference: old_ref },
+      ReferenceOr::Reference { reference: new_ref },
+    ) = (old, new)
+    {
+      let pair = (old_ref.clone(), new_ref.clone());
+      if self.visited.contains(&pair) {
+        return;
+      }
+      self.visited.push(pair);
+    }
+
+    let (old_context, new_context) = (self.old, self.new);
+    let (Some(old_schema), Some(new_schema)) = (
+      Self::resolve(old, &old_context, self.old_root),
+      Self::resolve(new, &new_context, self.new_root),
+    ) else {
+      return self.compare_ts_types(old, new, pointer);
+    };
+
+    // Changes of a referenced schema are reported at its definition, rather than at each use.
+    let pointer = match new {
+      ReferenceOr::Reference { reference } if reference.starts_with('#') => reference.as_str(),
+      _ => pointer,
+    };
+
+    match (schema_type(old_schema), schema_type(new_schema)) {
+      (Some(old_type), Some(new_type)) if old_type == new_type => {}
+      (Some("integer"), Some("number")) => self.push(
+        pointer,
+        "type-widened",
+        "Type widened from `integer` to `number`".to_string(),
+        (true, false),
+      ),
+      (Some("number"), Some("integer")) => self.push(
+        pointer,
+        "type-narrowed",
+        "Type narrowed from `number` to `integer`".to_string(),
+        (false, true),
+      ),
+      _ => return self.compare_ts_types(old, new, pointer),
+    }
+
+    match (
+      old_schema.schema_data.nullable,
+      new_schema.schema_data.nullable,
+    ) {
+      (false, true) => self.push(
+        pointer,
+        "nullable-added",
+        "Became nullable".to_string(),
+        (true, false),
+      ),
+      (true, false) => self.push(
+        pointer,
+        "nullable-removed",
+        "Isn't nullable anymore".to_string(),
+        (false, true),
+      ),
+      _ => {}
+    }
+
+    self.compare_enums(old_schema, new_schema, pointer);
+
+    if let (Some(old_properties), Some(new_properties)) =
+      (object_properties(old_schema), object_properties(new_schema))
+    {
+      self.compare_properties(old_properties, new_properties, pointer);
+    }
+
+    if let (Some(old_items), Some(new_items)) = (array_items(old_schema), array_items(new_schema)) {
+      self.compare(old_items, new_items, &pointer_join(pointer, &["items"]));
+    }
+  }
+
+  /// An empty enum allows any value of the type.
+  fn compare_enums(&mut self, old_schema: &Schema, new_schema: &Schema, pointer: &str) {
+    let old_values = enum_values(old_schema);
+    let new_values = enum_values(new_schema);
+    let pointer = pointer_join(pointer, &["enum"]);
+
+    match (old_values.is_empty(), new_values.is_empty()) {
+      (true, true) => {}
+      (true, false) => self.push(
+        &pointer,
+        "enum-narrowed",
+        format!(
+          "Restricted to {}",
+          format_values(&new_values.iter().collect::<Vec<_>>())
+        ),
+        (false, true),
+      ),
+      (false, true) => self.push(
+        &pointer,
+        "enum-widened",
+        "Isn't restricted to an enum anymore".to_string(),
+        (true, false),
+      ),
+      (false, false) => {
+        let removed: Vec<&Value> = old_values
+          .iter()
+          .filter(|value| !new_values.contains(value))
+          .collect();
+        let added: Vec<&Value> = new_values
+          .iter()
+          .filter(|value| !old_values.contains(value))
+          .collect();
+
+        if !removed.is_empty() {
+          self.push(
+            &pointer,
+            "enum-narrowed",
+            format!("Removed enum values {}", format_values(&removed)),
+            (false, true),
+          );
+        }
+        if !added.is_empty() {
+          self.push(
+            &pointer,
+            "enum-widened",
+            format!("Added enum values {}", format_values(&added)),
+            (true, false),
+          );
+        }
+      }
+    }
+  }
+
+  fn compare_properties(&mut self, old: Properties, new: Properties, pointer: &str) {
+    let (old_properties, old_required) = old;
+    let (new_properties, new_required) = new;
+
+    for (name, old_property) in &old_properties {
+      let property_pointer = pointer_join(pointer, &["properties", name]);
+      let was_required = old_required.contains(name);
+      let is_required = new_required.contains(name);
+
+      let Some((_, new_property)) = new_properties.iter().find(|(new_name, _)| new_name == name)
+      else {
+        self.push(
+          &property_pointer,
+          "property-removed",
+          format!("Property `{}` removed", name),
+          (true, !was_required),
+        );
+        continue;
+      };
+
+      match (was_required, is_required) {
+        (false, true) => self.push(
+          &property_pointer,
+          "property-required",
+          format!("Property `{}` became required", name),
+          (false, true),
+        ),
+        (true, false) => self.push(
+          &property_pointer,
+          "property-optional",
+          format!("Property `{}` became optional", name),
+          (true, false),
+        ),
+        _ => {}
+      }
+
+      self.compare(*old_property, *new_property, &property_pointer);
+    }
+
+    for (name, _) in &new_properties {
+      if old_properties.iter().any(|(old_name, _)| old_name == name) {
+        continue;
+      }
+
+      let is_required = new_required.contains(name);
+      self.push(
+        &pointer_join(pointer, &["properties", name]),
+        "property-added",
+        format!(
+          "{} property `{}` added",
+          if is_required { "Required" } else { "Optional" },
+          name
+        ),
+        (!is_required, true),
+      );
+    }
+  }
+}
+
+/// Changes between two versions of a standalone schema, along with their overall compatibility.
+pub fn diff_schemas(old: &Schema, new: &Schema) -> SchemaDiffOutput {
+  let mut schema_diff = SchemaDiff {
+    old_root: Some(old),
+    new_root: Some(new),
+    // The root is being compared, its `#` references are only compared once.
+    visited: vec![("#".to_string(), "#".to_string())],
+    ..SchemaDiff::new(None, None)
+  };
+  schema_diff.compare(
+    &ReferenceOr::Item(old.clone()),
+    &ReferenceOr::Item(new.clone()),
+    "#",
+  );
+
+  let changes = schema_diff.changes;
+  let backward = changes.iter().all(|change| change.backward);
+  let forward = changes.iter().all(|change| change.forward);
+
+  SchemaDiffOutput {
+    compatibility: match (backward, forward) {
+      (true, true) => Compatibility::Full,
+      (true, false) => Compatibility::Backward,
+      (false, true) => Compatibility::Forward,
+      (false, false) => Compatibility::None,
+    },
+    changes,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_schema_diff() {
+    let old: ReferenceOr<Schema> = serde_json::from_str(
+      r#"{
+        "type": "object",
+        "required": ["id", "name"],
+        "properties": {
+          "id": { "type": "integer" },
+          "name": { "type": "string" },
+          "role": { "type": "string", "enum": ["admin", "user"] },
+          "tags": { "type": "array", "items": { "type": "string" } }
+        }
+      }"#,
+    )
+    .unwrap();
+    let new: ReferenceOr<Schema> = serde_json::from_str(
+      r#"{
+        "type": "object",
+        "required": ["id", "email"],
+        "properties": {
+          "id": { "type": "number" },
+          "email": { "type": "string" },
+          "role": { "type": "string", "enum": ["admin", "guest"] },
+          "tags": { "type": "array", "items": { "type": "number" } }
+        }
+      }"#,
+    )
+    .unwrap();
+
+    let mut diff = SchemaDiff::new(None, None);
+    diff.compare(&old, &new, "#");
+
+    let changes: Vec<(&str, &str, bool, bool)> = diff
+      .changes
+      .iter()
+      .map(|change| {
+        (
+          change.pointer.as_str(),
+          change.code.as_str(),
+          change.backward,
+          change.forward,
+        )
+      })
+      .collect();
+
+    assert_eq!(
+      changes,
+      vec![
+        ("#/properties/id", "type-widened", true, false),
+        ("#/properties/name", "property-removed", true, false),
+        ("#/properties/role/enum", "enum-narrowed", false, true),
+        ("#/properties/role/enum", "enum-widened", true, false),
+        ("#/properties/tags/items", "type-changed", false, false),
+        ("#/properties/email", "property-added", false, true),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_diff_schemas() {
+    let old: Schema = serde_json::from_str(
+      r##"{
+        "type": "object",
+        "properties": {
+          "kind": { "type": "string", "enum": ["created", "deleted"] },
+          "children": { "type": "array", "items": { "$ref": "#" } }
+        }
+      }"##,
+    )
+    .unwrap();
+    let added_property: Schema = serde_json::from_str(
+      r##"{
+        "type": "object",
+        "properties": {
+          "kind": { "type": "string", "enum": ["created", "deleted"] },
+          "children": { "type": "array", "items": { "$ref": "#" } },
+          "source": { "type": "string" }
+        }
+      }"##,
+    )
+    .unwrap();
+    let added_enum_value: Schema = serde_json::from_str(
+      r##"{
+        "type": "object",
+        "properties": {
+          "kind": { "type": "string", "enum": ["created", "deleted", "updated"] },
+          "children": { "type": "array", "items": { "$ref": "#" } }
+        }
+      }"##,
+    )
+    .unwrap();
+
+    let changes = |old: &Schema, new: &Schema| -> Vec<(String, String)> {
+      diff_schemas(old, new)
+        .changes
+        .into_iter()
+        .map(|change| (change.pointer, change.code))
+        .collect()
+    };
+    let change = |pointer: &str, code: &str| (pointer.to_string(), code.to_string());
+
+    assert_eq!(diff_schemas(&old, &old).compatibility, Compatibility::Full);
+    assert!(diff_schemas(&old, &old).changes.is_empty());
+    assert_eq!(
+      diff_schemas(&old, &added_property).compatibility,
+      Compatibility::Full
+    );
+    assert_eq!(
+      changes(&old, &added_property),
+      vec![change("#/properties/source", "property-added")]
+    );
+    assert_eq!(
+      diff_schemas(&old, &added_enum_value).compatibility,
+      Compatibility::Backward
+    );
+    assert_eq!(
+      changes(&old, &added_enum_value),
+      vec![change("#/properties/kind/enum", "enum-widened")]
+    );
+    assert_eq!(
+      diff_schemas(&added_enum_value, &old).compatibility,
+      Compatibility::Forward
+    );
+    assert_eq!(
+      changes(&added_enum_value, &old),
+      vec![change("#/properties/kind/enum", "enum-narrowed")]
+    );
+  }
+}